@@ -1,60 +1,138 @@
-use crate::models::s3::{BucketInfo, S3Config};
+use aws_sdk_s3::Client;
+use tauri::State;
+use tracing::{info, warn};
+
+use crate::error::AppError;
+use crate::models::diagnostics::DiagnosticReport;
+use crate::models::s3::{
+    BucketInfo, BucketPermissions, BucketVersioning, BucketVersioningRequest,
+    ProbePermissionsRequest, S3Config, SetBucketVersioningRequest,
+};
+use crate::services::client_pool::S3ClientPool;
+use crate::services::diagnostics::{describe_error, diagnose};
+use crate::services::permissions;
+use crate::services::profile_store::ProfileStore;
 use crate::services::s3_client::create_s3_client;
+use crate::services::versioning;
 
 /// 测试 S3 连接
+///
+/// `draft` 是配置弹窗中尚未保存的配置，只用于这次测试，客户端不会进入缓存；
+/// 编辑已有配置时，草稿中留空的密钥使用已保存的值。其他命令只接受配置 ID。
 #[tauri::command]
-pub async fn test_s3_connection(config: S3Config) -> Result<String, String> {
-    println!(
-        "Testing connection with config: endpoint={}, region={}",
-        config.endpoint, config.region
+pub async fn test_s3_connection(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    profile_id: Option<String>,
+    draft: Option<S3Config>,
+) -> Result<String, AppError> {
+    let is_draft = draft.is_some();
+    let config = resolve_target(&profiles, profile_id.as_deref(), draft)?;
+    info!(
+        operation = "test_s3_connection",
+        endpoint = %config.endpoint,
+        region = %config.region,
+        "Testing connection"
     );
 
-    let client = create_s3_client(&config).await.map_err(|e| {
-        let error_msg = format!("Failed to create S3 client: {}", e);
-        println!("{}", error_msg);
-        error_msg
+    let client = if is_draft {
+        create_s3_client(&config).await
+    } else {
+        // 测试已保存的配置时总是重新创建客户端，避免沿用旧的连接状态
+        pool.invalidate(&config);
+        pool.get(&config).await
+    }
+    .inspect_err(|e| {
+        warn!(operation = "test_s3_connection", "{}", e);
     })?;
 
-    println!("S3 client created successfully, attempting to list buckets...");
-
     match client.list_buckets().send().await {
         Ok(output) => {
             let bucket_count = output.buckets().len();
-            let success_msg = format!("Connection successful! Found {} buckets", bucket_count);
-            println!("{}", success_msg);
-            Ok(success_msg)
+            info!(
+                operation = "test_s3_connection",
+                bucket_count, "Connection successful"
+            );
+            Ok(format!(
+                "Connection successful! Found {} buckets",
+                bucket_count
+            ))
         }
         Err(e) => {
-            let error_msg = format!("Connection failed: {} (Error type: {:?})", e, e);
-            println!("{}", error_msg);
-
-            // Try to provide more specific error information
-            let detailed_error = {
-                let error_str = e.to_string();
-                if error_str.contains("credentials") || error_str.contains("access") {
-                    "No valid credentials found. Please check your Access Key ID and Secret Access Key.".to_string()
-                } else if error_str.contains("endpoint") || error_str.contains("host") {
-                    "Invalid endpoint URL. Please check your endpoint configuration.".to_string()
-                } else if error_str.contains("region") {
-                    "Invalid region. For R2, try using 'auto' or leave empty.".to_string()
-                } else {
-                    format!(
-                        "Service error: {}. Please check your endpoint URL and credentials.",
-                        e
-                    )
-                }
+            let error = AppError {
+                message: format!("Connection failed: {}", describe_error(&e)),
+                ..AppError::from_sdk("Connection failed", &e)
             };
-
-            Err(detailed_error)
+            warn!(
+                operation = "test_s3_connection",
+                code = ?error.code,
+                request_id = ?error.request_id,
+                "{}",
+                error
+            );
+            Err(error)
         }
     }
 }
 
+/// 逐层诊断连接问题，返回每一步的结果和修复建议
+///
+/// 与 [`test_s3_connection`] 一样，可以诊断尚未保存的 `draft` 配置。
+#[tauri::command]
+pub async fn diagnose_connection(
+    profiles: State<'_, ProfileStore>,
+    profile_id: Option<String>,
+    draft: Option<S3Config>,
+) -> Result<DiagnosticReport, AppError> {
+    let config = resolve_target(&profiles, profile_id.as_deref(), draft)?;
+    Ok(diagnose(&config).await)
+}
+
+/// 要测试的配置：编辑中的草稿（补全已保存的密钥）或已保存的配置
+fn resolve_target(
+    profiles: &ProfileStore,
+    profile_id: Option<&str>,
+    draft: Option<S3Config>,
+) -> Result<S3Config, AppError> {
+    match (draft, profile_id) {
+        (Some(draft), Some(id)) => profiles.resolve_draft(id, draft),
+        (Some(draft), None) => Ok(draft),
+        (None, _) => profiles.resolve(profile_id),
+    }
+}
+
+/// 探测存储桶或前缀的列举、读取、写入、删除、ACL 和策略权限
+#[tauri::command]
+pub async fn probe_permissions(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: ProbePermissionsRequest,
+) -> Result<BucketPermissions, AppError> {
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+
+    permissions::probe(&client, &request.bucket_name, request.prefix.as_deref()).await
+}
+
+/// 清空缓存的 S3 客户端，例如删除配置或退出登录后
+#[tauri::command]
+pub fn clear_s3_client_cache(pool: State<'_, S3ClientPool>) {
+    pool.clear();
+}
+
 /// 列出所有存储桶
 #[tauri::command]
-pub async fn list_buckets(config: S3Config) -> Result<Vec<BucketInfo>, String> {
-    let client = create_s3_client(&config).await.map_err(|e| e.to_string())?;
+pub async fn list_buckets(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    profile_id: Option<String>,
+) -> Result<Vec<BucketInfo>, AppError> {
+    let config = profiles.resolve(profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+    fetch_buckets(&client).await
+}
 
+async fn fetch_buckets(client: &Client) -> Result<Vec<BucketInfo>, AppError> {
     match client.list_buckets().send().await {
         Ok(output) => {
             let buckets = output.buckets();
@@ -67,52 +145,77 @@ pub async fn list_buckets(config: S3Config) -> Result<Vec<BucketInfo>, String> {
                 .collect();
             Ok(bucket_list)
         }
-        Err(e) => Err(format!("Failed to list buckets: {}", e)),
+        Err(e) => Err(AppError::from_sdk("Failed to list buckets", &e)),
     }
 }
 
+/// 读取存储桶的版本控制状态
+#[tauri::command]
+pub async fn get_bucket_versioning(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: BucketVersioningRequest,
+) -> Result<BucketVersioning, AppError> {
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+    versioning::get_versioning(&client, &request.bucket_name).await
+}
+
+/// 开启或暂停存储桶的版本控制，返回修改后的状态
+///
+/// 暂停后已有的版本会保留，新写入的对象不再产生新版本。
+#[tauri::command]
+pub async fn set_bucket_versioning(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: SetBucketVersioningRequest,
+) -> Result<BucketVersioning, AppError> {
+    info!(
+        operation = "set_bucket_versioning",
+        bucket = %request.bucket_name,
+        enabled = request.enabled,
+        "Updating bucket versioning"
+    );
+
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+    versioning::set_versioning(&client, &request.bucket_name, request.enabled).await?;
+    versioning::get_versioning(&client, &request.bucket_name).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::ErrorCode;
+    use crate::test_support::{create_test_config, MockS3};
 
-    fn create_test_config() -> S3Config {
-        S3Config {
-            endpoint: "https://test.r2.cloudflarestorage.com".to_string(),
-            region: "auto".to_string(),
-            access_key_id: "test_access_key".to_string(),
-            secret_access_key: "test_secret_key".to_string(),
-            bucket: None,
-            custom_path: None,
-        }
-    }
+    #[tokio::test]
+    async fn test_fetch_buckets() {
+        let server = MockS3::start().await;
+        server.put("photos", "a.jpg", b"jpeg");
+        server.put("backups", "db.sql", b"sql");
+        let client = server.client().await;
 
-    #[test]
-    fn test_bucket_info_creation() {
-        let bucket = BucketInfo {
-            name: "test-bucket".to_string(),
-            creation_date: Some("2023-01-01T00:00:00Z".to_string()),
-        };
-        assert_eq!(bucket.name, "test-bucket");
-        assert!(bucket.creation_date.is_some());
+        let buckets = fetch_buckets(&client).await.unwrap();
+        let names: Vec<&str> = buckets.iter().map(|bucket| bucket.name.as_str()).collect();
+        assert_eq!(names, ["backups", "photos"]);
+        assert_eq!(
+            buckets[0].creation_date.as_deref(),
+            Some("2024-01-01T00:00:00Z")
+        );
     }
 
-    #[test]
-    fn test_r2_config_validation() {
-        // Test typical R2 configuration
-        let r2_config = S3Config {
-            endpoint: "https://account-id.r2.cloudflarestorage.com".to_string(),
-            region: "auto".to_string(),
-            access_key_id: "test_key".to_string(),
-            secret_access_key: "test_secret".to_string(),
-            bucket: None,
-            custom_path: None,
-        };
-
-        // Validate that endpoint is a valid URL
-        assert!(r2_config.endpoint.starts_with("https://"));
-        assert!(r2_config.endpoint.contains(".r2.cloudflarestorage.com"));
-        assert_eq!(r2_config.region, "auto");
-        assert!(!r2_config.access_key_id.is_empty());
-        assert!(!r2_config.secret_access_key.is_empty());
+    #[tokio::test]
+    async fn test_fetch_buckets_unreachable() {
+        let mut config = create_test_config();
+        config.endpoint = "http://127.0.0.1:1".to_string();
+        config.network.max_attempts = Some(1);
+        let client = crate::services::s3_client::create_s3_client(&config)
+            .await
+            .unwrap();
+
+        let error = fetch_buckets(&client).await.unwrap_err();
+        assert_eq!(error.code, ErrorCode::Network);
+        assert!(error.retryable);
     }
 }
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::error::AppError;
+use crate::models::profile::CredentialStatus;
+use crate::services::client_pool::S3ClientPool;
+use crate::services::credential_store::CredentialStore;
+use crate::services::logging;
+use crate::services::transfer_manager::TransferManager;
+
+/// 获取凭证存储的后端和锁定状态
+#[tauri::command]
+pub fn get_credential_status(credentials: State<'_, Arc<CredentialStore>>) -> CredentialStatus {
+    credentials.status()
+}
+
+/// 用主密码解锁加密凭证文件，第一次调用时设置主密码
+///
+/// 解锁后启动因锁定而等待的传输任务。
+#[tauri::command]
+pub fn unlock_credentials(
+    credentials: State<'_, Arc<CredentialStore>>,
+    transfers: State<'_, Arc<TransferManager>>,
+    password: String,
+) -> Result<CredentialStatus, AppError> {
+    credentials.unlock(&password)?;
+    transfers.schedule();
+    Ok(credentials.status())
+}
+
+/// 立即锁定凭证存储
+#[tauri::command]
+pub fn lock_credentials(
+    pool: State<'_, S3ClientPool>,
+    credentials: State<'_, Arc<CredentialStore>>,
+) -> CredentialStatus {
+    credentials.lock();
+    // 缓存的客户端持有密钥，锁定后不能继续使用
+    pool.clear();
+    logging::clear_secrets();
+    credentials.status()
+}
+
+/// 设置空闲多久后自动锁定（秒），0 表示不自动锁定
+#[tauri::command]
+pub fn set_auto_lock_timeout(
+    credentials: State<'_, Arc<CredentialStore>>,
+    seconds: u64,
+) -> Result<CredentialStatus, AppError> {
+    credentials.set_auto_lock(seconds)?;
+    Ok(credentials.status())
+}
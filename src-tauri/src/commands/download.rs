@@ -1,65 +1,80 @@
+use std::path::PathBuf;
+
+use tauri::State;
 use tauri_plugin_dialog::DialogExt;
 
+use crate::error::AppError;
 use crate::models::s3::DownloadFileRequest;
-use crate::services::s3_client::create_s3_client;
+use crate::models::transfer::TransferDirection;
+use crate::services::client_pool::S3ClientPool;
+use crate::services::download::ObjectDownloader;
+use crate::services::profile_store::ProfileStore;
+use crate::services::progress::{new_transfer_id, ProgressReporter};
 
 /// 下载文件到本地
+///
+/// 内容先流式写入 `.part` 临时文件，连接中断时通过 Range 请求续传，
+/// 校验大小和 ETag 后再重命名为用户选择的文件名。
 #[tauri::command]
 pub async fn download_file(
     app: tauri::AppHandle,
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
     request: DownloadFileRequest,
-) -> Result<String, String> {
-    // 显示文件保存对话框
+) -> Result<String, AppError> {
+    let path = pick_download_path(&app, &request.default_file_name)?;
+    let path = path.as_path();
+
+    // 创建S3客户端
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+
+    let downloader = ObjectDownloader::new(client, &request.bucket_name, &request.object_key);
+    let remote = downloader.head().await?;
+
+    let transfer_id = request
+        .transfer_id
+        .clone()
+        .unwrap_or_else(|| new_transfer_id(TransferDirection::Download));
+    let progress = ProgressReporter::new(
+        app.clone(),
+        transfer_id,
+        TransferDirection::Download,
+        &request.bucket_name,
+        &request.object_key,
+        remote.size,
+    );
+
+    // 下载到临时文件，完成后移动到用户选择的位置
+    match downloader.download(&remote, path, &progress).await {
+        Ok(()) => {
+            progress.complete();
+            Ok(path.display().to_string())
+        }
+        Err(e) => {
+            progress.fail(&e);
+            Err(e)
+        }
+    }
+}
+
+/// 显示文件保存对话框，返回用户选择的保存路径
+pub(crate) fn pick_download_path(
+    app: &tauri::AppHandle,
+    default_file_name: &str,
+) -> Result<PathBuf, AppError> {
     let file_path = app
         .dialog()
         .file()
-        .set_file_name(&request.default_file_name)
+        .set_file_name(default_file_name)
         .blocking_save_file();
 
     let file_path = match file_path {
         Some(path) => path,
-        None => return Err("用户取消了文件保存".to_string()),
+        None => return Err(AppError::cancelled("File save was cancelled")),
     };
 
-    // 创建S3客户端并生成预签名URL
-    let client = create_s3_client(&request.config)
-        .await
-        .map_err(|e| e.to_string())?;
-
-    let expires_in = std::time::Duration::from_secs(3600); // 1小时
-    let presigning_config = aws_sdk_s3::presigning::PresigningConfig::expires_in(expires_in)
-        .map_err(|e| format!("Failed to create presigning config: {}", e))?;
-
-    let presigned_request = client
-        .get_object()
-        .bucket(&request.bucket_name)
-        .key(&request.object_key)
-        .presigned(presigning_config)
-        .await
-        .map_err(|e| format!("Failed to generate presigned URL: {}", e))?;
-
-    let download_url = presigned_request.uri().to_string();
-
-    // 下载文件内容
-    let response = reqwest::get(&download_url)
-        .await
-        .map_err(|e| format!("Failed to download file: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!(
-            "Download failed with status: {}",
-            response.status()
-        ));
-    }
-
-    let content = response
-        .bytes()
-        .await
-        .map_err(|e| format!("Failed to read response content: {}", e))?;
-
-    // 保存文件到用户选择的位置
-    let path = file_path.as_path().ok_or("无效的文件路径")?;
-    std::fs::write(path, content).map_err(|e| format!("Failed to write file: {}", e))?;
-
-    Ok(path.display().to_string())
+    file_path
+        .into_path()
+        .map_err(|_| AppError::invalid_input("Invalid file path"))
 }
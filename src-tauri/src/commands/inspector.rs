@@ -0,0 +1,39 @@
+use crate::commands::download::pick_download_path;
+use crate::error::AppError;
+use crate::models::inspector::HttpInspectorState;
+use crate::services::inspector::{inspector, to_har};
+
+/// 获取请求记录的开关状态和已记录的请求
+#[tauri::command]
+pub fn get_http_inspector() -> HttpInspectorState {
+    inspector().state()
+}
+
+/// 打开或关闭请求记录，对已创建的客户端立即生效
+#[tauri::command]
+pub fn set_http_inspector_enabled(enabled: bool) {
+    inspector().set_enabled(enabled);
+    tracing::info!(
+        operation = "set_http_inspector_enabled",
+        enabled,
+        "HTTP inspector toggled"
+    );
+}
+
+#[tauri::command]
+pub fn clear_http_exchanges() {
+    inspector().clear();
+}
+
+/// 将已记录的请求导出为 HAR 文件，返回保存的路径
+#[tauri::command]
+pub fn export_http_har(app: tauri::AppHandle) -> Result<String, AppError> {
+    let har = to_har(&inspector().exchanges());
+    let content = serde_json::to_string_pretty(&har)
+        .map_err(|e| AppError::internal(format!("Failed to serialize HAR: {}", e)))?;
+
+    let path = pick_download_path(&app, "snowy-oss.har")?;
+    std::fs::write(&path, content).map_err(|e| AppError::io("Failed to write file", &e))?;
+
+    Ok(path.display().to_string())
+}
@@ -0,0 +1,12 @@
+use crate::error::AppError;
+use crate::models::log::{LogEntry, LogQuery};
+use crate::services::logging::{log_dir, read_entries};
+
+/// 按级别和操作筛选最近的日志，密钥和签名已在写入时替换，可以直接附在问题报告中
+#[tauri::command]
+pub fn get_logs(app: tauri::AppHandle, query: LogQuery) -> Result<Vec<LogEntry>, AppError> {
+    match log_dir(&app) {
+        Some(dir) => read_entries(&dir, &query),
+        None => Ok(Vec::new()),
+    }
+}
@@ -0,0 +1,111 @@
+use tauri::State;
+use tracing::info;
+
+use crate::error::AppError;
+use crate::models::s3::{
+    GetObjectMetadataRequest, ObjectMetadata, UpdateObjectMetadataRequest,
+    UpdatePrefixMetadataRequest, UpdatePrefixMetadataResult,
+};
+use crate::models::transfer::TransferDirection;
+use crate::services::client_pool::S3ClientPool;
+use crate::services::copy::{self, ObjectLocation};
+use crate::services::delete::collect_prefix_targets;
+use crate::services::metadata;
+use crate::services::profile_store::ProfileStore;
+use crate::services::progress::{new_transfer_id, ProgressReporter};
+
+/// 读取对象的完整元数据，包括用户元数据、服务端加密设置和版本 ID
+#[tauri::command]
+pub async fn get_object_metadata(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: GetObjectMetadataRequest,
+) -> Result<ObjectMetadata, AppError> {
+    info!(
+        operation = "get_object_metadata",
+        bucket = %request.bucket_name,
+        key = %request.object_key,
+        "Reading object metadata"
+    );
+
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+    let location = ObjectLocation::new(&request.bucket_name, &request.object_key);
+    metadata::head_metadata(&client, &location, request.version_id.as_deref()).await
+}
+
+/// 修改对象的元数据，通过把对象复制到自身并替换元数据实现
+///
+/// 返回修改后的元数据。
+#[tauri::command]
+pub async fn update_object_metadata(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: UpdateObjectMetadataRequest,
+) -> Result<ObjectMetadata, AppError> {
+    info!(
+        operation = "update_object_metadata",
+        bucket = %request.bucket_name,
+        key = %request.object_key,
+        "Updating object metadata"
+    );
+
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+    let location = ObjectLocation::new(&request.bucket_name, &request.object_key);
+    metadata::update_object(&client, &location, &request.changes).await?;
+    metadata::head_metadata(&client, &location, None).await
+}
+
+/// 批量修改前缀下所有对象的元数据
+///
+/// 单个对象失败不影响其他对象，结果中列出失败的对象。
+/// 通过 `transfer-progress` 事件按对象数报告进度。
+#[tauri::command]
+pub async fn update_prefix_metadata(
+    app: tauri::AppHandle,
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: UpdatePrefixMetadataRequest,
+) -> Result<UpdatePrefixMetadataResult, AppError> {
+    info!(
+        operation = "update_prefix_metadata",
+        bucket = %request.bucket_name,
+        prefix = %request.prefix,
+        "Updating metadata under prefix"
+    );
+
+    if request.prefix.is_empty() {
+        return Err(AppError::invalid_input("Prefix cannot be empty"));
+    }
+    // 先校验修改内容，避免每个对象都因同一个错误失败
+    metadata::apply_changes(&mut ObjectMetadata::default(), &request.changes)?;
+
+    let prefix = copy::normalize_prefix(&request.prefix);
+
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+
+    let transfer_id = request
+        .transfer_id
+        .clone()
+        .unwrap_or_else(|| new_transfer_id(TransferDirection::Batch));
+    let targets = collect_prefix_targets(&client, &request.bucket_name, &prefix, false).await?;
+    let progress = ProgressReporter::new(
+        app,
+        transfer_id,
+        TransferDirection::Batch,
+        &request.bucket_name,
+        &prefix,
+        targets.len() as u64,
+    );
+
+    Ok(metadata::update_prefix_metadata(
+        &client,
+        &request.bucket_name,
+        &targets,
+        &request.changes,
+        &progress,
+    )
+    .await)
+}
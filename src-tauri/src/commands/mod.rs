@@ -1,4 +1,14 @@
 pub mod bucket;
+pub mod credential;
 pub mod download;
+pub mod inspector;
+pub mod log;
+pub mod metadata;
+pub mod migrate;
 pub mod object;
+pub mod profile;
+pub mod provider;
+pub mod tagging;
+pub mod transfer;
 pub mod upload;
+pub mod versioning;
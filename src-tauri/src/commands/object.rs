@@ -1,94 +1,469 @@
+use aws_sdk_s3::Client;
+use tauri::State;
+use tracing::{info, warn};
+
+use crate::error::AppError;
 use crate::models::s3::{
-    DeleteObjectsRequest, GetPresignedUrlRequest, ListObjectsRequest, S3Object,
+    CopyObjectRequest, CopyPrefixRequest, CopyPrefixResult, DeleteObjectsRequest,
+    DeleteObjectsResult, DeletePrefixRequest, DeletePrefixResult, GetPresignedUrlRequest,
+    ListObjectsPage, ListObjectsRequest, S3Folder, S3Object,
+};
+use crate::models::transfer::TransferDirection;
+use crate::services::client_pool::S3ClientPool;
+use crate::services::copy::{self, ObjectLocation};
+use crate::services::delete::{
+    collect_prefix_targets, delete_targets, is_versioned, total_size, DeleteTarget,
 };
-use crate::services::s3_client::create_s3_client;
+use crate::services::profile_store::ProfileStore;
+use crate::services::progress::{new_transfer_id, ProgressReporter};
+use crate::services::tagging;
 
 /// 列出存储桶中的对象
+///
+/// 自动跟随续传令牌读取所有分页，不会在 1000 个键处截断。
 #[tauri::command]
-pub async fn list_objects(request: ListObjectsRequest) -> Result<Vec<S3Object>, String> {
-    println!(
-        "list_objects called with bucket_name: '{}', prefix: {:?}",
-        request.bucket_name, request.prefix
+pub async fn list_objects(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: ListObjectsRequest,
+) -> Result<Vec<S3Object>, AppError> {
+    info!(
+        operation = "list_objects",
+        bucket = %request.bucket_name,
+        prefix = ?request.prefix,
+        "Listing objects"
     );
 
     if request.bucket_name.is_empty() {
-        return Err("Bucket name cannot be empty".to_string());
+        return Err(AppError::invalid_input("Bucket name cannot be empty"));
     }
 
-    let client = create_s3_client(&request.config)
-        .await
-        .map_err(|e| e.to_string())?;
-
-    let mut s3_request = client.list_objects_v2().bucket(&request.bucket_name);
-
-    if let Some(prefix) = request.prefix {
-        s3_request = s3_request.prefix(prefix);
-    }
-
-    match s3_request.send().await {
-        Ok(output) => {
-            let objects = output.contents();
-            let object_list: Vec<S3Object> = objects
-                .iter()
-                .map(|obj| S3Object {
-                    key: obj.key().unwrap_or_default().to_string(),
-                    size: obj.size(),
-                    last_modified: obj.last_modified().map(|date| date.to_string()),
-                    etag: obj.e_tag().map(|tag| tag.to_string()),
-                    storage_class: obj.storage_class().map(|class| class.as_str().to_string()),
-                })
-                .collect();
-            Ok(object_list)
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+
+    fetch_all_objects(&client, &request).await.inspect_err(|e| {
+        warn!(
+            operation = "list_objects",
+            bucket = %request.bucket_name,
+            code = ?e.code,
+            request_id = ?e.request_id,
+            "{}",
+            e
+        )
+    })
+}
+
+/// 从请求的续传令牌开始，依次读取所有分页
+async fn fetch_all_objects(
+    client: &Client,
+    request: &ListObjectsRequest,
+) -> Result<Vec<S3Object>, AppError> {
+    let mut object_list = Vec::new();
+    let mut continuation_token = request.continuation_token.clone();
+
+    loop {
+        let page = fetch_objects_page(client, request, continuation_token).await?;
+        object_list.extend(page.objects);
+
+        match page.next_continuation_token {
+            Some(token) if page.is_truncated => continuation_token = Some(token),
+            _ => break,
         }
-        Err(e) => Err(format!("Failed to list objects: {}", e)),
     }
+
+    Ok(object_list)
 }
 
-/// 删除多个对象
+/// 分页列出对象和文件夹
+///
+/// 传入 `delimiter`（通常为 "/"）时，子目录以文件夹形式返回；
+/// 调用方通过 `nextContinuationToken` 请求下一页。
 #[tauri::command]
-pub async fn delete_objects(request: DeleteObjectsRequest) -> Result<String, String> {
-    let client = create_s3_client(&request.config)
+pub async fn list_objects_page(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: ListObjectsRequest,
+) -> Result<ListObjectsPage, AppError> {
+    if request.bucket_name.is_empty() {
+        return Err(AppError::invalid_input("Bucket name cannot be empty"));
+    }
+
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+
+    fetch_objects_page(&client, &request, request.continuation_token.clone()).await
+}
+
+/// 发送一次 `list_objects_v2` 请求
+async fn fetch_objects_page(
+    client: &Client,
+    request: &ListObjectsRequest,
+    continuation_token: Option<String>,
+) -> Result<ListObjectsPage, AppError> {
+    let output = client
+        .list_objects_v2()
+        .bucket(&request.bucket_name)
+        .set_prefix(request.prefix.clone())
+        .set_delimiter(request.delimiter.clone())
+        .set_max_keys(request.max_keys)
+        .set_continuation_token(continuation_token)
+        .send()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| AppError::from_sdk("Failed to list objects", &e))?;
 
-    let mut deleted_count = 0;
-    let mut errors = Vec::new();
+    let mut objects: Vec<S3Object> = output
+        .contents()
+        .iter()
+        .map(|obj| S3Object {
+            key: obj.key().unwrap_or_default().to_string(),
+            size: obj.size(),
+            last_modified: obj.last_modified().map(|date| date.to_string()),
+            etag: obj.e_tag().map(|tag| tag.to_string()),
+            storage_class: obj.storage_class().map(|class| class.as_str().to_string()),
+            tags: None,
+        })
+        .collect();
 
-    for key in request.object_keys {
-        match client
-            .delete_object()
-            .bucket(&request.bucket_name)
-            .key(&key)
-            .send()
-            .await
-        {
-            Ok(_) => deleted_count += 1,
-            Err(e) => errors.push(format!("Failed to delete {}: {}", key, e)),
+    // 按标签过滤时本页可能少于 maxKeys 个对象，续传令牌仍按原始列举返回
+    if request.include_tags || request.tag_filter.is_some() {
+        tagging::attach_tags(client, &request.bucket_name, &mut objects).await;
+    }
+    if let Some(filter) = &request.tag_filter {
+        objects.retain(|object| {
+            object
+                .tags
+                .as_deref()
+                .is_some_and(|tags| filter.matches(tags))
+        });
+    }
+
+    let delimiter = request.delimiter.as_deref().unwrap_or("/");
+    let folders = output
+        .common_prefixes()
+        .iter()
+        .filter_map(|common_prefix| common_prefix.prefix())
+        .map(|prefix| S3Folder {
+            prefix: prefix.to_string(),
+            name: folder_name(prefix, delimiter).to_string(),
+        })
+        .collect();
+
+    Ok(ListObjectsPage {
+        objects,
+        folders,
+        is_truncated: output.is_truncated().unwrap_or(false),
+        next_continuation_token: output.next_continuation_token().map(|t| t.to_string()),
+    })
+}
+
+/// 取文件夹前缀的最后一级名称，例如 "photos/2024/" -> "2024"
+pub fn folder_name<'a>(prefix: &'a str, delimiter: &str) -> &'a str {
+    let trimmed = prefix.strip_suffix(delimiter).unwrap_or(prefix);
+    if delimiter.is_empty() {
+        return trimmed;
+    }
+    match trimmed.rfind(delimiter) {
+        Some(index) => &trimmed[index + delimiter.len()..],
+        None => trimmed,
+    }
+}
+
+/// 删除多个对象
+///
+/// 使用 DeleteObjects 批量接口，每批最多 1000 个键，返回已删除的键和逐个列出的失败键。
+#[tauri::command]
+pub async fn delete_objects(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: DeleteObjectsRequest,
+) -> Result<DeleteObjectsResult, AppError> {
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+
+    run_delete_objects(&client, &request).await
+}
+
+async fn run_delete_objects(
+    client: &Client,
+    request: &DeleteObjectsRequest,
+) -> Result<DeleteObjectsResult, AppError> {
+    let targets: Vec<DeleteTarget> = request
+        .object_keys
+        .iter()
+        .map(DeleteTarget::from_key)
+        .collect();
+
+    let outcome = delete_targets(client, &request.bucket_name, &targets).await?;
+
+    let deleted = request
+        .object_keys
+        .iter()
+        .filter(|key| !outcome.errors.iter().any(|error| error.key == **key))
+        .cloned()
+        .collect();
+    Ok(DeleteObjectsResult {
+        deleted,
+        errors: outcome.errors,
+    })
+}
+
+/// 删除前缀下的所有对象
+///
+/// 开启过版本控制的存储桶会同时删除所有历史版本和删除标记。
+/// `dryRun` 为 true 时只返回匹配的数量和总大小，不做任何删除。
+#[tauri::command]
+pub async fn delete_prefix(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: DeletePrefixRequest,
+) -> Result<DeletePrefixResult, AppError> {
+    if request.prefix.is_empty() {
+        return Err(AppError::invalid_input("Prefix cannot be empty"));
+    }
+
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+
+    run_delete_prefix(&client, &request).await
+}
+
+/// 按文件夹语义删除前缀：`thumbs` 只匹配 `thumbs/` 下的对象，不会波及 `thumbs2/`
+async fn run_delete_prefix(
+    client: &Client,
+    request: &DeletePrefixRequest,
+) -> Result<DeletePrefixResult, AppError> {
+    let prefix = copy::normalize_prefix(&request.prefix);
+    let versioned = is_versioned(client, &request.bucket_name).await?;
+    let targets = collect_prefix_targets(client, &request.bucket_name, &prefix, versioned).await?;
+
+    let mut result = DeletePrefixResult {
+        dry_run: request.dry_run,
+        versioned,
+        object_count: targets.len(),
+        total_size: total_size(&targets),
+        deleted_count: 0,
+        errors: Vec::new(),
+    };
+
+    if !request.dry_run {
+        let outcome = delete_targets(client, &request.bucket_name, &targets).await?;
+        result.deleted_count = outcome.deleted;
+        result.errors = outcome.errors;
+    }
+
+    Ok(result)
+}
+
+/// 在服务端复制对象，保留元数据、标签和内容类型
+///
+/// 可以复制到同一服务上的其他存储桶，超过 5 GiB 的对象使用分片复制。
+#[tauri::command]
+pub async fn copy_object(
+    app: tauri::AppHandle,
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: CopyObjectRequest,
+) -> Result<String, AppError> {
+    transfer_object(app, &pool, &profiles, request, false).await
+}
+
+/// 移动或重命名对象：复制到新位置后删除源对象
+#[tauri::command]
+pub async fn move_object(
+    app: tauri::AppHandle,
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: CopyObjectRequest,
+) -> Result<String, AppError> {
+    transfer_object(app, &pool, &profiles, request, true).await
+}
+
+async fn transfer_object(
+    app: tauri::AppHandle,
+    pool: &S3ClientPool,
+    profiles: &ProfileStore,
+    request: CopyObjectRequest,
+    delete_source: bool,
+) -> Result<String, AppError> {
+    let operation = if delete_source {
+        "move_object"
+    } else {
+        "copy_object"
+    };
+    let source = ObjectLocation::new(&request.source_bucket, &request.source_key);
+    let dest = ObjectLocation::new(
+        request
+            .dest_bucket
+            .as_deref()
+            .unwrap_or(&request.source_bucket),
+        &request.dest_key,
+    );
+    info!(
+        operation,
+        source = %source.copy_source(),
+        dest = %dest.copy_source(),
+        "Copying object"
+    );
+
+    if source.key.is_empty() || dest.key.is_empty() {
+        return Err(AppError::invalid_input("Object key cannot be empty"));
+    }
+    if source == dest {
+        return Err(AppError::invalid_input(
+            "Source and destination are the same object",
+        ));
+    }
+
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+
+    let size = copy::object_size(&client, &source).await?;
+    if !request.overwrite && copy::object_exists(&client, &dest).await? {
+        return Err(AppError::conflict(format!(
+            "Object {} already exists in {}",
+            dest.key, dest.bucket
+        )));
+    }
+
+    let transfer_id = request
+        .transfer_id
+        .clone()
+        .unwrap_or_else(|| new_transfer_id(TransferDirection::Copy));
+    let progress = ProgressReporter::new(
+        app,
+        transfer_id,
+        TransferDirection::Copy,
+        &dest.bucket,
+        &dest.key,
+        size,
+    );
+
+    let result = match copy::copy_object(&client, &source, &dest, size, Some(&progress)).await {
+        Ok(()) if delete_source => copy::delete_object(&client, &source).await,
+        other => other,
+    };
+    match result {
+        Ok(()) => {
+            progress.complete();
+            let verb = if delete_source { "moved" } else { "copied" };
+            Ok(format!(
+                "Successfully {} '{}' to '{}' in {}",
+                verb, source.key, dest.key, dest.bucket
+            ))
+        }
+        Err(e) => {
+            warn!(operation, code = ?e.code, request_id = ?e.request_id, "{}", e);
+            progress.fail(&e);
+            Err(e)
         }
     }
+}
 
-    if errors.is_empty() {
-        Ok(format!("Successfully deleted {} objects", deleted_count))
+/// 复制前缀下的所有对象到另一个前缀
+///
+/// 目标前缀必须为空。任何对象复制失败时删除已复制的对象，结果中列出失败和回滚情况。
+/// 通过 `transfer-progress` 事件按字节数报告进度。
+#[tauri::command]
+pub async fn copy_prefix(
+    app: tauri::AppHandle,
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: CopyPrefixRequest,
+) -> Result<CopyPrefixResult, AppError> {
+    transfer_prefix(app, &pool, &profiles, request, false).await
+}
+
+/// 移动或重命名前缀（“文件夹”）
+///
+/// 全部对象复制成功后才删除源对象；复制失败时回滚，源对象保持不变。
+#[tauri::command]
+pub async fn move_prefix(
+    app: tauri::AppHandle,
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: CopyPrefixRequest,
+) -> Result<CopyPrefixResult, AppError> {
+    transfer_prefix(app, &pool, &profiles, request, true).await
+}
+
+async fn transfer_prefix(
+    app: tauri::AppHandle,
+    pool: &S3ClientPool,
+    profiles: &ProfileStore,
+    request: CopyPrefixRequest,
+    delete_source: bool,
+) -> Result<CopyPrefixResult, AppError> {
+    let operation = if delete_source {
+        "move_prefix"
     } else {
-        Err(format!(
-            "Deleted {} objects, but encountered errors: {}",
-            deleted_count,
-            errors.join(", ")
-        ))
+        "copy_prefix"
+    };
+    let source = ObjectLocation::new(
+        &request.source_bucket,
+        copy::normalize_prefix(&request.source_prefix),
+    );
+    let dest = ObjectLocation::new(
+        request
+            .dest_bucket
+            .as_deref()
+            .unwrap_or(&request.source_bucket),
+        copy::normalize_prefix(&request.dest_prefix),
+    );
+    info!(
+        operation,
+        source = %source.copy_source(),
+        dest = %dest.copy_source(),
+        "Copying prefix"
+    );
+
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+
+    let transfer_id = request
+        .transfer_id
+        .clone()
+        .unwrap_or_else(|| new_transfer_id(TransferDirection::Copy));
+    // 进度事件以目标前缀标识这次操作，总大小为所有源对象大小之和
+    let targets = copy::plan_prefix_copy(&client, &source, &dest).await?;
+    let progress = ProgressReporter::new(
+        app,
+        transfer_id,
+        TransferDirection::Copy,
+        &dest.bucket,
+        &dest.key,
+        total_size(&targets),
+    );
+
+    let result = copy::copy_prefix(&client, &source, &dest, &targets, delete_source, &progress)
+        .await
+        .inspect_err(|e| progress.fail(e))?;
+    if result.rolled_back {
+        warn!(
+            operation,
+            failed = result.failed.len(),
+            rollback_errors = result.rollback_errors.len(),
+            "Copy failed and was rolled back"
+        );
     }
+    Ok(result)
 }
 
 /// 获取预签名 URL
 #[tauri::command]
-pub async fn get_presigned_url(request: GetPresignedUrlRequest) -> Result<String, String> {
-    let client = create_s3_client(&request.config)
-        .await
-        .map_err(|e| e.to_string())?;
+pub async fn get_presigned_url(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: GetPresignedUrlRequest,
+) -> Result<String, AppError> {
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
 
     let expires_in = std::time::Duration::from_secs(request.expires_in_seconds.unwrap_or(3600));
 
     let presigning_config = aws_sdk_s3::presigning::PresigningConfig::expires_in(expires_in)
-        .map_err(|e| format!("Failed to create presigning config: {}", e))?;
+        .map_err(|e| {
+            AppError::invalid_input(format!("Failed to create presigning config: {}", e))
+        })?;
 
     let presigned_request = client
         .get_object()
@@ -96,7 +471,7 @@ pub async fn get_presigned_url(request: GetPresignedUrlRequest) -> Result<String
         .key(&request.object_key)
         .presigned(presigning_config)
         .await
-        .map_err(|e| format!("Failed to generate presigned URL: {}", e))?;
+        .map_err(|e| AppError::from_sdk("Failed to generate presigned URL", &e))?;
 
     Ok(presigned_request.uri().to_string())
 }
@@ -104,42 +479,155 @@ pub async fn get_presigned_url(request: GetPresignedUrlRequest) -> Result<String
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::s3::S3Config;
-
-    fn create_test_config() -> S3Config {
-        S3Config {
-            endpoint: "https://test.r2.cloudflarestorage.com".to_string(),
-            region: "auto".to_string(),
-            access_key_id: "test_access_key".to_string(),
-            secret_access_key: "test_secret_key".to_string(),
-            bucket: None,
-            custom_path: None,
+    use crate::test_support::MockS3;
+
+    fn create_list_request(prefix: &str, delimiter: Option<&str>) -> ListObjectsRequest {
+        ListObjectsRequest {
+            profile_id: None,
+            bucket_name: "photos".to_string(),
+            prefix: Some(prefix.to_string()),
+            delimiter: delimiter.map(str::to_string),
+            continuation_token: None,
+            max_keys: None,
+            include_tags: false,
+            tag_filter: None,
         }
     }
 
-    #[test]
-    fn test_s3_object_creation() {
-        let object = S3Object {
-            key: "test-file.txt".to_string(),
-            size: Some(1024),
-            last_modified: Some("2023-01-01T00:00:00Z".to_string()),
-            etag: Some("d41d8cd98f00b204e9800998ecf8427e".to_string()),
-            storage_class: Some("STANDARD".to_string()),
+    #[tokio::test]
+    async fn test_fetch_all_objects_follows_continuation_tokens() {
+        let server = MockS3::start().await;
+        server.set_page_size(2);
+        for key in [
+            "2024/a.jpg",
+            "2024/b.jpg",
+            "2024/c.jpg",
+            "2024/d.jpg",
+            "2024/e.jpg",
+        ] {
+            server.put("photos", key, b"data");
+        }
+        server.put("photos", "2025/f.jpg", b"data");
+        let client = server.client().await;
+
+        let objects = fetch_all_objects(&client, &create_list_request("2024/", None))
+            .await
+            .unwrap();
+        let keys: Vec<&str> = objects.iter().map(|object| object.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            [
+                "2024/a.jpg",
+                "2024/b.jpg",
+                "2024/c.jpg",
+                "2024/d.jpg",
+                "2024/e.jpg"
+            ]
+        );
+        assert_eq!(objects[0].size, Some(4));
+        assert_eq!(objects[0].storage_class.as_deref(), Some("STANDARD"));
+        assert!(objects[0].etag.is_some());
+
+        // Three pages, each after the first resuming from the previous token
+        let tokens: Vec<Option<String>> = server
+            .requests()
+            .iter()
+            .filter(|request| request.is_list())
+            .map(|request| request.param("continuation-token").map(str::to_string))
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                None,
+                Some("2024/b.jpg".to_string()),
+                Some("2024/d.jpg".to_string())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_objects_page_returns_folders_and_token() {
+        let server = MockS3::start().await;
+        server.set_page_size(2);
+        for key in ["a.jpg", "2023/x.jpg", "2024/y.jpg", "2024/z.jpg"] {
+            server.put("photos", key, b"data");
+        }
+        let client = server.client().await;
+        let request = create_list_request("", Some("/"));
+
+        let page = fetch_objects_page(&client, &request, None).await.unwrap();
+        let folders: Vec<&str> = page.folders.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(folders, ["2023", "2024"]);
+        assert!(page.objects.is_empty());
+        assert!(page.is_truncated);
+
+        let page = fetch_objects_page(&client, &request, page.next_continuation_token)
+            .await
+            .unwrap();
+        assert!(page.folders.is_empty());
+        assert_eq!(page.objects.len(), 1);
+        assert_eq!(page.objects[0].key, "a.jpg");
+        assert!(!page.is_truncated);
+        assert_eq!(page.next_continuation_token, None);
+    }
+
+    #[tokio::test]
+    async fn test_delete_prefix_only_matches_folder() {
+        let server = MockS3::start().await;
+        for key in ["thumbs/1.jpg", "thumbs/2.jpg", "thumbs2/4.jpg", "thumbsX"] {
+            server.put("photos", key, b"jpeg");
+        }
+        let client = server.client().await;
+        let mut request = DeletePrefixRequest {
+            profile_id: None,
+            bucket_name: "photos".to_string(),
+            prefix: "thumbs".to_string(),
+            dry_run: true,
         };
-        assert_eq!(object.key, "test-file.txt");
-        assert_eq!(object.size, Some(1024));
+
+        let result = run_delete_prefix(&client, &request).await.unwrap();
+        assert_eq!(result.object_count, 2);
+        assert_eq!(result.total_size, 8);
+        assert_eq!(result.deleted_count, 0);
+
+        request.dry_run = false;
+        let result = run_delete_prefix(&client, &request).await.unwrap();
+        assert_eq!(result.deleted_count, 2);
+        assert!(result.errors.is_empty());
+        assert_eq!(server.keys("photos"), ["thumbs2/4.jpg", "thumbsX"]);
     }
 
-    #[test]
-    fn test_list_objects_request_validation() {
-        let config = create_test_config();
-        let request = ListObjectsRequest {
-            config,
-            bucket_name: "".to_string(),
-            prefix: None,
+    #[tokio::test]
+    async fn test_delete_objects_reports_failed_keys() {
+        let server = MockS3::start().await;
+        for key in ["a.jpg", "b.jpg", "c.jpg"] {
+            server.put("photos", key, b"jpeg");
+        }
+        server.protect("photos", "b.jpg");
+        let client = server.client().await;
+        let request = DeleteObjectsRequest {
+            profile_id: None,
+            bucket_name: "photos".to_string(),
+            object_keys: vec![
+                "a.jpg".to_string(),
+                "b.jpg".to_string(),
+                "c.jpg".to_string(),
+            ],
         };
 
-        // Validate empty bucket name would fail
-        assert!(request.bucket_name.is_empty());
+        let result = run_delete_objects(&client, &request).await.unwrap();
+        assert_eq!(result.deleted, ["a.jpg", "c.jpg"]);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].key, "b.jpg");
+        assert_eq!(result.errors[0].code.as_deref(), Some("AccessDenied"));
+        assert_eq!(server.keys("photos"), ["b.jpg"]);
+    }
+
+    #[test]
+    fn test_folder_name() {
+        assert_eq!(folder_name("photos/", "/"), "photos");
+        assert_eq!(folder_name("photos/2024/", "/"), "2024");
+        assert_eq!(folder_name("a/b/c", "/"), "c");
+        assert_eq!(folder_name("2024-01|", "|"), "2024-01");
     }
 }
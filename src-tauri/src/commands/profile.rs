@@ -0,0 +1,188 @@
+use std::path::Path;
+
+use tauri::{Manager, State};
+
+use crate::commands::download::pick_download_path;
+use crate::error::AppError;
+use crate::models::profile::{
+    ConflictStrategy, ExportFormat, ExportProfilesRequest, ExportProfilesResult, ImportCandidate,
+    ImportProfilesRequest, ImportProfilesResult, ImportSource, ProfileInput, ProfileSummary,
+};
+use crate::models::s3::CredentialSource;
+use crate::services::client_pool::S3ClientPool;
+use crate::services::logging;
+use crate::services::profile_import::{export_aws, export_rclone, load_source, preview};
+use crate::services::profile_store::ProfileStore;
+
+/// 列出保存的连接配置，密钥不会返回给前端
+#[tauri::command]
+pub fn list_profiles(profiles: State<'_, ProfileStore>) -> Vec<ProfileSummary> {
+    profiles.list()
+}
+
+/// 当前选中的连接配置
+#[tauri::command]
+pub fn get_selected_profile(profiles: State<'_, ProfileStore>) -> Option<ProfileSummary> {
+    profiles.selected()
+}
+
+/// 新建连接配置
+#[tauri::command]
+pub fn create_profile(
+    profiles: State<'_, ProfileStore>,
+    input: ProfileInput,
+) -> Result<ProfileSummary, AppError> {
+    profiles.create(input)
+}
+
+/// 更新连接配置，密钥留空时保留原有密钥
+#[tauri::command]
+pub fn update_profile(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    profile_id: String,
+    input: ProfileInput,
+) -> Result<ProfileSummary, AppError> {
+    let (summary, previous) = profiles.update(&profile_id, input)?;
+
+    // 旧配置创建的客户端不再可用
+    pool.invalidate(&previous);
+
+    Ok(summary)
+}
+
+/// 删除连接配置
+#[tauri::command]
+pub fn delete_profile(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    profile_id: String,
+) -> Result<(), AppError> {
+    let removed = profiles.delete(&profile_id)?;
+    pool.invalidate(&removed);
+    logging::forget_secrets(&removed);
+    Ok(())
+}
+
+/// 选中连接配置，未指定配置的命令会使用它
+#[tauri::command]
+pub fn select_profile(
+    profiles: State<'_, ProfileStore>,
+    profile_id: String,
+) -> Result<ProfileSummary, AppError> {
+    profiles.select(&profile_id)
+}
+
+/// 使用 MFA 验证码为 AssumeRole 配置建立会话
+///
+/// 验证码不会被保存，临时凭证过期后需要重新输入。
+#[tauri::command]
+pub async fn start_mfa_session(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    profile_id: String,
+    mfa_code: String,
+) -> Result<(), AppError> {
+    let mut config = profiles.resolve(Some(&profile_id))?;
+    match &mut config.credential_source {
+        CredentialSource::AssumeRole { mfa_code: code, .. } => *code = Some(mfa_code),
+        _ => return Err(AppError::invalid_input("Profile does not assume a role")),
+    }
+
+    // 验证码不参与配置指纹，重建客户端后后续调用会复用这个会话
+    pool.invalidate(&config);
+    pool.get(&config).await?;
+
+    Ok(())
+}
+
+/// 读取 AWS、rclone 或 s3cmd 配置文件，返回可导入的配置列表
+#[tauri::command]
+pub fn preview_profile_import(
+    app: tauri::AppHandle,
+    profiles: State<'_, ProfileStore>,
+    source: ImportSource,
+    path: Option<String>,
+) -> Result<Vec<ImportCandidate>, AppError> {
+    let inputs = read_import_source(&app, source, path.as_deref())?;
+    Ok(preview(&inputs, &profiles.names()))
+}
+
+/// 从 AWS、rclone 或 s3cmd 配置文件导入配置
+#[tauri::command]
+pub fn import_profiles(
+    app: tauri::AppHandle,
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: ImportProfilesRequest,
+) -> Result<ImportProfilesResult, AppError> {
+    let mut inputs = read_import_source(&app, request.source, request.path.as_deref())?;
+    if let Some(names) = &request.names {
+        inputs.retain(|input| names.contains(&input.name));
+    }
+
+    let result = profiles.import(inputs, request.conflict);
+
+    // 被覆盖的配置可能还有缓存的客户端
+    if request.conflict == ConflictStrategy::Overwrite && !result.imported.is_empty() {
+        pool.clear();
+    }
+
+    Ok(result)
+}
+
+/// 将配置导出为 AWS 或 rclone 配置文件，返回保存的路径和无法导出的配置
+///
+/// 文件包含访问密钥，直接由后端写入，不经过前端。
+#[tauri::command]
+pub fn export_profiles(
+    app: tauri::AppHandle,
+    profiles: State<'_, ProfileStore>,
+    request: ExportProfilesRequest,
+) -> Result<ExportProfilesResult, AppError> {
+    let exported = profiles.export(&request.profile_ids)?;
+    let ((content, skipped), default_file_name) = match request.format {
+        ExportFormat::Aws => (export_aws(&exported), "config"),
+        ExportFormat::Rclone => (export_rclone(&exported), "rclone.conf"),
+    };
+    if !skipped.is_empty() && skipped.len() == exported.len() {
+        return Err(AppError::invalid_input(format!(
+            "None of the selected profiles can be exported: {}",
+            skipped[0].reason
+        )));
+    }
+
+    let path = pick_download_path(&app, default_file_name)?;
+    write_private_file(&path, &content)?;
+
+    Ok(ExportProfilesResult {
+        path: path.display().to_string(),
+        skipped,
+    })
+}
+
+fn read_import_source(
+    app: &tauri::AppHandle,
+    source: ImportSource,
+    path: Option<&str>,
+) -> Result<Vec<ProfileInput>, AppError> {
+    let home_dir = app
+        .path()
+        .home_dir()
+        .map_err(|e| AppError::internal(format!("Failed to resolve home directory: {}", e)))?;
+    load_source(source, path.map(Path::new), &home_dir)
+}
+
+/// 写入只有当前用户可读的文件
+fn write_private_file(path: &Path, content: &str) -> Result<(), AppError> {
+    std::fs::write(path, content).map_err(|e| AppError::io("Failed to write file", &e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+            .map_err(|e| AppError::io("Failed to set file permissions", &e))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use crate::error::AppError;
+use crate::models::provider::{BuildProviderConfigRequest, ProviderMatch, ProviderPreset};
+use crate::models::s3::{CompatibilityDefaults, S3Config};
+use crate::services::provider;
+
+/// 列出内置的服务商预设
+#[tauri::command]
+pub fn list_provider_presets() -> Vec<ProviderPreset> {
+    provider::PRESETS.to_vec()
+}
+
+/// 根据预设、区域和模板参数生成配置，访问密钥需要用户另行填写
+#[tauri::command]
+pub fn build_config_from_preset(request: BuildProviderConfigRequest) -> Result<S3Config, AppError> {
+    provider::build_config(&request)
+}
+
+/// 根据粘贴的服务地址识别服务商
+#[tauri::command]
+pub fn detect_provider(endpoint: String) -> Option<ProviderMatch> {
+    provider::detect_provider(&endpoint)
+}
+
+/// 根据服务地址返回推荐的寻址方式和兼容性设置，用于填充新建配置的表单
+#[tauri::command]
+pub fn get_compatibility_defaults(endpoint: String) -> CompatibilityDefaults {
+    provider::compatibility_defaults(&endpoint)
+}
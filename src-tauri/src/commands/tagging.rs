@@ -0,0 +1,141 @@
+use tauri::State;
+use tracing::info;
+
+use crate::error::AppError;
+use crate::models::s3::{
+    ObjectTag, ObjectTagsRequest, PutObjectTagsRequest, TagObjectsRequest, TagObjectsResult,
+};
+use crate::models::transfer::TransferDirection;
+use crate::services::client_pool::S3ClientPool;
+use crate::services::copy::{self, ObjectLocation};
+use crate::services::delete::collect_prefix_targets;
+use crate::services::profile_store::ProfileStore;
+use crate::services::progress::{new_transfer_id, ProgressReporter};
+use crate::services::tagging::{self, TagChanges};
+
+/// 读取对象的标签
+#[tauri::command]
+pub async fn get_object_tags(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: ObjectTagsRequest,
+) -> Result<Vec<ObjectTag>, AppError> {
+    info!(
+        operation = "get_object_tags",
+        bucket = %request.bucket_name,
+        key = %request.object_key,
+        "Reading object tags"
+    );
+
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+    let location = ObjectLocation::new(&request.bucket_name, &request.object_key);
+    tagging::get_tags(&client, &location, request.version_id.as_deref()).await
+}
+
+/// 替换对象的全部标签，标签列表为空时删除所有标签
+#[tauri::command]
+pub async fn put_object_tags(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: PutObjectTagsRequest,
+) -> Result<(), AppError> {
+    info!(
+        operation = "put_object_tags",
+        bucket = %request.bucket_name,
+        key = %request.object_key,
+        count = request.tags.len(),
+        "Updating object tags"
+    );
+
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+    let location = ObjectLocation::new(&request.bucket_name, &request.object_key);
+    tagging::put_tags(
+        &client,
+        &location,
+        request.version_id.as_deref(),
+        &request.tags,
+    )
+    .await
+}
+
+/// 删除对象的全部标签
+#[tauri::command]
+pub async fn delete_object_tags(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: ObjectTagsRequest,
+) -> Result<(), AppError> {
+    info!(
+        operation = "delete_object_tags",
+        bucket = %request.bucket_name,
+        key = %request.object_key,
+        "Deleting object tags"
+    );
+
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+    let location = ObjectLocation::new(&request.bucket_name, &request.object_key);
+    tagging::delete_tags(&client, &location, request.version_id.as_deref()).await
+}
+
+/// 批量修改选中对象和前缀下所有对象的标签
+///
+/// 单个对象失败不影响其他对象，结果中列出失败的对象。
+/// 通过 `transfer-progress` 事件按对象数报告进度。
+#[tauri::command]
+pub async fn tag_objects(
+    app: tauri::AppHandle,
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: TagObjectsRequest,
+) -> Result<TagObjectsResult, AppError> {
+    info!(
+        operation = "tag_objects",
+        bucket = %request.bucket_name,
+        keys = request.object_keys.len(),
+        prefix = ?request.prefix,
+        "Tagging objects"
+    );
+
+    let changes = TagChanges {
+        tags: request.tags,
+        remove_keys: request.remove_keys,
+        replace: request.replace,
+    };
+    changes.validate()?;
+    if request.prefix.as_deref() == Some("") {
+        return Err(AppError::invalid_input("Prefix cannot be empty"));
+    }
+
+    let prefix = request.prefix.as_deref().map(copy::normalize_prefix);
+
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+
+    let mut keys = request.object_keys;
+    if let Some(prefix) = &prefix {
+        let targets = collect_prefix_targets(&client, &request.bucket_name, prefix, false).await?;
+        keys.extend(targets.into_iter().map(|target| target.key));
+    }
+    keys.sort();
+    keys.dedup();
+    if keys.is_empty() {
+        return Err(AppError::invalid_input("No objects to tag"));
+    }
+
+    let transfer_id = request
+        .transfer_id
+        .unwrap_or_else(|| new_transfer_id(TransferDirection::Batch));
+    let progress = ProgressReporter::new(
+        app,
+        transfer_id,
+        TransferDirection::Batch,
+        &request.bucket_name,
+        prefix.as_deref().unwrap_or_default(),
+        keys.len() as u64,
+    );
+
+    Ok(tagging::tag_objects(&client, &request.bucket_name, &keys, &changes, &progress).await)
+}
@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::commands::download::pick_download_path;
+use crate::commands::upload::pick_upload_requests;
+use crate::error::AppError;
+use crate::models::s3::{DownloadFileRequest, UploadFileRequest};
+use crate::models::transfer::{TransferJobInfo, TransferTask};
+use crate::services::profile_store::ProfileStore;
+use crate::services::transfer_manager::TransferManager;
+
+/// 将文件上传加入传输队列，返回任务 ID
+#[tauri::command]
+pub async fn enqueue_upload(
+    manager: State<'_, Arc<TransferManager>>,
+    profiles: State<'_, ProfileStore>,
+    mut request: UploadFileRequest,
+) -> Result<String, AppError> {
+    request.profile_id = pin_profile(&profiles, request.profile_id)?;
+    std::fs::metadata(&request.file_path).map_err(|e| AppError::io("Failed to read file", &e))?;
+
+    Ok(manager.enqueue(TransferTask::Upload {
+        request,
+        checkpoint: None,
+    }))
+}
+
+/// 通过文件选择对话框将多个文件加入上传队列
+#[tauri::command]
+pub async fn enqueue_uploads_with_dialog(
+    app: tauri::AppHandle,
+    manager: State<'_, Arc<TransferManager>>,
+    profiles: State<'_, ProfileStore>,
+    profile_id: Option<String>,
+    bucket_name: String,
+    prefix: Option<String>,
+) -> Result<Vec<String>, AppError> {
+    let profile_id = pin_profile(&profiles, profile_id)?;
+    let upload_requests =
+        pick_upload_requests(&app, profile_id.as_deref(), &bucket_name, prefix.as_deref())?;
+
+    Ok(upload_requests
+        .into_iter()
+        .map(|request| {
+            manager.enqueue(TransferTask::Upload {
+                request,
+                checkpoint: None,
+            })
+        })
+        .collect())
+}
+
+/// 选择保存位置后将下载加入传输队列，返回任务 ID
+#[tauri::command]
+pub async fn enqueue_download(
+    app: tauri::AppHandle,
+    manager: State<'_, Arc<TransferManager>>,
+    profiles: State<'_, ProfileStore>,
+    mut request: DownloadFileRequest,
+) -> Result<String, AppError> {
+    request.profile_id = pin_profile(&profiles, request.profile_id)?;
+    let path = pick_download_path(&app, &request.default_file_name)?;
+
+    Ok(manager.enqueue(TransferTask::Download {
+        request,
+        dest_path: path.display().to_string(),
+    }))
+}
+
+/// 入队时记下所用的配置 ID
+///
+/// 任务可能在之后才执行，不能依赖届时选中的配置；未指定时固定为当前选中的配置。
+fn pin_profile(
+    profiles: &ProfileStore,
+    profile_id: Option<String>,
+) -> Result<Option<String>, AppError> {
+    if profile_id.is_some() {
+        return Ok(profile_id);
+    }
+    profiles
+        .selected()
+        .map(|profile| Some(profile.id))
+        .ok_or_else(|| AppError::invalid_input("No profile selected"))
+}
+
+/// 列出传输队列中的任务
+#[tauri::command]
+pub fn list_transfers(manager: State<'_, Arc<TransferManager>>) -> Vec<TransferJobInfo> {
+    manager.list()
+}
+
+/// 暂停传输任务
+#[tauri::command]
+pub fn pause_transfer(
+    manager: State<'_, Arc<TransferManager>>,
+    transfer_id: String,
+) -> Result<(), AppError> {
+    manager.pause(&transfer_id)
+}
+
+/// 继续暂停或失败的传输任务
+#[tauri::command]
+pub fn resume_transfer(
+    manager: State<'_, Arc<TransferManager>>,
+    transfer_id: String,
+) -> Result<(), AppError> {
+    manager.resume(&transfer_id)
+}
+
+/// 取消传输任务
+#[tauri::command]
+pub fn cancel_transfer(
+    manager: State<'_, Arc<TransferManager>>,
+    transfer_id: String,
+) -> Result<(), AppError> {
+    manager.cancel(&transfer_id)
+}
+
+/// 设置同时运行的传输任务数
+#[tauri::command]
+pub fn set_transfer_concurrency(manager: State<'_, Arc<TransferManager>>, max_concurrent: usize) {
+    manager.set_max_concurrent(max_concurrent);
+}
+
+/// 清除已完成的传输任务
+#[tauri::command]
+pub fn clear_completed_transfers(manager: State<'_, Arc<TransferManager>>) {
+    manager.clear_completed();
+}
@@ -1,43 +1,72 @@
+use std::path::Path;
+
+use tauri::State;
 use tauri_plugin_dialog::DialogExt;
 
-use crate::models::s3::{S3Config, UploadFileBytesRequest, UploadFileRequest};
-use crate::services::s3_client::create_s3_client;
+use crate::error::AppError;
+use crate::models::s3::{UploadFileBytesRequest, UploadFileRequest};
+use crate::models::transfer::TransferDirection;
+use crate::services::client_pool::S3ClientPool;
+use crate::services::profile_store::ProfileStore;
+use crate::services::progress::{new_transfer_id, ProgressReporter};
+use crate::services::upload::{upload_path, UploadControl};
 use crate::utils::content_type::guess_content_type;
 
 /// 上传文件到 S3
+///
+/// 超过分片阈值的文件使用分片上传，内容从磁盘流式读取，不会整体载入内存。
+/// 上传过程中通过 `transfer-progress` 事件报告进度。
 #[tauri::command]
-pub async fn upload_file(request: UploadFileRequest) -> Result<String, String> {
-    // 读取文件内容
-    let file_content =
-        std::fs::read(&request.file_path).map_err(|e| format!("Failed to read file: {}", e))?;
+pub async fn upload_file(
+    app: tauri::AppHandle,
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: UploadFileRequest,
+) -> Result<String, AppError> {
+    let path = Path::new(&request.file_path);
 
     // 获取文件大小
-    let file_size = file_content.len();
+    let file_size = std::fs::metadata(path)
+        .map_err(|e| AppError::io("Failed to read file", &e))?
+        .len();
 
     // 创建 S3 客户端
-    let client = create_s3_client(&request.config)
-        .await
-        .map_err(|e| e.to_string())?;
-
-    // 构建上传请求
-    let mut put_request = client
-        .put_object()
-        .bucket(&request.bucket_name)
-        .key(&request.object_key)
-        .body(file_content.into());
-
-    // 如果指定了 content_type，则设置
-    if let Some(content_type) = &request.content_type {
-        put_request = put_request.content_type(content_type);
-    }
-
-    // 执行上传
-    match put_request.send().await {
-        Ok(_) => Ok(format!(
-            "Successfully uploaded file '{}' ({} bytes) to {}",
-            request.object_key, file_size, request.bucket_name
-        )),
-        Err(e) => Err(format!("Upload failed: {}", e)),
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+
+    let transfer_id = request
+        .transfer_id
+        .clone()
+        .unwrap_or_else(|| new_transfer_id(TransferDirection::Upload));
+    let progress = ProgressReporter::new(
+        app,
+        transfer_id,
+        TransferDirection::Upload,
+        &request.bucket_name,
+        &request.object_key,
+        file_size,
+    );
+
+    match upload_path(
+        client,
+        &request,
+        file_size,
+        &progress,
+        UploadControl::default(),
+    )
+    .await
+    {
+        Ok(()) => {
+            progress.complete();
+            Ok(format!(
+                "Successfully uploaded file '{}' ({} bytes) to {}",
+                request.object_key, file_size, request.bucket_name
+            ))
+        }
+        Err(e) => {
+            progress.fail(&e);
+            Err(e)
+        }
     }
 }
 
@@ -45,10 +74,60 @@ pub async fn upload_file(request: UploadFileRequest) -> Result<String, String> {
 #[tauri::command]
 pub async fn upload_files_with_dialog(
     app: tauri::AppHandle,
-    config: S3Config,
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    profile_id: Option<String>,
     bucket_name: String,
     prefix: Option<String>,
-) -> Result<Vec<String>, String> {
+) -> Result<Vec<String>, AppError> {
+    let upload_requests =
+        pick_upload_requests(&app, profile_id.as_deref(), &bucket_name, prefix.as_deref())?;
+
+    let mut results = Vec::new();
+    let mut upload_count = 0;
+    let mut errors = Vec::new();
+
+    for upload_request in upload_requests {
+        let file_name = upload_request
+            .object_key
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        // 执行上传
+        match upload_file(app.clone(), pool.clone(), profiles.clone(), upload_request).await {
+            Ok(message) => {
+                upload_count += 1;
+                results.push(format!("✓ {}", message));
+            }
+            Err(error) => {
+                errors.push(format!("✗ Failed to upload '{}': {}", file_name, error));
+            }
+        }
+    }
+
+    // 汇总结果
+    let mut summary = vec![format!("Uploaded {} files", upload_count)];
+    summary.extend(results);
+
+    if !errors.is_empty() {
+        summary.push("Errors:".to_string());
+        summary.extend(errors);
+    }
+
+    Ok(summary)
+}
+
+/// 显示文件选择对话框，为选中的每个文件构建上传请求
+///
+/// 用户取消选择时返回空列表。
+pub(crate) fn pick_upload_requests(
+    app: &tauri::AppHandle,
+    profile_id: Option<&str>,
+    bucket_name: &str,
+    prefix: Option<&str>,
+) -> Result<Vec<UploadFileRequest>, AppError> {
     // 显示文件选择对话框，允许多选
     let file_paths = app
         .dialog()
@@ -79,19 +158,19 @@ pub async fn upload_files_with_dialog(
         None => return Ok(vec![]), // 用户取消选择
     };
 
-    let mut results = Vec::new();
-    let mut upload_count = 0;
-    let mut errors = Vec::new();
+    let mut requests = Vec::new();
 
     for file_path in file_paths {
-        let path_buf = file_path.as_path().ok_or("无效的文件路径")?;
+        let path_buf = file_path
+            .as_path()
+            .ok_or_else(|| AppError::invalid_input("Invalid file path"))?;
         let file_name = path_buf
             .file_name()
             .and_then(|name| name.to_str())
-            .ok_or("无法获取文件名")?;
+            .ok_or_else(|| AppError::invalid_input("Failed to get file name"))?;
 
         // 构建对象键
-        let object_key = if let Some(ref p) = prefix {
+        let object_key = if let Some(p) = prefix {
             format!("{}{}", p, file_name)
         } else {
             file_name.to_string()
@@ -100,49 +179,35 @@ pub async fn upload_files_with_dialog(
         // 推断 Content-Type
         let content_type = guess_content_type(file_name);
 
-        // 创建上传请求
-        let upload_request = UploadFileRequest {
-            config: config.clone(),
-            bucket_name: bucket_name.clone(),
-            object_key: object_key.clone(),
+        requests.push(UploadFileRequest {
+            profile_id: profile_id.map(|id| id.to_string()),
+            bucket_name: bucket_name.to_string(),
+            object_key,
             file_path: path_buf.display().to_string(),
             content_type: Some(content_type),
-        };
-
-        // 执行上传
-        match upload_file(upload_request).await {
-            Ok(message) => {
-                upload_count += 1;
-                results.push(format!("✓ {}", message));
-            }
-            Err(error) => {
-                errors.push(format!("✗ 上传 '{}' 失败: {}", file_name, error));
-            }
-        }
+            multipart_threshold: None,
+            part_size: None,
+            max_concurrency: None,
+            transfer_id: None,
+        });
     }
 
-    // 汇总结果
-    let mut summary = vec![format!("上传完成: {} 个文件成功", upload_count)];
-    summary.extend(results);
-
-    if !errors.is_empty() {
-        summary.push("错误:".to_string());
-        summary.extend(errors);
-    }
-
-    Ok(summary)
+    Ok(requests)
 }
 
 /// 通过字节数组上传文件
 #[tauri::command]
-pub async fn upload_file_from_bytes(request: UploadFileBytesRequest) -> Result<String, String> {
+pub async fn upload_file_from_bytes(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: UploadFileBytesRequest,
+) -> Result<String, AppError> {
     // 获取文件大小
     let file_size = request.file_bytes.len();
 
     // 创建 S3 客户端
-    let client = create_s3_client(&request.config)
-        .await
-        .map_err(|e| e.to_string())?;
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
 
     // 构建上传请求
     let put_request = client
@@ -158,6 +223,6 @@ pub async fn upload_file_from_bytes(request: UploadFileBytesRequest) -> Result<S
             "Successfully uploaded '{}' ({} bytes) to {}",
             request.file_name, file_size, request.bucket_name
         )),
-        Err(e) => Err(format!("Upload failed: {}", e)),
+        Err(e) => Err(AppError::from_sdk("Upload failed", &e)),
     }
 }
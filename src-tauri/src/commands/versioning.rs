@@ -0,0 +1,166 @@
+use tauri::State;
+use tracing::info;
+
+use super::object::folder_name;
+use crate::error::AppError;
+use crate::models::s3::{
+    DeleteObjectVersionsRequest, DeleteObjectVersionsResult, ListObjectVersionsRequest,
+    ObjectMetadata, ObjectVersionsPage, RestoreObjectVersionRequest, S3Folder,
+    UndeleteObjectRequest,
+};
+use crate::services::client_pool::S3ClientPool;
+use crate::services::delete::{delete_targets, DeleteTarget};
+use crate::services::profile_store::ProfileStore;
+use crate::services::versioning;
+
+/// 分页列出对象的所有版本和删除标记
+///
+/// 调用方通过 `nextKeyMarker` 和 `nextVersionIdMarker` 请求下一页。
+#[tauri::command]
+pub async fn list_object_versions(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: ListObjectVersionsRequest,
+) -> Result<ObjectVersionsPage, AppError> {
+    info!(
+        operation = "list_object_versions",
+        bucket = %request.bucket_name,
+        prefix = ?request.prefix,
+        "Listing object versions"
+    );
+
+    if request.bucket_name.is_empty() {
+        return Err(AppError::invalid_input("Bucket name cannot be empty"));
+    }
+
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+
+    let output = client
+        .list_object_versions()
+        .bucket(&request.bucket_name)
+        .set_prefix(request.prefix.clone())
+        .set_delimiter(request.delimiter.clone())
+        .set_key_marker(request.key_marker.clone())
+        .set_version_id_marker(request.version_id_marker.clone())
+        .set_max_keys(request.max_keys)
+        .send()
+        .await
+        .map_err(|e| AppError::from_sdk("Failed to list object versions", &e))?;
+
+    let delimiter = request.delimiter.as_deref().unwrap_or("/");
+    let folders = output
+        .common_prefixes()
+        .iter()
+        .filter_map(|common_prefix| common_prefix.prefix())
+        .map(|prefix| S3Folder {
+            prefix: prefix.to_string(),
+            name: folder_name(prefix, delimiter).to_string(),
+        })
+        .collect();
+
+    Ok(ObjectVersionsPage {
+        versions: versioning::collect_versions(&output),
+        folders,
+        is_truncated: output.is_truncated().unwrap_or(false),
+        next_key_marker: output.next_key_marker().map(str::to_string),
+        next_version_id_marker: output.next_version_id_marker().map(str::to_string),
+    })
+}
+
+/// 把历史版本恢复为最新版本
+///
+/// 通过把该版本复制到同一个键实现，原有的版本都会保留；返回新版本的元数据。
+#[tauri::command]
+pub async fn restore_object_version(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: RestoreObjectVersionRequest,
+) -> Result<ObjectMetadata, AppError> {
+    info!(
+        operation = "restore_object_version",
+        bucket = %request.bucket_name,
+        key = %request.object_key,
+        version_id = %request.version_id,
+        "Restoring object version"
+    );
+
+    if request.version_id.is_empty() {
+        return Err(AppError::invalid_input("Version id cannot be empty"));
+    }
+
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+    versioning::restore_version(
+        &client,
+        &request.bucket_name,
+        &request.object_key,
+        &request.version_id,
+    )
+    .await
+}
+
+/// 恢复已删除的对象：删除最新的删除标记
+#[tauri::command]
+pub async fn undelete_object(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: UndeleteObjectRequest,
+) -> Result<ObjectMetadata, AppError> {
+    info!(
+        operation = "undelete_object",
+        bucket = %request.bucket_name,
+        key = %request.object_key,
+        "Removing delete marker"
+    );
+
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+    versioning::undelete(&client, &request.bucket_name, &request.object_key).await
+}
+
+/// 永久删除指定的版本或删除标记，删除后无法恢复
+#[tauri::command]
+pub async fn delete_object_versions(
+    pool: State<'_, S3ClientPool>,
+    profiles: State<'_, ProfileStore>,
+    request: DeleteObjectVersionsRequest,
+) -> Result<DeleteObjectVersionsResult, AppError> {
+    info!(
+        operation = "delete_object_versions",
+        bucket = %request.bucket_name,
+        count = request.versions.len(),
+        "Permanently deleting object versions"
+    );
+
+    // 缺少版本 ID 时 DeleteObjects 只会添加删除标记，不是永久删除
+    if let Some(version) = request
+        .versions
+        .iter()
+        .find(|version| version.version_id.is_empty())
+    {
+        return Err(AppError::invalid_input(format!(
+            "Version id is required to permanently delete {}",
+            version.key
+        )));
+    }
+
+    let config = profiles.resolve(request.profile_id.as_deref())?;
+    let client = pool.get(&config).await?;
+
+    let targets: Vec<DeleteTarget> = request
+        .versions
+        .iter()
+        .map(|version| DeleteTarget {
+            key: version.key.clone(),
+            version_id: Some(version.version_id.clone()),
+            size: 0,
+        })
+        .collect();
+    let outcome = delete_targets(&client, &request.bucket_name, &targets).await?;
+
+    Ok(DeleteObjectVersionsResult {
+        deleted_count: outcome.deleted,
+        errors: outcome.errors,
+    })
+}
@@ -0,0 +1,271 @@
+use std::fmt;
+
+use aws_sdk_s3::config::http::HttpResponse;
+use aws_sdk_s3::error::{DisplayErrorContext, ProvideErrorMetadata, SdkError};
+use aws_sdk_s3::operation::RequestId;
+use serde::{Deserialize, Serialize};
+
+/// 错误类别，前端根据它显示本地化的提示
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ErrorCode {
+    /// 用户取消了操作，或传输被暂停、取消而中断
+    Cancelled,
+    /// 没有权限执行操作
+    AccessDenied,
+    /// 访问密钥无效、签名不匹配或临时凭证已过期
+    InvalidCredentials,
+    /// 存储桶、对象、配置或任务不存在
+    NotFound,
+    /// 与已有资源或当前状态冲突，例如重名或对象在下载过程中被修改
+    Conflict,
+    /// 请求过于频繁，服务端要求降速
+    Throttled,
+    /// 无法连接服务器或连接中断
+    Network,
+    /// 请求超时
+    Timeout,
+    /// 参数或配置不正确
+    InvalidInput,
+    /// 凭证库已锁定，需要先输入主密码
+    Locked,
+    /// 主密码错误
+    WrongPassword,
+    /// 读写本地文件失败
+    Io,
+    /// 服务端返回的其他错误
+    Service,
+    /// 应用内部错误
+    Internal,
+}
+
+/// 所有命令返回的错误
+///
+/// `message` 是英文的详细信息，用于日志和调试；界面上的提示由前端根据 `code` 生成。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppError {
+    pub code: ErrorCode,
+    pub message: String,
+    /// 服务端返回的错误码，例如 `NoSuchKey`
+    #[serde(rename = "s3Code")]
+    pub s3_code: Option<String>,
+    #[serde(rename = "requestId")]
+    pub request_id: Option<String>,
+    /// HTTP 状态码，请求没有得到响应时为空
+    pub status: Option<u16>,
+    /// 稍后重试是否可能成功
+    pub retryable: bool,
+}
+
+impl AppError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            s3_code: None,
+            request_id: None,
+            status: None,
+            retryable: matches!(
+                code,
+                ErrorCode::Throttled | ErrorCode::Network | ErrorCode::Timeout
+            ),
+        }
+    }
+
+    pub fn cancelled(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Cancelled, message)
+    }
+
+    /// 传输被暂停或取消，已传输的部分保留，之后可以继续
+    pub fn interrupted() -> Self {
+        Self::cancelled("Transfer interrupted")
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotFound, message)
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Conflict, message)
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::InvalidInput, message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+
+    /// 本地文件错误，文件不存在和没有权限单独归类
+    pub fn io(context: &str, error: &std::io::Error) -> Self {
+        let code = match error.kind() {
+            std::io::ErrorKind::NotFound => ErrorCode::NotFound,
+            std::io::ErrorKind::PermissionDenied => ErrorCode::AccessDenied,
+            _ => ErrorCode::Io,
+        };
+        Self::new(code, format!("{}: {}", context, error))
+    }
+
+    /// 根据 SDK 错误的类型、服务端错误码和 HTTP 状态归类，并保留请求 ID
+    pub fn from_sdk<E>(context: &str, error: &SdkError<E, HttpResponse>) -> Self
+    where
+        E: ProvideErrorMetadata + std::error::Error + 'static,
+    {
+        let status = error
+            .raw_response()
+            .map(|response| response.status().as_u16());
+        let code = match error {
+            SdkError::TimeoutError(_) => ErrorCode::Timeout,
+            SdkError::DispatchFailure(failure) if failure.is_timeout() => ErrorCode::Timeout,
+            SdkError::DispatchFailure(failure) if failure.is_user() => ErrorCode::InvalidInput,
+            SdkError::DispatchFailure(_) => ErrorCode::Network,
+            SdkError::ConstructionFailure(_) => ErrorCode::InvalidInput,
+            _ => classify(error.code(), status),
+        };
+        let detail = match error.message() {
+            Some(message) => message.to_string(),
+            None => DisplayErrorContext(error).to_string(),
+        };
+
+        Self {
+            code,
+            message: format!("{}: {}", context, detail),
+            s3_code: error.code().map(str::to_string),
+            request_id: error.request_id().map(str::to_string),
+            status,
+            retryable: is_retryable(code, status),
+        }
+    }
+}
+
+/// 根据服务端错误码和 HTTP 状态归类，错误码优先
+pub fn classify(s3_code: Option<&str>, status: Option<u16>) -> ErrorCode {
+    match s3_code {
+        Some("AccessDenied" | "AllAccessDisabled" | "AccountProblem") => {
+            return ErrorCode::AccessDenied
+        }
+        Some(
+            "InvalidAccessKeyId"
+            | "SignatureDoesNotMatch"
+            | "ExpiredToken"
+            | "InvalidToken"
+            | "TokenRefreshRequired"
+            | "InvalidSecurity",
+        ) => return ErrorCode::InvalidCredentials,
+        Some("NoSuchBucket" | "NoSuchKey" | "NoSuchUpload" | "NoSuchVersion" | "NotFound") => {
+            return ErrorCode::NotFound
+        }
+        Some(
+            "SlowDown"
+            | "Throttling"
+            | "ThrottlingException"
+            | "RequestLimitExceeded"
+            | "TooManyRequestsException",
+        ) => return ErrorCode::Throttled,
+        Some("RequestTimeout") => return ErrorCode::Timeout,
+        Some(
+            "BucketAlreadyExists"
+            | "BucketAlreadyOwnedByYou"
+            | "BucketNotEmpty"
+            | "OperationAborted"
+            | "PreconditionFailed",
+        ) => return ErrorCode::Conflict,
+        Some("InvalidBucketName" | "InvalidArgument" | "InvalidRequest" | "KeyTooLongError") => {
+            return ErrorCode::InvalidInput
+        }
+        _ => {}
+    }
+
+    match status {
+        Some(401) => ErrorCode::InvalidCredentials,
+        Some(403) => ErrorCode::AccessDenied,
+        Some(404) => ErrorCode::NotFound,
+        Some(409 | 412) => ErrorCode::Conflict,
+        Some(429 | 503) => ErrorCode::Throttled,
+        Some(400) => ErrorCode::InvalidInput,
+        _ => ErrorCode::Service,
+    }
+}
+
+/// 网络问题、限流和服务端 5xx 错误可以重试
+fn is_retryable(code: ErrorCode, status: Option<u16>) -> bool {
+    match code {
+        ErrorCode::Throttled | ErrorCode::Network | ErrorCode::Timeout => true,
+        ErrorCode::Service => status.map(|status| status >= 500).unwrap_or(false),
+        _ => false,
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for AppError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_s3_code() {
+        assert_eq!(
+            classify(Some("AccessDenied"), Some(403)),
+            ErrorCode::AccessDenied
+        );
+        assert_eq!(
+            classify(Some("SignatureDoesNotMatch"), Some(403)),
+            ErrorCode::InvalidCredentials
+        );
+        assert_eq!(classify(Some("NoSuchKey"), Some(404)), ErrorCode::NotFound);
+        assert_eq!(classify(Some("SlowDown"), Some(503)), ErrorCode::Throttled);
+        assert_eq!(
+            classify(Some("BucketNotEmpty"), Some(409)),
+            ErrorCode::Conflict
+        );
+    }
+
+    #[test]
+    fn test_classify_status() {
+        // HEAD 请求的错误响应没有正文，只能根据状态码判断
+        assert_eq!(classify(None, Some(403)), ErrorCode::AccessDenied);
+        assert_eq!(classify(None, Some(404)), ErrorCode::NotFound);
+        assert_eq!(classify(None, Some(429)), ErrorCode::Throttled);
+        assert_eq!(classify(None, Some(500)), ErrorCode::Service);
+        assert_eq!(classify(Some("Unknown"), None), ErrorCode::Service);
+    }
+
+    #[test]
+    fn test_retryable() {
+        assert!(AppError::new(ErrorCode::Network, "offline").retryable);
+        assert!(!AppError::cancelled("cancelled").retryable);
+        assert!(is_retryable(ErrorCode::Service, Some(500)));
+        assert!(!is_retryable(ErrorCode::Service, Some(400)));
+    }
+
+    #[test]
+    fn test_io_error() {
+        let error = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+        let error = AppError::io("Failed to read file", &error);
+        assert_eq!(error.code, ErrorCode::NotFound);
+        assert_eq!(error.message, "Failed to read file: missing");
+    }
+
+    #[test]
+    fn test_app_error_serialization() {
+        let error = AppError {
+            s3_code: Some("NoSuchKey".to_string()),
+            request_id: Some("req-1".to_string()),
+            status: Some(404),
+            ..AppError::not_found("Failed to get object metadata: missing")
+        };
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["code"], "notFound");
+        assert_eq!(json["s3Code"], "NoSuchKey");
+        assert_eq!(json["requestId"], "req-1");
+        assert_eq!(json["status"], 404);
+        assert_eq!(json["retryable"], false);
+    }
+}
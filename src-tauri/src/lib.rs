@@ -1,14 +1,55 @@
 // 模块声明
 mod commands;
+mod error;
 mod models;
 mod services;
+#[cfg(test)]
+mod test_support;
 mod utils;
 
+use std::sync::Arc;
+
 // 导入 Tauri 命令
-use commands::bucket::{list_buckets, test_s3_connection};
+use commands::bucket::{
+    clear_s3_client_cache, diagnose_connection, get_bucket_versioning, list_buckets,
+    probe_permissions, set_bucket_versioning, test_s3_connection,
+};
+use commands::credential::{
+    get_credential_status, lock_credentials, set_auto_lock_timeout, unlock_credentials,
+};
 use commands::download::download_file;
-use commands::object::{delete_objects, get_presigned_url, list_objects};
+use commands::inspector::{
+    clear_http_exchanges, export_http_har, get_http_inspector, set_http_inspector_enabled,
+};
+use commands::log::get_logs;
+use commands::metadata::{get_object_metadata, update_object_metadata, update_prefix_metadata};
+use commands::migrate::migrate_objects;
+use commands::object::{
+    copy_object, copy_prefix, delete_objects, delete_prefix, get_presigned_url, list_objects,
+    list_objects_page, move_object, move_prefix,
+};
+use commands::profile::{
+    create_profile, delete_profile, export_profiles, get_selected_profile, import_profiles,
+    list_profiles, preview_profile_import, select_profile, start_mfa_session, update_profile,
+};
+use commands::provider::{
+    build_config_from_preset, detect_provider, get_compatibility_defaults, list_provider_presets,
+};
+use commands::tagging::{delete_object_tags, get_object_tags, put_object_tags, tag_objects};
+use commands::transfer::{
+    cancel_transfer, clear_completed_transfers, enqueue_download, enqueue_upload,
+    enqueue_uploads_with_dialog, list_transfers, pause_transfer, resume_transfer,
+    set_transfer_concurrency,
+};
 use commands::upload::{upload_file, upload_file_from_bytes, upload_files_with_dialog};
+use commands::versioning::{
+    delete_object_versions, list_object_versions, restore_object_version, undelete_object,
+};
+use services::client_pool::S3ClientPool;
+use services::credential_store::CredentialStore;
+use services::profile_store::ProfileStore;
+use services::transfer_manager::TransferManager;
+use tauri::Manager;
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
@@ -23,17 +64,91 @@ pub fn run() {
         .plugin(tauri_plugin_http::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
+        .manage(S3ClientPool::default())
+        .setup(|app| {
+            // 最先初始化日志，后面加载配置时的错误也会写入日志文件
+            services::logging::init(app.handle());
+
+            // 访问密钥保存在系统钥匙串或加密文件中
+            let credentials = Arc::new(CredentialStore::load(app.handle()));
+            credentials.start_auto_lock(app.handle().clone());
+
+            // 连接配置保存在后端，前端只传配置 ID
+            app.manage(ProfileStore::load(app.handle(), credentials.clone()));
+            app.manage(credentials);
+
+            // 加载持久化的传输队列，继续上次未完成的任务（凭证锁定时等到解锁后再开始）
+            let transfer_manager = TransferManager::load(app.handle().clone());
+            transfer_manager.schedule();
+            app.manage(transfer_manager);
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             greet,
             test_s3_connection,
+            diagnose_connection,
+            probe_permissions,
             list_buckets,
+            clear_s3_client_cache,
+            list_profiles,
+            get_selected_profile,
+            create_profile,
+            update_profile,
+            delete_profile,
+            select_profile,
+            start_mfa_session,
+            list_provider_presets,
+            build_config_from_preset,
+            detect_provider,
+            get_compatibility_defaults,
+            preview_profile_import,
+            import_profiles,
+            export_profiles,
+            get_credential_status,
+            unlock_credentials,
+            lock_credentials,
+            set_auto_lock_timeout,
             list_objects,
+            list_objects_page,
             delete_objects,
+            delete_prefix,
+            copy_object,
+            move_object,
+            copy_prefix,
+            move_prefix,
+            migrate_objects,
+            get_object_metadata,
+            update_object_metadata,
+            update_prefix_metadata,
+            get_object_tags,
+            put_object_tags,
+            delete_object_tags,
+            tag_objects,
+            list_object_versions,
+            restore_object_version,
+            undelete_object,
+            delete_object_versions,
+            get_bucket_versioning,
+            set_bucket_versioning,
             get_presigned_url,
             download_file,
             upload_file,
             upload_files_with_dialog,
-            upload_file_from_bytes
+            upload_file_from_bytes,
+            enqueue_upload,
+            enqueue_uploads_with_dialog,
+            enqueue_download,
+            list_transfers,
+            pause_transfer,
+            resume_transfer,
+            cancel_transfer,
+            set_transfer_concurrency,
+            clear_completed_transfers,
+            get_logs,
+            get_http_inspector,
+            set_http_inspector_enabled,
+            clear_http_exchanges,
+            export_http_har
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -42,19 +157,9 @@ pub fn run() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::create_test_config;
     use models::s3::S3Config;
 
-    fn create_test_config() -> S3Config {
-        S3Config {
-            endpoint: "https://test.r2.cloudflarestorage.com".to_string(),
-            region: "auto".to_string(),
-            access_key_id: "test_access_key".to_string(),
-            secret_access_key: "test_secret_key".to_string(),
-            bucket: None,
-            custom_path: None,
-        }
-    }
-
     #[test]
     fn test_s3_config_serialization() {
         let config = create_test_config();
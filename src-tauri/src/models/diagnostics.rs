@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+/// 连接诊断的检查项，按执行顺序排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiagnosticCheck {
+    /// 服务地址格式
+    Url,
+    /// 域名解析
+    Dns,
+    /// TCP 连接
+    Connect,
+    /// TLS 握手和证书
+    Tls,
+    /// 本地时间与服务器 `Date` 头的偏差
+    Clock,
+    /// 签名和访问密钥
+    Signature,
+    /// 区域是否与存储桶所在区域一致
+    Region,
+    /// 路径方式与虚拟主机方式
+    Addressing,
+}
+
+/// 检查结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiagnosticStatus {
+    Passed,
+    /// 不影响使用，但可能导致部分操作失败
+    Warning,
+    Failed,
+    /// 前面的检查失败或条件不满足，未执行
+    Skipped,
+}
+
+/// 单个检查项的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticStep {
+    pub check: DiagnosticCheck,
+    pub status: DiagnosticStatus,
+    pub detail: String,
+    /// 修复建议
+    pub hint: Option<String>,
+    #[serde(rename = "durationMs")]
+    pub duration_ms: u64,
+}
+
+/// 连接诊断报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticReport {
+    /// 没有失败的检查项
+    pub passed: bool,
+    pub steps: Vec<DiagnosticStep>,
+}
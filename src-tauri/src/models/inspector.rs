@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// 一个 HTTP 头
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HttpHeader {
+    pub name: String,
+    pub value: String,
+}
+
+/// 记录的一次 HTTP 请求和响应，签名和密钥已被替换
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HttpExchange {
+    pub id: u64,
+    /// 发送请求的时间（RFC 3339）
+    #[serde(rename = "startedAt")]
+    pub started_at: String,
+    #[serde(rename = "durationMs")]
+    pub duration_ms: u64,
+    pub method: String,
+    pub url: String,
+    /// 签名后实际发送的请求头
+    #[serde(rename = "requestHeaders")]
+    pub request_headers: Vec<HttpHeader>,
+    /// 没有收到响应时为空
+    pub status: Option<u16>,
+    #[serde(rename = "responseHeaders")]
+    pub response_headers: Vec<HttpHeader>,
+    /// 响应体的开头部分，流式响应（例如下载的对象内容）不记录
+    #[serde(rename = "responseBody")]
+    pub response_body: Option<String>,
+    /// 响应体超过记录上限被截断
+    #[serde(rename = "bodyTruncated")]
+    pub body_truncated: bool,
+    /// 请求失败时 SDK 返回的错误
+    pub error: Option<String>,
+}
+
+/// 请求记录的开关状态和已记录的请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpInspectorState {
+    pub enabled: bool,
+    /// 最多保留的记录数，超出后丢弃最早的记录
+    pub capacity: usize,
+    pub exchanges: Vec<HttpExchange>,
+}
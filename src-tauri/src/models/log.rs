@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// 日志级别，按严重程度从低到高排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// 解析日志文件中的级别，例如 `INFO`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "trace" => Some(Self::Trace),
+            "debug" => Some(Self::Debug),
+            "info" => Some(Self::Info),
+            "warn" => Some(Self::Warn),
+            "error" => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+
+/// 日志文件中的一条记录，密钥和签名在写入时已被替换
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub level: LogLevel,
+    pub target: String,
+    /// 产生日志的命令或模块，例如 `list_objects`
+    pub operation: Option<String>,
+    pub message: String,
+    /// 其他结构化字段
+    pub fields: Map<String, Value>,
+}
+
+/// 查询最近日志的条件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LogQuery {
+    /// 最低级别，未指定时返回所有级别
+    #[serde(default)]
+    pub level: Option<LogLevel>,
+    #[serde(default)]
+    pub operation: Option<String>,
+    /// 最多返回的条数，未指定时为 500
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
@@ -1 +1,7 @@
+pub mod diagnostics;
+pub mod inspector;
+pub mod log;
+pub mod profile;
+pub mod provider;
 pub mod s3;
+pub mod transfer;
@@ -0,0 +1,206 @@
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use crate::models::s3::{AddressingStyle, Compatibility, NetworkSettings, S3Config};
+
+/// 保存在后端的连接配置
+///
+/// 访问密钥保存在凭证存储中，`config` 里的密钥字段持久化时为空。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: String,
+    pub name: String,
+    pub config: S3Config,
+    #[serde(rename = "accessKeyHint", default)]
+    pub access_key_hint: String,
+}
+
+/// 保存在凭证存储中的访问密钥
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Credentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy_password: Option<String>,
+}
+
+impl Zeroize for Credentials {
+    fn zeroize(&mut self) {
+        self.access_key_id.zeroize();
+        self.secret_access_key.zeroize();
+        self.session_token.zeroize();
+        self.proxy_password.zeroize();
+    }
+}
+
+/// 凭证存储后端
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CredentialBackend {
+    /// 系统钥匙串（Keychain、Credential Manager、Secret Service）
+    Keyring,
+    /// 主密码加密的本地文件
+    Vault,
+}
+
+/// 凭证存储的状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CredentialStatus {
+    pub backend: CredentialBackend,
+    pub locked: bool,
+    /// 加密文件是否已经设置过主密码
+    pub initialized: bool,
+    /// 空闲多久后自动锁定（秒），0 表示不自动锁定
+    #[serde(rename = "autoLockSeconds")]
+    pub auto_lock_seconds: u64,
+}
+
+/// 创建或更新配置时前端提交的内容
+///
+/// 更新时 `secret_access_key` 和代理密码留空表示保留原值。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileInput {
+    pub name: String,
+    pub config: S3Config,
+}
+
+/// 返回给前端的配置概要，不包含密钥
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileSummary {
+    pub id: String,
+    pub name: String,
+    pub endpoint: String,
+    pub region: String,
+    /// 打码后的 Access Key ID，例如 `AKIA****WXYZ`
+    #[serde(rename = "accessKeyHint")]
+    pub access_key_hint: String,
+    pub bucket: Option<String>,
+    #[serde(rename = "customPath")]
+    pub custom_path: Option<String>,
+    #[serde(rename = "addressingStyle")]
+    pub addressing_style: AddressingStyle,
+    pub compatibility: Compatibility,
+    pub network: NetworkSettings,
+    pub selected: bool,
+}
+
+/// 打码显示访问密钥，只保留首尾各 4 个字符
+pub fn mask_key(key: &str) -> String {
+    let chars: Vec<char> = key.chars().collect();
+    if chars.len() <= 8 {
+        return "*".repeat(chars.len());
+    }
+
+    let head: String = chars[..4].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}****{}", head, tail)
+}
+
+impl Profile {
+    pub fn summary(&self, selected: bool) -> ProfileSummary {
+        ProfileSummary {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            endpoint: self.config.endpoint.clone(),
+            region: self.config.region.clone(),
+            access_key_hint: self.access_key_hint.clone(),
+            bucket: self.config.bucket.clone(),
+            custom_path: self.config.custom_path.clone(),
+            addressing_style: self.config.addressing_style,
+            compatibility: self.config.compatibility.clone(),
+            network: self.config.network.clone(),
+            selected,
+        }
+    }
+}
+
+/// 可以导入的配置文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportSource {
+    /// `~/.aws/credentials` 和 `~/.aws/config`
+    Aws,
+    /// `rclone.conf` 中 `type = s3` 的远端
+    Rclone,
+    /// s3cmd 的 `.s3cfg`
+    S3cmd,
+}
+
+/// 可以导出的配置文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Aws,
+    Rclone,
+}
+
+/// 导入时与已有配置重名的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictStrategy {
+    /// 跳过重名的配置
+    #[default]
+    Skip,
+    /// 以 "name (2)" 的形式重命名后导入
+    Rename,
+    /// 覆盖已有配置
+    Overwrite,
+}
+
+/// 导入预览中的一项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportCandidate {
+    pub name: String,
+    pub endpoint: String,
+    pub region: String,
+    #[serde(rename = "accessKeyHint")]
+    pub access_key_hint: String,
+    #[serde(rename = "addressingStyle")]
+    pub addressing_style: AddressingStyle,
+    /// 是否与已有配置重名
+    pub conflict: bool,
+    /// 无法导入的原因，例如缺少密钥
+    pub problem: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportProfilesRequest {
+    pub source: ImportSource,
+    /// 配置文件路径，未指定时使用该工具的默认位置
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub conflict: ConflictStrategy,
+    /// 只导入这些名称，未指定时导入全部
+    #[serde(default)]
+    pub names: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedImport {
+    pub name: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportProfilesResult {
+    pub imported: Vec<ProfileSummary>,
+    pub skipped: Vec<SkippedImport>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportProfilesRequest {
+    pub format: ExportFormat,
+    #[serde(rename = "profileIds")]
+    pub profile_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportProfilesResult {
+    /// 导出文件的保存路径
+    pub path: String,
+    /// 凭证来源无法用该格式表示而没有导出的配置
+    pub skipped: Vec<SkippedImport>,
+}
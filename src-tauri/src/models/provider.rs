@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::s3::{AddressingStyle, Compatibility};
+
+/// 内置的服务商预设
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderPreset {
+    pub id: &'static str,
+    pub name: &'static str,
+    /// 服务地址模板，例如 `https://oss-{region}.aliyuncs.com`
+    #[serde(rename = "endpointTemplate")]
+    pub endpoint_template: &'static str,
+    /// 模板中除 `{region}` 以外需要用户填写的参数
+    pub parameters: &'static [&'static str],
+    pub regions: &'static [ProviderRegion],
+    #[serde(rename = "defaultRegion")]
+    pub default_region: &'static str,
+    /// 是否允许使用列表以外的区域
+    #[serde(rename = "customRegion")]
+    pub custom_region: bool,
+    /// 用于识别地址的主机名后缀，不匹配模板时使用
+    #[serde(skip)]
+    pub host_suffix: Option<&'static str>,
+    #[serde(rename = "addressingStyle")]
+    pub addressing_style: AddressingStyle,
+    pub compatibility: Compatibility,
+    pub quirks: &'static [ProviderQuirk],
+}
+
+/// 服务商的区域
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderRegion {
+    pub id: &'static str,
+    pub name: &'static str,
+}
+
+/// 服务商与标准 S3 的差异，前端据此显示提示
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProviderQuirk {
+    /// 只接受虚拟主机方式访问
+    VirtualHostOnly,
+    /// 存储桶名称带有 `-APPID` 后缀
+    BucketNameAppId,
+    /// 区域固定为 `auto`
+    RegionAuto,
+    /// 旧版本不支持 aws-chunked 校验和上传
+    LegacyChecksum,
+    /// 支持双栈和传输加速地址
+    DualStackAccelerate,
+}
+
+/// 根据预设生成配置的请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildProviderConfigRequest {
+    #[serde(rename = "providerId")]
+    pub provider_id: String,
+    /// 未指定时使用预设的默认区域
+    #[serde(default)]
+    pub region: Option<String>,
+    /// 模板参数，例如 R2 的 `account`
+    #[serde(default)]
+    pub parameters: HashMap<String, String>,
+    #[serde(default)]
+    pub bucket: Option<String>,
+}
+
+/// 从服务地址识别出的服务商
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderMatch {
+    #[serde(rename = "providerId")]
+    pub provider_id: String,
+    /// 地址中包含的区域，无法识别时为空
+    pub region: Option<String>,
+    pub parameters: HashMap<String, String>,
+}
@@ -1,5 +1,9 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::error::AppError;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct S3Config {
     pub endpoint: String,
@@ -8,6 +12,170 @@ pub struct S3Config {
     pub secret_access_key: String,
     pub bucket: Option<String>,
     pub custom_path: Option<String>,
+    #[serde(default)]
+    pub addressing_style: AddressingStyle,
+    #[serde(default)]
+    pub credential_source: CredentialSource,
+    #[serde(default)]
+    pub compatibility: Compatibility,
+    #[serde(default)]
+    pub network: NetworkSettings,
+}
+
+/// 凭证来源
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum CredentialSource {
+    /// 使用 `access_key_id` 和 `secret_access_key`
+    #[default]
+    Static,
+    /// 静态密钥加会话令牌（例如 STS 签发的临时凭证）
+    SessionToken {
+        #[serde(rename = "sessionToken")]
+        session_token: String,
+    },
+    /// 用静态密钥调用 STS AssumeRole 获取临时凭证，过期前自动刷新
+    AssumeRole {
+        #[serde(rename = "roleArn")]
+        role_arn: String,
+        #[serde(rename = "sessionName", default)]
+        session_name: Option<String>,
+        #[serde(rename = "externalId", default)]
+        external_id: Option<String>,
+        /// MFA 设备的序列号或 ARN
+        #[serde(rename = "mfaSerial", default)]
+        mfa_serial: Option<String>,
+        /// 一次性 MFA 验证码，不会被保存
+        #[serde(rename = "mfaCode", default, skip_serializing)]
+        mfa_code: Option<String>,
+        #[serde(rename = "durationSeconds", default)]
+        duration_seconds: Option<i32>,
+        /// STS 服务地址，未指定时使用 AWS STS（MinIO 等服务使用自身地址）
+        #[serde(rename = "stsEndpoint", default)]
+        sts_endpoint: Option<String>,
+    },
+    /// 读取 `AWS_ACCESS_KEY_ID` 等环境变量
+    Environment,
+    /// 使用 `~/.aws/config` 中的命名配置（支持 SSO、credential_process 和角色链）
+    Profile { name: String },
+    /// 不签名的匿名访问，用于公开存储桶
+    Anonymous,
+}
+
+impl CredentialSource {
+    /// 是否需要保存的 Access Key ID 和 Secret Access Key
+    pub fn uses_static_keys(&self) -> bool {
+        matches!(
+            self,
+            CredentialSource::Static
+                | CredentialSource::SessionToken { .. }
+                | CredentialSource::AssumeRole { .. }
+        )
+    }
+}
+
+/// 存储桶寻址方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AddressingStyle {
+    /// `https://endpoint/bucket/key`，大多数 S3 兼容服务使用这种方式
+    #[default]
+    Path,
+    /// `https://bucket.endpoint/key`
+    Virtual,
+}
+
+/// 请求校验和的计算时机
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecksumMode {
+    /// 操作支持时总是计算 CRC 校验和（SDK 默认行为）
+    #[default]
+    WhenSupported,
+    /// 只在操作要求时计算，兼容不支持 aws-chunked 上传的旧网关
+    WhenRequired,
+}
+
+/// 针对不同服务商的请求兼容性设置
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Compatibility {
+    #[serde(default)]
+    pub request_checksum: ChecksumMode,
+    /// 上传时不对请求体签名（`UNSIGNED-PAYLOAD`）
+    #[serde(default)]
+    pub unsigned_payload: bool,
+    /// 使用 IPv4/IPv6 双栈地址，仅支持 AWS S3
+    #[serde(default)]
+    pub dual_stack: bool,
+    /// 使用 S3 Transfer Acceleration 地址，仅支持 AWS S3
+    #[serde(default)]
+    pub accelerate: bool,
+}
+
+/// 失败请求的重试策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryMode {
+    /// 指数退避重试（SDK 默认行为）
+    #[default]
+    Standard,
+    /// 在标准重试的基础上，被限流时自动降低发送速率
+    Adaptive,
+}
+
+/// 超时、重试和限速设置，未设置的项使用 SDK 默认值
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkSettings {
+    /// 建立连接的超时（秒）
+    #[serde(default)]
+    pub connect_timeout_secs: Option<u64>,
+    /// 等待响应数据的超时（秒），慢速链路上传下载大文件时需要加大
+    #[serde(default)]
+    pub read_timeout_secs: Option<u64>,
+    /// 单个操作（包括所有重试）的总超时（秒）
+    #[serde(default)]
+    pub operation_timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub retry_mode: RetryMode,
+    /// 包括第一次请求在内的最多尝试次数
+    #[serde(default)]
+    pub max_attempts: Option<u32>,
+    /// 每秒最多发送的请求数，同一配置的所有操作共用
+    #[serde(default)]
+    pub max_requests_per_second: Option<u32>,
+    #[serde(default)]
+    pub proxy: Option<ProxySettings>,
+    /// 额外信任的根证书（PEM，可以包含多个证书），与系统证书一起使用
+    #[serde(default)]
+    pub ca_certificates: Option<String>,
+    /// 服务器证书的 SHA-256 指纹（十六进制），设置后只接受这一个证书
+    #[serde(default)]
+    pub pinned_certificate: Option<String>,
+    /// 不校验服务器证书，只用于测试环境
+    #[serde(default)]
+    pub accept_invalid_certs: bool,
+}
+
+/// 代理服务器，同时用于 SDK 和应用自己发出的 HTTP 请求
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProxySettings {
+    /// 支持 `http://`、`https://`、`socks5://` 和由代理解析域名的 `socks5h://`
+    pub url: String,
+    #[serde(default)]
+    pub username: Option<String>,
+    /// 保存在凭证存储中，配置文件中只留下空字符串表示已设置
+    #[serde(default)]
+    pub password: Option<String>,
+    /// 不经过代理的主机：完整主机名、`.example.com` 形式的后缀、IP 地址或 `*`
+    #[serde(default)]
+    pub no_proxy: Vec<String>,
+}
+
+/// 服务商推荐的寻址方式和兼容性设置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompatibilityDefaults {
+    pub addressing_style: AddressingStyle,
+    pub compatibility: Compatibility,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -17,6 +185,27 @@ pub struct S3Object {
     pub last_modified: Option<String>,
     pub etag: Option<String>,
     pub storage_class: Option<String>,
+    /// 对象标签，仅在列举时请求了标签才会返回
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<ObjectTag>>,
+}
+
+/// 使用分隔符列举时返回的“文件夹”（CommonPrefixes）
+#[derive(Debug, Serialize, Deserialize)]
+pub struct S3Folder {
+    pub prefix: String,
+    pub name: String,
+}
+
+/// 单页列举结果
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListObjectsPage {
+    pub objects: Vec<S3Object>,
+    pub folders: Vec<S3Folder>,
+    #[serde(rename = "isTruncated")]
+    pub is_truncated: bool,
+    #[serde(rename = "nextContinuationToken")]
+    pub next_continuation_token: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -27,24 +216,578 @@ pub struct BucketInfo {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ListObjectsRequest {
-    pub config: S3Config,
+    /// 后端保存的配置 ID，未指定时使用当前选中的配置
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
     #[serde(rename = "bucketName")]
     pub bucket_name: String,
     pub prefix: Option<String>,
+    /// 分隔符，通常为 "/"，用于按文件夹列举
+    #[serde(default)]
+    pub delimiter: Option<String>,
+    /// 上一页返回的续传令牌
+    #[serde(rename = "continuationToken", default)]
+    pub continuation_token: Option<String>,
+    /// 每页最多返回的键数量
+    #[serde(rename = "maxKeys", default)]
+    pub max_keys: Option<i32>,
+    /// 为每个对象读取标签
+    #[serde(rename = "includeTags", default)]
+    pub include_tags: bool,
+    /// 只返回标签匹配的对象，指定后自动读取标签
+    #[serde(rename = "tagFilter", default)]
+    pub tag_filter: Option<TagFilter>,
+}
+
+/// 对象标签
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObjectTag {
+    pub key: String,
+    pub value: String,
+}
+
+/// 按标签过滤对象：`value` 为空时只要求存在该标签键
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagFilter {
+    pub key: String,
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+/// 读取或删除单个对象的标签
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ObjectTagsRequest {
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
+    #[serde(rename = "bucketName")]
+    pub bucket_name: String,
+    #[serde(rename = "objectKey")]
+    pub object_key: String,
+    #[serde(rename = "versionId", default)]
+    pub version_id: Option<String>,
+}
+
+/// 替换单个对象的全部标签
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PutObjectTagsRequest {
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
+    #[serde(rename = "bucketName")]
+    pub bucket_name: String,
+    #[serde(rename = "objectKey")]
+    pub object_key: String,
+    #[serde(rename = "versionId", default)]
+    pub version_id: Option<String>,
+    pub tags: Vec<ObjectTag>,
+}
+
+/// 批量修改选中对象或前缀下所有对象的标签
+///
+/// `replace` 为 true 时用 `tags` 替换原有标签；否则把 `tags` 合并到原有标签中，
+/// 并删除 `removeKeys` 列出的标签键。
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagObjectsRequest {
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
+    #[serde(rename = "bucketName")]
+    pub bucket_name: String,
+    #[serde(rename = "objectKeys", default)]
+    pub object_keys: Vec<String>,
+    /// 同时修改该前缀下的所有对象
+    #[serde(default)]
+    pub prefix: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<ObjectTag>,
+    #[serde(rename = "removeKeys", default)]
+    pub remove_keys: Vec<String>,
+    #[serde(default)]
+    pub replace: bool,
+    /// 进度事件使用的传输 ID，未指定时自动生成
+    #[serde(rename = "transferId", default)]
+    pub transfer_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagObjectsResult {
+    #[serde(rename = "transferId")]
+    pub transfer_id: String,
+    #[serde(rename = "objectCount")]
+    pub object_count: usize,
+    #[serde(rename = "updatedCount")]
+    pub updated_count: usize,
+    pub failed: Vec<ObjectError>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DeleteObjectsRequest {
-    pub config: S3Config,
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
     #[serde(rename = "bucketName")]
     pub bucket_name: String,
     #[serde(rename = "objectKeys")]
     pub object_keys: Vec<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeletePrefixRequest {
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
+    #[serde(rename = "bucketName")]
+    pub bucket_name: String,
+    pub prefix: String,
+    /// 只统计将被删除的对象，不实际删除
+    #[serde(rename = "dryRun", default)]
+    pub dry_run: bool,
+}
+
+/// 批量删除中单个键的错误
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteObjectError {
+    pub key: String,
+    #[serde(rename = "versionId")]
+    pub version_id: Option<String>,
+    pub code: Option<String>,
+    pub message: Option<String>,
+}
+
+/// 删除多个对象的结果，部分键失败时其余键照常删除
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeleteObjectsResult {
+    pub deleted: Vec<String>,
+    pub errors: Vec<DeleteObjectError>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeletePrefixResult {
+    #[serde(rename = "dryRun")]
+    pub dry_run: bool,
+    /// 存储桶是否开启过版本控制（开启时会删除所有版本和删除标记）
+    pub versioned: bool,
+    /// 匹配的对象（或版本、删除标记）数量
+    #[serde(rename = "objectCount")]
+    pub object_count: usize,
+    #[serde(rename = "totalSize")]
+    pub total_size: u64,
+    #[serde(rename = "deletedCount")]
+    pub deleted_count: usize,
+    pub errors: Vec<DeleteObjectError>,
+}
+
+/// 复制、移动或重命名单个对象
+///
+/// 未指定 `destBucket` 时在源存储桶内操作。
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CopyObjectRequest {
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
+    #[serde(rename = "sourceBucket")]
+    pub source_bucket: String,
+    #[serde(rename = "sourceKey")]
+    pub source_key: String,
+    #[serde(rename = "destBucket", default)]
+    pub dest_bucket: Option<String>,
+    #[serde(rename = "destKey")]
+    pub dest_key: String,
+    /// 目标对象已存在时覆盖，默认报冲突
+    #[serde(default)]
+    pub overwrite: bool,
+    /// 进度事件使用的传输 ID，未指定时自动生成
+    #[serde(rename = "transferId", default)]
+    pub transfer_id: Option<String>,
+}
+
+/// 复制、移动或重命名前缀下的所有对象
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CopyPrefixRequest {
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
+    #[serde(rename = "sourceBucket")]
+    pub source_bucket: String,
+    #[serde(rename = "sourcePrefix")]
+    pub source_prefix: String,
+    #[serde(rename = "destBucket", default)]
+    pub dest_bucket: Option<String>,
+    #[serde(rename = "destPrefix")]
+    pub dest_prefix: String,
+    /// 进度事件使用的传输 ID，未指定时自动生成
+    #[serde(rename = "transferId", default)]
+    pub transfer_id: Option<String>,
+}
+
+/// 批量复制中单个对象的错误
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopyObjectError {
+    #[serde(rename = "sourceKey")]
+    pub source_key: String,
+    #[serde(rename = "destKey")]
+    pub dest_key: String,
+    pub error: AppError,
+}
+
+/// 前缀复制或移动的结果
+///
+/// 复制阶段有对象失败时，已复制的目标对象会被删除（回滚），源对象保持不变；
+/// 移动时全部复制成功后才删除源对象。
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CopyPrefixResult {
+    #[serde(rename = "transferId")]
+    pub transfer_id: String,
+    #[serde(rename = "objectCount")]
+    pub object_count: usize,
+    #[serde(rename = "totalSize")]
+    pub total_size: u64,
+    #[serde(rename = "copiedCount")]
+    pub copied_count: usize,
+    /// 复制失败的对象
+    pub failed: Vec<CopyObjectError>,
+    /// 是否执行了回滚
+    #[serde(rename = "rolledBack")]
+    pub rolled_back: bool,
+    /// 回滚时未能删除的目标对象，需要手动清理
+    #[serde(rename = "rollbackErrors")]
+    pub rollback_errors: Vec<DeleteObjectError>,
+    /// 移动时已删除的源对象数量
+    #[serde(rename = "deletedCount")]
+    pub deleted_count: usize,
+    /// 移动时未能删除的源对象，这些对象在源和目标位置都存在
+    #[serde(rename = "deleteErrors")]
+    pub delete_errors: Vec<DeleteObjectError>,
+}
+
+/// HeadObject 返回的对象属性和元数据
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ObjectMetadata {
+    pub key: String,
+    pub size: u64,
+    #[serde(rename = "lastModified")]
+    pub last_modified: Option<String>,
+    pub etag: Option<String>,
+    #[serde(rename = "versionId")]
+    pub version_id: Option<String>,
+    #[serde(rename = "storageClass")]
+    pub storage_class: Option<String>,
+    #[serde(rename = "contentType")]
+    pub content_type: Option<String>,
+    #[serde(rename = "cacheControl")]
+    pub cache_control: Option<String>,
+    #[serde(rename = "contentDisposition")]
+    pub content_disposition: Option<String>,
+    #[serde(rename = "contentEncoding")]
+    pub content_encoding: Option<String>,
+    #[serde(rename = "contentLanguage")]
+    pub content_language: Option<String>,
+    /// `Expires` 头的原始值
+    pub expires: Option<String>,
+    /// 用户元数据（`x-amz-meta-*`），键不带前缀
+    #[serde(rename = "userMetadata")]
+    pub user_metadata: BTreeMap<String, String>,
+    /// 服务端加密方式，例如 `AES256`、`aws:kms`
+    #[serde(rename = "serverSideEncryption")]
+    pub server_side_encryption: Option<String>,
+    #[serde(rename = "sseKmsKeyId")]
+    pub sse_kms_key_id: Option<String>,
+    #[serde(rename = "bucketKeyEnabled")]
+    pub bucket_key_enabled: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetObjectMetadataRequest {
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
+    #[serde(rename = "bucketName")]
+    pub bucket_name: String,
+    #[serde(rename = "objectKey")]
+    pub object_key: String,
+    /// 读取指定版本，未指定时读取当前版本
+    #[serde(rename = "versionId", default)]
+    pub version_id: Option<String>,
+}
+
+/// 要修改的元数据
+///
+/// 未指定的字段保持不变，空字符串表示删除该字段；`userMetadata` 会整体替换原有的用户元数据。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetadataChanges {
+    #[serde(rename = "contentType", default)]
+    pub content_type: Option<String>,
+    #[serde(rename = "cacheControl", default)]
+    pub cache_control: Option<String>,
+    #[serde(rename = "contentDisposition", default)]
+    pub content_disposition: Option<String>,
+    #[serde(rename = "contentEncoding", default)]
+    pub content_encoding: Option<String>,
+    #[serde(rename = "contentLanguage", default)]
+    pub content_language: Option<String>,
+    #[serde(rename = "userMetadata", default)]
+    pub user_metadata: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateObjectMetadataRequest {
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
+    #[serde(rename = "bucketName")]
+    pub bucket_name: String,
+    #[serde(rename = "objectKey")]
+    pub object_key: String,
+    pub changes: MetadataChanges,
+}
+
+/// 批量修改前缀下所有对象的元数据
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdatePrefixMetadataRequest {
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
+    #[serde(rename = "bucketName")]
+    pub bucket_name: String,
+    pub prefix: String,
+    pub changes: MetadataChanges,
+    /// 进度事件使用的传输 ID，未指定时自动生成
+    #[serde(rename = "transferId", default)]
+    pub transfer_id: Option<String>,
+}
+
+/// 批量操作中单个对象的错误
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectError {
+    pub key: String,
+    pub error: AppError,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdatePrefixMetadataResult {
+    #[serde(rename = "transferId")]
+    pub transfer_id: String,
+    #[serde(rename = "objectCount")]
+    pub object_count: usize,
+    #[serde(rename = "updatedCount")]
+    pub updated_count: usize,
+    pub failed: Vec<ObjectError>,
+}
+
+/// 对象的一个版本或删除标记
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectVersion {
+    pub key: String,
+    #[serde(rename = "versionId")]
+    pub version_id: Option<String>,
+    #[serde(rename = "isLatest")]
+    pub is_latest: bool,
+    #[serde(rename = "isDeleteMarker")]
+    pub is_delete_marker: bool,
+    pub size: u64,
+    #[serde(rename = "lastModified")]
+    pub last_modified: Option<String>,
+    pub etag: Option<String>,
+    #[serde(rename = "storageClass")]
+    pub storage_class: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListObjectVersionsRequest {
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
+    #[serde(rename = "bucketName")]
+    pub bucket_name: String,
+    #[serde(default)]
+    pub prefix: Option<String>,
+    /// 分隔符，通常为 "/"，用于按文件夹列举
+    #[serde(default)]
+    pub delimiter: Option<String>,
+    /// 上一页返回的 `nextKeyMarker`
+    #[serde(rename = "keyMarker", default)]
+    pub key_marker: Option<String>,
+    /// 上一页返回的 `nextVersionIdMarker`
+    #[serde(rename = "versionIdMarker", default)]
+    pub version_id_marker: Option<String>,
+    #[serde(rename = "maxKeys", default)]
+    pub max_keys: Option<i32>,
+}
+
+/// 单页版本列举结果，同一个键的版本按时间从新到旧排列
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ObjectVersionsPage {
+    pub versions: Vec<ObjectVersion>,
+    pub folders: Vec<S3Folder>,
+    #[serde(rename = "isTruncated")]
+    pub is_truncated: bool,
+    #[serde(rename = "nextKeyMarker")]
+    pub next_key_marker: Option<String>,
+    #[serde(rename = "nextVersionIdMarker")]
+    pub next_version_id_marker: Option<String>,
+}
+
+/// 把历史版本恢复为最新版本
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RestoreObjectVersionRequest {
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
+    #[serde(rename = "bucketName")]
+    pub bucket_name: String,
+    #[serde(rename = "objectKey")]
+    pub object_key: String,
+    #[serde(rename = "versionId")]
+    pub version_id: String,
+}
+
+/// 删除最新的删除标记，恢复被删除的对象
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UndeleteObjectRequest {
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
+    #[serde(rename = "bucketName")]
+    pub bucket_name: String,
+    #[serde(rename = "objectKey")]
+    pub object_key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectVersionId {
+    pub key: String,
+    #[serde(rename = "versionId")]
+    pub version_id: String,
+}
+
+/// 永久删除指定的版本或删除标记
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeleteObjectVersionsRequest {
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
+    #[serde(rename = "bucketName")]
+    pub bucket_name: String,
+    pub versions: Vec<ObjectVersionId>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeleteObjectVersionsResult {
+    #[serde(rename = "deletedCount")]
+    pub deleted_count: usize,
+    pub errors: Vec<DeleteObjectError>,
+}
+
+/// 存储桶的版本控制状态；从未开启过时为 `disabled`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersioningStatus {
+    Enabled,
+    Suspended,
+    Disabled,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BucketVersioning {
+    pub status: VersioningStatus,
+    /// 是否开启了 MFA Delete
+    #[serde(rename = "mfaDelete")]
+    pub mfa_delete: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BucketVersioningRequest {
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
+    #[serde(rename = "bucketName")]
+    pub bucket_name: String,
+}
+
+/// 开启或暂停版本控制；版本控制一旦开启就无法关闭，只能暂停
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetBucketVersioningRequest {
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
+    #[serde(rename = "bucketName")]
+    pub bucket_name: String,
+    pub enabled: bool,
+}
+
+/// 跨配置传输的一端：配置、存储桶和前缀
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransferLocation {
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
+    #[serde(rename = "bucketName")]
+    pub bucket_name: String,
+    /// 为空时表示整个存储桶
+    #[serde(default)]
+    pub prefix: String,
+}
+
+/// 在两个配置（可以是不同的服务商）之间传输前缀下的对象
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MigrateObjectsRequest {
+    pub source: TransferLocation,
+    pub destination: TransferLocation,
+    /// 分片大小（字节），同时决定内存占用
+    #[serde(rename = "partSize", default)]
+    pub part_size: Option<u64>,
+    /// 同一对象同时上传的分片数
+    #[serde(rename = "maxConcurrency", default)]
+    pub max_concurrency: Option<usize>,
+    /// 进度事件使用的传输 ID，未指定时自动生成
+    #[serde(rename = "transferId", default)]
+    pub transfer_id: Option<String>,
+}
+
+/// 跨配置传输的结果
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MigrateObjectsResult {
+    #[serde(rename = "transferId")]
+    pub transfer_id: String,
+    #[serde(rename = "objectCount")]
+    pub object_count: usize,
+    #[serde(rename = "totalSize")]
+    pub total_size: u64,
+    #[serde(rename = "transferredCount")]
+    pub transferred_count: usize,
+    /// 目标位置已有大小和校验值相同的对象，没有传输
+    #[serde(rename = "skippedCount")]
+    pub skipped_count: usize,
+    pub failed: Vec<CopyObjectError>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProbePermissionsRequest {
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
+    #[serde(rename = "bucketName")]
+    pub bucket_name: String,
+    /// 只检查该前缀下的权限，例如 `photos/`
+    #[serde(default)]
+    pub prefix: Option<String>,
+}
+
+/// 单项权限的探测结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Capability {
+    Allowed,
+    Denied,
+    /// 无法确定，例如前缀下没有可读取的对象，或服务不支持该操作
+    Unknown,
+}
+
+/// 存储桶（或前缀）的权限，前端据此禁用不可用的操作
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BucketPermissions {
+    pub list: Capability,
+    pub read: Capability,
+    pub write: Capability,
+    pub delete: Capability,
+    /// 读取存储桶 ACL
+    #[serde(rename = "readAcl")]
+    pub read_acl: Capability,
+    /// 读取存储桶策略
+    #[serde(rename = "readPolicy")]
+    pub read_policy: Capability,
+    /// 探测写入的标记对象未能删除时的键，需要用户手动清理
+    #[serde(rename = "leftoverKey")]
+    pub leftover_key: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GetPresignedUrlRequest {
-    pub config: S3Config,
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
     #[serde(rename = "bucketName")]
     pub bucket_name: String,
     #[serde(rename = "objectKey")]
@@ -53,20 +796,25 @@ pub struct GetPresignedUrlRequest {
     pub expires_in_seconds: Option<u64>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DownloadFileRequest {
-    pub config: S3Config,
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
     #[serde(rename = "bucketName")]
     pub bucket_name: String,
     #[serde(rename = "objectKey")]
     pub object_key: String,
     #[serde(rename = "defaultFileName")]
     pub default_file_name: String,
+    /// 进度事件使用的传输 ID，未指定时自动生成
+    #[serde(rename = "transferId", default)]
+    pub transfer_id: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UploadFileRequest {
-    pub config: S3Config,
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
     #[serde(rename = "bucketName")]
     pub bucket_name: String,
     #[serde(rename = "objectKey")]
@@ -75,11 +823,24 @@ pub struct UploadFileRequest {
     pub file_path: String,
     #[serde(rename = "contentType")]
     pub content_type: Option<String>,
+    /// 超过该大小（字节）时使用分片上传
+    #[serde(rename = "multipartThreshold", default)]
+    pub multipart_threshold: Option<u64>,
+    /// 分片大小（字节）
+    #[serde(rename = "partSize", default)]
+    pub part_size: Option<u64>,
+    /// 同时上传的分片数
+    #[serde(rename = "maxConcurrency", default)]
+    pub max_concurrency: Option<usize>,
+    /// 进度事件使用的传输 ID，未指定时自动生成
+    #[serde(rename = "transferId", default)]
+    pub transfer_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UploadFileBytesRequest {
-    pub config: S3Config,
+    #[serde(rename = "profileId", default)]
+    pub profile_id: Option<String>,
     #[serde(rename = "bucketName")]
     pub bucket_name: String,
     #[serde(rename = "objectKey")]
@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use crate::models::s3::{DownloadFileRequest, UploadFileRequest};
+
+/// 传输方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferDirection {
+    Upload,
+    Download,
+    /// 服务端复制或移动，进度按已复制的字节数计算
+    Copy,
+    /// 批量修改元数据或标签，`bytesDone`/`totalBytes` 为已处理和总的对象数
+    Batch,
+}
+
+/// 传输状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+/// 通过 `transfer-progress` 事件发送给前端的进度信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferProgress {
+    #[serde(rename = "transferId")]
+    pub transfer_id: String,
+    pub direction: TransferDirection,
+    pub status: TransferStatus,
+    #[serde(rename = "bucketName")]
+    pub bucket_name: String,
+    #[serde(rename = "objectKey")]
+    pub object_key: String,
+    #[serde(rename = "bytesDone")]
+    pub bytes_done: u64,
+    #[serde(rename = "totalBytes")]
+    pub total_bytes: u64,
+    #[serde(rename = "bytesPerSecond")]
+    pub bytes_per_second: f64,
+    #[serde(rename = "etaSeconds")]
+    pub eta_seconds: Option<f64>,
+    pub error: Option<AppError>,
+}
+
+/// 传输队列中任务的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobState {
+    Queued,
+    Running,
+    Paused,
+    Failed,
+    Completed,
+}
+
+/// 已上传完成的分片
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UploadedPart {
+    #[serde(rename = "partNumber")]
+    pub part_number: i32,
+    pub etag: Option<String>,
+}
+
+/// 分片上传的断点信息，用于暂停或重启后继续上传
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultipartCheckpoint {
+    #[serde(rename = "uploadId")]
+    pub upload_id: String,
+    #[serde(rename = "partSize")]
+    pub part_size: u64,
+    /// 开始上传时本地文件的大小和修改时间（毫秒），变化后断点作废
+    #[serde(rename = "fileSize")]
+    pub file_size: u64,
+    pub modified: Option<u64>,
+    pub parts: Vec<UploadedPart>,
+}
+
+/// 传输任务的具体内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum TransferTask {
+    Upload {
+        request: UploadFileRequest,
+        checkpoint: Option<MultipartCheckpoint>,
+    },
+    Download {
+        request: DownloadFileRequest,
+        #[serde(rename = "destPath")]
+        dest_path: String,
+    },
+}
+
+/// 传输队列中的任务，会被持久化到磁盘
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferJob {
+    pub id: String,
+    pub state: JobState,
+    pub task: TransferTask,
+    #[serde(rename = "bytesDone")]
+    pub bytes_done: u64,
+    #[serde(rename = "totalBytes")]
+    pub total_bytes: u64,
+    #[serde(default)]
+    pub error: Option<AppError>,
+}
+
+/// 返回给前端的任务概要，不包含连接配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferJobInfo {
+    pub id: String,
+    pub direction: TransferDirection,
+    pub state: JobState,
+    #[serde(rename = "bucketName")]
+    pub bucket_name: String,
+    #[serde(rename = "objectKey")]
+    pub object_key: String,
+    #[serde(rename = "localPath")]
+    pub local_path: String,
+    #[serde(rename = "bytesDone")]
+    pub bytes_done: u64,
+    #[serde(rename = "totalBytes")]
+    pub total_bytes: u64,
+    pub error: Option<AppError>,
+}
+
+impl TransferJob {
+    pub fn direction(&self) -> TransferDirection {
+        match self.task {
+            TransferTask::Upload { .. } => TransferDirection::Upload,
+            TransferTask::Download { .. } => TransferDirection::Download,
+        }
+    }
+
+    pub fn info(&self) -> TransferJobInfo {
+        let (bucket_name, object_key, local_path) = match &self.task {
+            TransferTask::Upload { request, .. } => (
+                &request.bucket_name,
+                &request.object_key,
+                &request.file_path,
+            ),
+            TransferTask::Download { request, dest_path } => {
+                (&request.bucket_name, &request.object_key, dest_path)
+            }
+        };
+
+        TransferJobInfo {
+            id: self.id.clone(),
+            direction: self.direction(),
+            state: self.state,
+            bucket_name: bucket_name.clone(),
+            object_key: object_key.clone(),
+            local_path: local_path.clone(),
+            bytes_done: self.bytes_done,
+            total_bytes: self.total_bytes,
+            error: self.error.clone(),
+        }
+    }
+}
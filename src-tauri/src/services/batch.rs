@@ -0,0 +1,105 @@
+use std::future::Future;
+
+use tokio::task::JoinSet;
+
+use crate::error::AppError;
+use crate::models::s3::ObjectError;
+use crate::services::progress::ProgressReporter;
+
+/// 以有限并发对每个键执行 `f`，单个对象失败不影响其他对象
+///
+/// 返回成功的结果（附带键在 `keys` 中的下标）和失败的对象。
+/// 指定 `progress` 时按已处理的对象数报告进度，结束时发送完成或失败事件。
+pub async fn for_each_object<T, F, Fut>(
+    keys: &[String],
+    concurrency: usize,
+    progress: Option<&ProgressReporter>,
+    f: F,
+) -> (Vec<(usize, T)>, Vec<ObjectError>)
+where
+    T: Send + 'static,
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<T, AppError>> + Send + 'static,
+{
+    let mut succeeded = Vec::new();
+    let mut failed = Vec::new();
+
+    let mut pending = keys.iter().enumerate();
+    let mut tasks = JoinSet::new();
+    loop {
+        while tasks.len() < concurrency.max(1) {
+            let Some((index, key)) = pending.next() else {
+                break;
+            };
+            let task = f(key.clone());
+            tasks.spawn(async move { (index, task.await) });
+        }
+
+        let Some(joined) = tasks.join_next().await else {
+            break;
+        };
+        match joined {
+            Ok((index, Ok(value))) => succeeded.push((index, value)),
+            Ok((index, Err(error))) => failed.push(ObjectError {
+                key: keys[index].clone(),
+                error,
+            }),
+            Err(e) => failed.push(ObjectError {
+                key: String::new(),
+                error: AppError::internal(format!("Object task failed: {}", e)),
+            }),
+        }
+        if let Some(progress) = progress {
+            progress.set((succeeded.len() + failed.len()) as u64);
+        }
+    }
+
+    if let Some(progress) = progress {
+        match failed.first() {
+            Some(failure) => progress.fail(&failure.error),
+            None => progress.complete(),
+        }
+    }
+    (succeeded, failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_for_each_object_bounds_concurrency_and_collects_failures() {
+        let keys: Vec<String> = (0..10).map(|index| format!("key-{}", index)).collect();
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let progress = ProgressReporter::detached(keys.len() as u64);
+
+        let (succeeded, failed) = for_each_object(&keys, 3, Some(&progress), |key| {
+            let running = running.clone();
+            let peak = peak.clone();
+            async move {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                if key == "key-4" {
+                    Err(AppError::invalid_input("bad key"))
+                } else {
+                    Ok(key.len())
+                }
+            }
+        })
+        .await;
+
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+        assert_eq!(succeeded.len(), 9);
+        assert!(succeeded.iter().all(|(index, _)| *index != 4));
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].key, "key-4");
+        assert_eq!(progress.bytes_done(), 10);
+    }
+}
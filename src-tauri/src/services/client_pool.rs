@@ -0,0 +1,135 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+use aws_sdk_s3::Client;
+
+use crate::error::AppError;
+use crate::models::s3::S3Config;
+use crate::services::credential_provider::credential_identity;
+use crate::services::s3_client::create_s3_client;
+
+/// 缓存的客户端及其对应配置的指纹
+struct CachedClient {
+    fingerprint: u64,
+    client: Client,
+}
+
+/// 以 Tauri State 管理的 S3 客户端缓存
+///
+/// 同一个配置的多次调用复用同一个 `Client`（及其连接池），
+/// 配置中的凭证或其他字段变化时自动重建客户端。
+#[derive(Default)]
+pub struct S3ClientPool {
+    clients: Mutex<HashMap<String, CachedClient>>,
+}
+
+/// 配置的身份标识：同一服务、同一区域下的同一个访问密钥（或其他凭证来源）
+pub fn profile_identity(config: &S3Config) -> String {
+    format!(
+        "{}|{}|{}",
+        config.endpoint,
+        config.region,
+        credential_identity(config)
+    )
+}
+
+/// 配置内容的指纹，任何字段变化（例如更换密钥）都会改变指纹
+pub fn config_fingerprint(config: &S3Config) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    serde_json::to_string(config)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
+impl S3ClientPool {
+    /// 获取配置对应的客户端，不存在或配置已变化时重新创建
+    pub async fn get(&self, config: &S3Config) -> Result<Client, AppError> {
+        let identity = profile_identity(config);
+        let fingerprint = config_fingerprint(config);
+
+        if let Some(cached) = self.clients.lock().unwrap().get(&identity) {
+            if cached.fingerprint == fingerprint {
+                return Ok(cached.client.clone());
+            }
+        }
+
+        let client = create_s3_client(config).await?;
+
+        self.clients.lock().unwrap().insert(
+            identity,
+            CachedClient {
+                fingerprint,
+                client: client.clone(),
+            },
+        );
+
+        Ok(client)
+    }
+
+    /// 移除配置对应的缓存客户端
+    pub fn invalidate(&self, config: &S3Config) {
+        self.clients
+            .lock()
+            .unwrap()
+            .remove(&profile_identity(config));
+    }
+
+    /// 清空所有缓存的客户端
+    pub fn clear(&self) {
+        self.clients.lock().unwrap().clear();
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.clients.lock().unwrap().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_test_config;
+
+    #[test]
+    fn test_fingerprint_changes_with_credentials() {
+        let config = create_test_config();
+        let mut rotated = create_test_config();
+        rotated.secret_access_key = "rotated_secret".to_string();
+
+        assert_eq!(profile_identity(&config), profile_identity(&rotated));
+        assert_ne!(config_fingerprint(&config), config_fingerprint(&rotated));
+        assert_eq!(
+            config_fingerprint(&config),
+            config_fingerprint(&create_test_config())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pool_reuses_and_replaces_clients() {
+        let pool = S3ClientPool::default();
+        let config = create_test_config();
+
+        pool.get(&config).await.unwrap();
+        pool.get(&config).await.unwrap();
+        assert_eq!(pool.len(), 1);
+
+        // Rotated credentials replace the cached entry for the same identity
+        let mut rotated = create_test_config();
+        rotated.secret_access_key = "rotated_secret".to_string();
+        pool.get(&rotated).await.unwrap();
+        assert_eq!(pool.len(), 1);
+
+        let mut other = create_test_config();
+        other.access_key_id = "other_key".to_string();
+        pool.get(&other).await.unwrap();
+        assert_eq!(pool.len(), 2);
+
+        pool.invalidate(&other);
+        assert_eq!(pool.len(), 1);
+        pool.clear();
+        assert_eq!(pool.len(), 0);
+    }
+}
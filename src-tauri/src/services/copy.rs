@@ -0,0 +1,635 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use aws_sdk_s3::types::{
+    CompletedMultipartUpload, CompletedPart, MetadataDirective, TaggingDirective,
+};
+use aws_sdk_s3::Client;
+use tokio::task::JoinSet;
+use tracing::warn;
+
+use crate::error::{AppError, ErrorCode};
+use crate::models::s3::{CopyObjectError, CopyPrefixResult, ObjectMetadata};
+use crate::services::delete::{collect_prefix_targets, delete_targets, total_size, DeleteTarget};
+use crate::services::metadata;
+use crate::services::multipart::{
+    effective_part_size, plan_parts, PartRange, DEFAULT_CONCURRENCY, MAX_SINGLE_PUT_SIZE,
+};
+use crate::services::progress::ProgressReporter;
+use crate::services::tagging;
+
+/// 超过该大小（5 GiB，CopyObject 的上限）的对象使用 UploadPartCopy 分片复制
+pub const MAX_COPY_OBJECT_SIZE: u64 = MAX_SINGLE_PUT_SIZE;
+
+/// 分片复制的默认分片大小
+pub const COPY_PART_SIZE: u64 = 512 * 1024 * 1024;
+
+/// 对象在存储桶中的位置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectLocation {
+    pub bucket: String,
+    pub key: String,
+    /// 指定的对象版本，仅作为复制源时使用
+    pub version_id: Option<String>,
+}
+
+impl ObjectLocation {
+    pub fn new(bucket: impl Into<String>, key: impl Into<String>) -> Self {
+        Self {
+            bucket: bucket.into(),
+            key: key.into(),
+            version_id: None,
+        }
+    }
+
+    /// 指向该对象的某个历史版本
+    pub fn with_version(mut self, version_id: impl Into<String>) -> Self {
+        self.version_id = Some(version_id.into());
+        self
+    }
+
+    /// `x-amz-copy-source` 的值，键按 URL 编码，保留 `/`；指定版本时附加 `?versionId=`
+    pub fn copy_source(&self) -> String {
+        let mut encoded = String::with_capacity(self.bucket.len() + self.key.len() + 1);
+        encoded.push_str(&self.bucket);
+        encoded.push('/');
+        for byte in self.key.bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                    encoded.push(byte as char)
+                }
+                _ => encoded.push_str(&format!("%{:02X}", byte)),
+            }
+        }
+        if let Some(version_id) = &self.version_id {
+            encoded.push_str("?versionId=");
+            encoded.extend(url::form_urlencoded::byte_serialize(version_id.as_bytes()));
+        }
+        encoded
+    }
+}
+
+/// 把源前缀下的键换成目标前缀，例如 ("a/", "b/", "a/x/1.txt") -> "b/x/1.txt"
+pub fn replace_prefix(source_prefix: &str, dest_prefix: &str, key: &str) -> String {
+    format!(
+        "{}{}",
+        dest_prefix,
+        key.strip_prefix(source_prefix).unwrap_or(key)
+    )
+}
+
+/// 把前缀规范为以 `/` 结尾，避免 "photos" 匹配到 "photos2/" 下的对象；空前缀保持为空
+pub fn normalize_prefix(prefix: &str) -> String {
+    if prefix.is_empty() || prefix.ends_with('/') {
+        prefix.to_string()
+    } else {
+        format!("{}/", prefix)
+    }
+}
+
+/// 读取对象大小，对象不存在时返回 NotFound 错误
+pub async fn object_size(client: &Client, location: &ObjectLocation) -> Result<u64, AppError> {
+    let output = client
+        .head_object()
+        .bucket(&location.bucket)
+        .key(&location.key)
+        .set_version_id(location.version_id.clone())
+        .send()
+        .await
+        .map_err(|e| AppError::from_sdk("Failed to read source object", &e))?;
+
+    Ok(output.content_length().unwrap_or_default().max(0) as u64)
+}
+
+/// 对象是否存在
+pub async fn object_exists(client: &Client, location: &ObjectLocation) -> Result<bool, AppError> {
+    match object_size(client, location).await {
+        Ok(_) => Ok(true),
+        Err(e) if e.code == ErrorCode::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// 在服务端复制对象，保留元数据、标签和内容类型
+///
+/// 不超过 5 GiB 的对象使用 CopyObject，更大的对象使用 UploadPartCopy 分片复制。
+pub async fn copy_object(
+    client: &Client,
+    source: &ObjectLocation,
+    dest: &ObjectLocation,
+    size: u64,
+    progress: Option<&ProgressReporter>,
+) -> Result<(), AppError> {
+    if size > MAX_COPY_OBJECT_SIZE {
+        let attributes =
+            metadata::head_metadata(client, source, source.version_id.as_deref()).await?;
+        return copy_multipart(client, source, dest, size, &attributes, progress).await;
+    }
+
+    client
+        .copy_object()
+        .copy_source(source.copy_source())
+        .bucket(&dest.bucket)
+        .key(&dest.key)
+        .metadata_directive(MetadataDirective::Copy)
+        .tagging_directive(TaggingDirective::Copy)
+        .send()
+        .await
+        .map_err(|e| AppError::from_sdk("Failed to copy object", &e))?;
+
+    if let Some(progress) = progress {
+        progress.advance(size);
+    }
+    Ok(())
+}
+
+/// 删除单个对象
+pub async fn delete_object(client: &Client, location: &ObjectLocation) -> Result<(), AppError> {
+    client
+        .delete_object()
+        .bucket(&location.bucket)
+        .key(&location.key)
+        .send()
+        .await
+        .map_err(|e| AppError::from_sdk("Failed to delete source object", &e))?;
+    Ok(())
+}
+
+/// 分片复制：按 `attributes` 给出的属性和源对象的标签创建分片上传，再逐段复制
+///
+/// 分片上传不会自动带上源对象的属性，需要由调用方提供。
+pub async fn copy_multipart(
+    client: &Client,
+    source: &ObjectLocation,
+    dest: &ObjectLocation,
+    size: u64,
+    attributes: &ObjectMetadata,
+    progress: Option<&ProgressReporter>,
+) -> Result<(), AppError> {
+    let tagging = object_tagging(client, source).await;
+
+    let output = client
+        .create_multipart_upload()
+        .bucket(&dest.bucket)
+        .key(&dest.key)
+        .set_content_type(attributes.content_type.clone())
+        .set_cache_control(attributes.cache_control.clone())
+        .set_content_disposition(attributes.content_disposition.clone())
+        .set_content_encoding(attributes.content_encoding.clone())
+        .set_content_language(attributes.content_language.clone())
+        .set_expires(metadata::expires(attributes))
+        .set_metadata(metadata::user_metadata(attributes))
+        .set_storage_class(metadata::storage_class(attributes))
+        .set_server_side_encryption(metadata::server_side_encryption(attributes))
+        .set_ssekms_key_id(attributes.sse_kms_key_id.clone())
+        .set_bucket_key_enabled(attributes.bucket_key_enabled)
+        .set_tagging(tagging)
+        .send()
+        .await
+        .map_err(|e| AppError::from_sdk("Failed to create multipart upload", &e))?;
+    let upload_id = output.upload_id().map(str::to_string).ok_or_else(|| {
+        AppError::new(
+            ErrorCode::Service,
+            "Multipart upload id missing from response",
+        )
+    })?;
+
+    let result = match copy_parts(client, source, dest, &upload_id, size, progress).await {
+        Ok(parts) => client
+            .complete_multipart_upload()
+            .bucket(&dest.bucket)
+            .key(&dest.key)
+            .upload_id(&upload_id)
+            .multipart_upload(
+                CompletedMultipartUpload::builder()
+                    .set_parts(Some(parts))
+                    .build(),
+            )
+            .send()
+            .await
+            .map(|_| ())
+            .map_err(|e| AppError::from_sdk("Failed to complete multipart upload", &e)),
+        Err(e) => Err(e),
+    };
+
+    if result.is_err() {
+        if let Err(e) = client
+            .abort_multipart_upload()
+            .bucket(&dest.bucket)
+            .key(&dest.key)
+            .upload_id(&upload_id)
+            .send()
+            .await
+        {
+            warn!(
+                operation = "copy",
+                bucket = %dest.bucket,
+                key = %dest.key,
+                upload_id,
+                "Failed to abort multipart upload: {}",
+                e
+            );
+        }
+    }
+    result
+}
+
+/// 并发复制各个分片，同时在途的分片数不超过 `DEFAULT_CONCURRENCY`
+async fn copy_parts(
+    client: &Client,
+    source: &ObjectLocation,
+    dest: &ObjectLocation,
+    upload_id: &str,
+    size: u64,
+    progress: Option<&ProgressReporter>,
+) -> Result<Vec<CompletedPart>, AppError> {
+    let context = Arc::new(CopyContext {
+        client: client.clone(),
+        source: source.clone(),
+        dest: dest.clone(),
+        upload_id: upload_id.to_string(),
+    });
+    let mut pending = plan_parts(size, effective_part_size(size, COPY_PART_SIZE)).into_iter();
+    let mut tasks = JoinSet::new();
+    let mut parts = Vec::new();
+
+    loop {
+        while tasks.len() < DEFAULT_CONCURRENCY {
+            match pending.next() {
+                Some(part) => {
+                    tasks.spawn(copy_part(context.clone(), part));
+                }
+                None => break,
+            }
+        }
+
+        let Some(joined) = tasks.join_next().await else {
+            break;
+        };
+        match joined {
+            Ok(Ok((part, length))) => {
+                if let Some(progress) = progress {
+                    progress.advance(length);
+                }
+                parts.push(part);
+            }
+            Ok(Err(e)) => return Err(e),
+            Err(e) => return Err(AppError::internal(format!("Part copy task failed: {}", e))),
+        }
+    }
+
+    parts.sort_by_key(|part| part.part_number());
+    Ok(parts)
+}
+
+/// 分片复制期间各个任务共享的上下文
+struct CopyContext {
+    client: Client,
+    source: ObjectLocation,
+    dest: ObjectLocation,
+    upload_id: String,
+}
+
+async fn copy_part(
+    context: Arc<CopyContext>,
+    part: PartRange,
+) -> Result<(CompletedPart, u64), AppError> {
+    let output = context
+        .client
+        .upload_part_copy()
+        .copy_source(context.source.copy_source())
+        .copy_source_range(format!(
+            "bytes={}-{}",
+            part.offset,
+            part.offset + part.length - 1
+        ))
+        .bucket(&context.dest.bucket)
+        .key(&context.dest.key)
+        .upload_id(&context.upload_id)
+        .part_number(part.part_number)
+        .send()
+        .await
+        .map_err(|e| {
+            AppError::from_sdk(&format!("Failed to copy part {}", part.part_number), &e)
+        })?;
+
+    let etag = output
+        .copy_part_result()
+        .and_then(|result| result.e_tag())
+        .map(str::to_string);
+    Ok((
+        CompletedPart::builder()
+            .part_number(part.part_number)
+            .set_e_tag(etag)
+            .build(),
+        part.length,
+    ))
+}
+
+/// 源对象的标签，编码为 `x-amz-tagging` 的格式；不支持标签的服务返回 `None`
+async fn object_tagging(client: &Client, location: &ObjectLocation) -> Option<String> {
+    match tagging::get_tags(client, location, location.version_id.as_deref()).await {
+        Ok(tags) => tagging::encode_tags(&tags),
+        Err(e) => {
+            warn!(
+                operation = "copy",
+                bucket = %location.bucket,
+                key = %location.key,
+                "Failed to read object tags, copying without tags: {}",
+                e
+            );
+            None
+        }
+    }
+}
+
+/// 列出要复制的源对象，同时检查源和目标前缀
+///
+/// 两个前缀都应已经过 [`normalize_prefix`]。源前缀下没有对象、目标前缀位于源前缀之内、
+/// 目标前缀下已有源对象以外的对象，或者复制会覆盖尚未复制的源对象时返回错误。
+/// 目标前缀包含源前缀时（例如把 `a/b/` 移到 `a/`），源对象本身不算作目标前缀下的已有对象。
+pub async fn plan_prefix_copy(
+    client: &Client,
+    source: &ObjectLocation,
+    dest: &ObjectLocation,
+) -> Result<Vec<DeleteTarget>, AppError> {
+    if source.key.is_empty() || dest.key.is_empty() {
+        return Err(AppError::invalid_input("Prefix cannot be empty"));
+    }
+    if source == dest {
+        return Err(AppError::invalid_input(
+            "Source and destination prefixes are the same",
+        ));
+    }
+    let same_bucket = source.bucket == dest.bucket;
+    if same_bucket && dest.key.starts_with(&source.key) {
+        return Err(AppError::invalid_input(
+            "Destination prefix cannot be inside the source prefix",
+        ));
+    }
+
+    let targets = collect_prefix_targets(client, &source.bucket, &source.key, false).await?;
+    if targets.is_empty() {
+        return Err(AppError::not_found(format!(
+            "No objects under prefix {}",
+            source.key
+        )));
+    }
+    if same_bucket {
+        let source_keys: HashSet<&str> = targets.iter().map(|target| target.key.as_str()).collect();
+        // 复制并发进行，目标键与尚未复制的源键相同时会覆盖源对象
+        let overwritten = targets.iter().find_map(|target| {
+            let dest_key = replace_prefix(&source.key, &dest.key, &target.key);
+            source_keys
+                .contains(dest_key.as_str())
+                .then_some((&target.key, dest_key))
+        });
+        if let Some((source_key, dest_key)) = overwritten {
+            return Err(AppError::conflict(format!(
+                "Copying {} would overwrite source object {}",
+                source_key, dest_key
+            )));
+        }
+    }
+    if dest_has_other_objects(client, source, dest).await? {
+        return Err(AppError::conflict(format!(
+            "Destination prefix {} is not empty",
+            dest.key
+        )));
+    }
+    Ok(targets)
+}
+
+/// 复制或移动 [`plan_prefix_copy`] 列出的对象
+///
+/// 复制阶段任何对象失败时停止复制并删除已复制的目标对象，源对象保持不变；
+/// `delete_source` 为 true 时，全部复制成功后再删除源对象。
+pub async fn copy_prefix(
+    client: &Client,
+    source: &ObjectLocation,
+    dest: &ObjectLocation,
+    targets: &[DeleteTarget],
+    delete_source: bool,
+    progress: &ProgressReporter,
+) -> Result<CopyPrefixResult, AppError> {
+    let mut result = CopyPrefixResult {
+        transfer_id: progress.transfer_id().to_string(),
+        object_count: targets.len(),
+        total_size: total_size(targets),
+        copied_count: 0,
+        failed: Vec::new(),
+        rolled_back: false,
+        rollback_errors: Vec::new(),
+        deleted_count: 0,
+        delete_errors: Vec::new(),
+    };
+
+    let mut copied = Vec::new();
+    let mut pending = targets.iter();
+    let mut tasks = JoinSet::new();
+    loop {
+        // 出现失败后不再启动新的复制，等待在途的复制结束后回滚
+        while result.failed.is_empty() && tasks.len() < DEFAULT_CONCURRENCY {
+            let Some(target) = pending.next() else {
+                break;
+            };
+            let client = client.clone();
+            let from = ObjectLocation::new(&source.bucket, &target.key);
+            let to = ObjectLocation::new(
+                &dest.bucket,
+                replace_prefix(&source.key, &dest.key, &target.key),
+            );
+            let size = target.size;
+            let progress = progress.clone();
+            tasks.spawn(async move {
+                let copied = copy_object(&client, &from, &to, size, Some(&progress)).await;
+                (from, to, copied)
+            });
+        }
+
+        let Some(joined) = tasks.join_next().await else {
+            break;
+        };
+        match joined {
+            Ok((_, to, Ok(()))) => copied.push(DeleteTarget::from_key(to.key)),
+            Ok((from, to, Err(error))) => result.failed.push(CopyObjectError {
+                source_key: from.key,
+                dest_key: to.key,
+                error,
+            }),
+            Err(e) => return Err(AppError::internal(format!("Copy task failed: {}", e))),
+        }
+    }
+    result.copied_count = copied.len();
+
+    if let Some(failure) = result.failed.first() {
+        progress.fail(&failure.error);
+        let outcome = delete_targets(client, &dest.bucket, &copied).await?;
+        result.rolled_back = true;
+        result.rollback_errors = outcome.errors;
+        return Ok(result);
+    }
+
+    if delete_source {
+        let outcome = delete_targets(client, &source.bucket, targets).await?;
+        result.deleted_count = outcome.deleted;
+        result.delete_errors = outcome.errors;
+    }
+
+    progress.complete();
+    Ok(result)
+}
+
+/// 目标前缀下是否有源前缀以外的对象
+async fn dest_has_other_objects(
+    client: &Client,
+    source: &ObjectLocation,
+    dest: &ObjectLocation,
+) -> Result<bool, AppError> {
+    let is_source = |key: &str| source.bucket == dest.bucket && key.starts_with(&source.key);
+    let mut continuation_token = None;
+    loop {
+        let output = client
+            .list_objects_v2()
+            .bucket(&dest.bucket)
+            .prefix(&dest.key)
+            .set_continuation_token(continuation_token)
+            .send()
+            .await
+            .map_err(|e| AppError::from_sdk("Failed to list objects", &e))?;
+
+        if output
+            .contents()
+            .iter()
+            .any(|obj| !is_source(obj.key().unwrap_or_default()))
+        {
+            return Ok(true);
+        }
+        match output.next_continuation_token() {
+            Some(token) if output.is_truncated().unwrap_or(false) => {
+                continuation_token = Some(token.to_string())
+            }
+            _ => return Ok(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::MockS3;
+
+    #[test]
+    fn test_copy_source() {
+        let location = ObjectLocation::new("photos", "2024/summer trip/a+b.jpg");
+        assert_eq!(
+            location.copy_source(),
+            "photos/2024/summer%20trip/a%2Bb.jpg"
+        );
+        let location = ObjectLocation::new("docs", "报告.pdf");
+        assert_eq!(location.copy_source(), "docs/%E6%8A%A5%E5%91%8A.pdf");
+        let location = ObjectLocation::new("docs", "a.txt").with_version("3/L4kqtJl+cY");
+        assert_eq!(
+            location.copy_source(),
+            "docs/a.txt?versionId=3%2FL4kqtJl%2BcY"
+        );
+    }
+
+    #[test]
+    fn test_replace_prefix() {
+        assert_eq!(replace_prefix("a/", "b/", "a/x/1.txt"), "b/x/1.txt");
+        assert_eq!(replace_prefix("a/", "a/old/", "a/1.txt"), "a/old/1.txt");
+        assert_eq!(
+            replace_prefix("photos", "images", "photos/1.jpg"),
+            "images/1.jpg"
+        );
+    }
+
+    #[test]
+    fn test_normalize_prefix() {
+        assert_eq!(normalize_prefix("photos"), "photos/");
+        assert_eq!(normalize_prefix("photos/"), "photos/");
+        assert_eq!(normalize_prefix("a/b"), "a/b/");
+        assert_eq!(normalize_prefix(""), "");
+    }
+
+    fn prefix(bucket: &str, prefix: &str) -> ObjectLocation {
+        ObjectLocation::new(bucket, normalize_prefix(prefix))
+    }
+
+    #[tokio::test]
+    async fn test_plan_prefix_copy_into_parent_prefix() {
+        let server = MockS3::start().await;
+        server.put("photos", "a/b/1.jpg", b"one");
+        server.put("photos", "a/b/2024/2.jpg", b"two");
+        let client = server.client().await;
+
+        // The source objects under a/b/ don't make a/ count as non-empty
+        let targets = plan_prefix_copy(&client, &prefix("photos", "a/b"), &prefix("photos", "a"))
+            .await
+            .unwrap();
+        assert_eq!(targets.len(), 2);
+
+        // a/b/b/3.jpg would land on the source key a/b/3.jpg before it is copied
+        server.put("photos", "a/b/3.jpg", b"three");
+        server.put("photos", "a/b/b/3.jpg", b"nested");
+        let error = plan_prefix_copy(&client, &prefix("photos", "a/b"), &prefix("photos", "a"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::Conflict);
+
+        server.put("photos", "c/d/1.jpg", b"one");
+        server.put("photos", "c/other.jpg", b"other");
+        let error = plan_prefix_copy(&client, &prefix("photos", "c/d"), &prefix("photos", "c"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::Conflict);
+
+        let error = plan_prefix_copy(&client, &prefix("photos", "a"), &prefix("photos", "a/b"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidInput);
+    }
+
+    #[tokio::test]
+    async fn test_plan_prefix_copy_matches_whole_folders() {
+        let server = MockS3::start().await;
+        server.put("photos", "photos/1.jpg", b"one");
+        server.put("photos", "photos2/2.jpg", b"two");
+        server.put("photos", "images2/3.jpg", b"three");
+        let client = server.client().await;
+
+        // photos2/ is not under photos/, and images2/ doesn't make images/ non-empty
+        let targets = plan_prefix_copy(
+            &client,
+            &prefix("photos", "photos"),
+            &prefix("photos", "images"),
+        )
+        .await
+        .unwrap();
+        let keys: Vec<&str> = targets.iter().map(|target| target.key.as_str()).collect();
+        assert_eq!(keys, ["photos/1.jpg"]);
+    }
+
+    #[tokio::test]
+    async fn test_move_prefix_rewrites_keys() {
+        let server = MockS3::start().await;
+        server.put("photos", "a/b/1.jpg", b"one");
+        server.put("photos", "a/b/2024/2.jpg", b"two");
+        let client = server.client().await;
+        let source = prefix("photos", "a/b");
+        let dest = prefix("photos", "a");
+
+        let targets = plan_prefix_copy(&client, &source, &dest).await.unwrap();
+        let progress = ProgressReporter::detached(total_size(&targets));
+        let result = copy_prefix(&client, &source, &dest, &targets, true, &progress)
+            .await
+            .unwrap();
+
+        assert_eq!(result.copied_count, 2);
+        assert_eq!(result.deleted_count, 2);
+        assert!(result.failed.is_empty());
+        assert_eq!(server.keys("photos"), ["a/1.jpg", "a/2024/2.jpg"]);
+        assert_eq!(progress.bytes_done(), 6);
+    }
+}
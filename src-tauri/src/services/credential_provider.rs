@@ -0,0 +1,223 @@
+use std::time::SystemTime;
+
+use aws_config::environment::EnvironmentVariableCredentialsProvider;
+use aws_config::profile::ProfileFileCredentialsProvider;
+use aws_config::BehaviorVersion;
+use aws_credential_types::provider::error::CredentialsError;
+use aws_credential_types::provider::{future, ProvideCredentials, SharedCredentialsProvider};
+use aws_sdk_s3::config::{Credentials, Region};
+
+use crate::error::AppError;
+use crate::models::s3::{CredentialSource, S3Config};
+use crate::services::transport;
+
+/// AssumeRole 未指定会话名时使用的名称
+const DEFAULT_SESSION_NAME: &str = "snowy-oss";
+
+/// 根据配置的凭证来源创建凭证提供者，返回 `None` 表示匿名访问
+///
+/// 临时凭证（AssumeRole、SSO、credential_process 等）由 SDK 缓存，过期前自动重新获取。
+pub fn credentials_provider(
+    config: &S3Config,
+    region: &Region,
+) -> Result<Option<SharedCredentialsProvider>, AppError> {
+    let provider = match &config.credential_source {
+        CredentialSource::Static => {
+            SharedCredentialsProvider::new(static_credentials(config, None))
+        }
+        CredentialSource::SessionToken { session_token } => {
+            SharedCredentialsProvider::new(static_credentials(config, Some(session_token.clone())))
+        }
+        CredentialSource::AssumeRole {
+            role_arn,
+            session_name,
+            external_id,
+            mfa_serial,
+            mfa_code,
+            duration_seconds,
+            sts_endpoint,
+        } => {
+            let mut sts_config = aws_sdk_sts::Config::builder()
+                .behavior_version(BehaviorVersion::latest())
+                .region(region.clone())
+                .credentials_provider(static_credentials(config, None));
+            sts_config.set_endpoint_url(sts_endpoint.clone());
+            // STS 请求与 S3 请求走同一个代理，但不使用存储服务地址的证书固定和跳过校验
+            sts_config.set_http_client(transport::proxy_http_client(&config.network)?);
+
+            SharedCredentialsProvider::new(AssumeRoleProvider {
+                sts: aws_sdk_sts::Client::from_conf(sts_config.build()),
+                role_arn: role_arn.clone(),
+                session_name: session_name
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_SESSION_NAME.to_string()),
+                external_id: external_id.clone(),
+                mfa_serial: mfa_serial.clone(),
+                mfa_code: mfa_code.clone(),
+                duration_seconds: *duration_seconds,
+            })
+        }
+        CredentialSource::Environment => {
+            SharedCredentialsProvider::new(EnvironmentVariableCredentialsProvider::new())
+        }
+        CredentialSource::Profile { name } => SharedCredentialsProvider::new(
+            ProfileFileCredentialsProvider::builder()
+                .profile_name(name)
+                .build(),
+        ),
+        CredentialSource::Anonymous => return Ok(None),
+    };
+
+    Ok(Some(provider))
+}
+
+fn static_credentials(config: &S3Config, session_token: Option<String>) -> Credentials {
+    Credentials::new(
+        &config.access_key_id,
+        &config.secret_access_key,
+        session_token,
+        None,
+        "custom",
+    )
+}
+
+/// 通过 STS AssumeRole 获取临时凭证
+///
+/// SDK 自带的 `AssumeRoleProvider` 不支持 MFA，这里直接调用 STS。
+/// MFA 验证码只能使用一次，会话过期后需要重新输入验证码。
+#[derive(Debug)]
+struct AssumeRoleProvider {
+    sts: aws_sdk_sts::Client,
+    role_arn: String,
+    session_name: String,
+    external_id: Option<String>,
+    mfa_serial: Option<String>,
+    mfa_code: Option<String>,
+    duration_seconds: Option<i32>,
+}
+
+impl AssumeRoleProvider {
+    async fn assume_role(&self) -> Result<Credentials, CredentialsError> {
+        if self.mfa_serial.is_some() && self.mfa_code.is_none() {
+            return Err(CredentialsError::not_loaded(
+                "MFA code required to assume role",
+            ));
+        }
+
+        let output = self
+            .sts
+            .assume_role()
+            .role_arn(&self.role_arn)
+            .role_session_name(&self.session_name)
+            .set_external_id(self.external_id.clone())
+            .set_serial_number(self.mfa_serial.clone())
+            .set_token_code(self.mfa_code.clone())
+            .set_duration_seconds(self.duration_seconds)
+            .send()
+            .await
+            .map_err(CredentialsError::provider_error)?;
+
+        let credentials = output.credentials().ok_or_else(|| {
+            CredentialsError::unhandled("AssumeRole response did not include credentials")
+        })?;
+        let expiry = SystemTime::try_from(*credentials.expiration()).ok();
+
+        Ok(Credentials::new(
+            credentials.access_key_id(),
+            credentials.secret_access_key(),
+            Some(credentials.session_token().to_string()),
+            expiry,
+            "assume-role",
+        ))
+    }
+}
+
+impl ProvideCredentials for AssumeRoleProvider {
+    fn provide_credentials<'a>(&'a self) -> future::ProvideCredentials<'a>
+    where
+        Self: 'a,
+    {
+        future::ProvideCredentials::new(self.assume_role())
+    }
+}
+
+/// 凭证来源的标识，用于区分同一服务下的不同身份
+pub fn credential_identity(config: &S3Config) -> String {
+    match &config.credential_source {
+        CredentialSource::Static | CredentialSource::SessionToken { .. } => {
+            config.access_key_id.clone()
+        }
+        CredentialSource::AssumeRole { role_arn, .. } => {
+            format!("{}>{}", config.access_key_id, role_arn)
+        }
+        CredentialSource::Environment => "env".to_string(),
+        CredentialSource::Profile { name } => format!("profile:{}", name),
+        CredentialSource::Anonymous => "anonymous".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_test_config;
+
+    #[tokio::test]
+    async fn test_session_token_credentials() {
+        let mut config = create_test_config();
+        config.credential_source = CredentialSource::SessionToken {
+            session_token: "test_token".to_string(),
+        };
+
+        let provider = credentials_provider(&config, &Region::new("us-east-1"))
+            .unwrap()
+            .unwrap();
+        let credentials = provider.provide_credentials().await.unwrap();
+        assert_eq!(credentials.access_key_id(), "test_access_key");
+        assert_eq!(credentials.session_token(), Some("test_token"));
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_requires_mfa_code() {
+        let mut config = create_test_config();
+        config.credential_source = CredentialSource::AssumeRole {
+            role_arn: "arn:aws:iam::123456789012:role/test".to_string(),
+            session_name: None,
+            external_id: None,
+            mfa_serial: Some("arn:aws:iam::123456789012:mfa/test".to_string()),
+            mfa_code: None,
+            duration_seconds: None,
+            sts_endpoint: None,
+        };
+
+        let provider = credentials_provider(&config, &Region::new("us-east-1"))
+            .unwrap()
+            .unwrap();
+        assert!(provider.provide_credentials().await.is_err());
+    }
+
+    #[test]
+    fn test_anonymous_has_no_provider() {
+        let mut config = create_test_config();
+        config.credential_source = CredentialSource::Anonymous;
+        assert!(credentials_provider(&config, &Region::new("us-east-1"))
+            .unwrap()
+            .is_none());
+        assert_eq!(credential_identity(&config), "anonymous");
+    }
+
+    #[test]
+    fn test_mfa_code_is_not_serialized() {
+        let source = CredentialSource::AssumeRole {
+            role_arn: "arn:aws:iam::123456789012:role/test".to_string(),
+            session_name: None,
+            external_id: None,
+            mfa_serial: None,
+            mfa_code: Some("000999".to_string()),
+            duration_seconds: None,
+            sts_endpoint: None,
+        };
+        let json = serde_json::to_string(&source).unwrap();
+        assert!(json.contains("\"type\":\"assumeRole\""));
+        assert!(!json.contains("000999"));
+    }
+}
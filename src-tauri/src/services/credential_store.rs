@@ -0,0 +1,553 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use argon2::Argon2;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{error, info, warn};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::error::{AppError, ErrorCode};
+use crate::models::profile::{CredentialBackend, CredentialStatus, Credentials};
+use crate::services::client_pool::S3ClientPool;
+use crate::services::logging;
+use crate::utils::storage::{read_json, write_json_atomic};
+
+/// 系统钥匙串中的服务名
+const KEYRING_SERVICE: &str = "com.snowy-oss.app";
+
+/// 加密凭证文件名
+const VAULT_FILE: &str = "credentials.vault";
+
+/// 凭证存储被自动锁定时发送的事件
+pub const CREDENTIALS_LOCKED_EVENT: &str = "credentials-locked";
+
+/// 默认空闲 15 分钟后自动锁定
+pub const DEFAULT_AUTO_LOCK_SECONDS: u64 = 15 * 60;
+
+/// 检查是否需要自动锁定的间隔
+const AUTO_LOCK_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+const VAULT_VERSION: u32 = 1;
+const SALT_LEN: usize = 16;
+
+const LOCKED: &str = "Credential vault is locked";
+
+/// 日志中的操作名
+const OPERATION: &str = "credentials";
+
+/// 持久化的加密凭证文件
+///
+/// 内容为 `HashMap<配置 ID, Credentials>` 的 JSON，使用 XChaCha20-Poly1305 加密，
+/// 密钥由主密码经 Argon2id 派生。自动锁定时间以明文保存，作为附加数据参与认证，被改动后无法解锁。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SealedVault {
+    version: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+    #[serde(rename = "autoLockSeconds")]
+    auto_lock_seconds: u64,
+}
+
+/// 解锁后的密钥和凭证，锁定（释放）时从内存中清零
+struct UnlockedVault {
+    key: Zeroizing<[u8; 32]>,
+    salt: Vec<u8>,
+    entries: HashMap<String, Credentials>,
+    last_used: Instant,
+}
+
+impl Drop for UnlockedVault {
+    fn drop(&mut self) {
+        self.entries.values_mut().for_each(Zeroize::zeroize);
+    }
+}
+
+struct Vault {
+    path: Option<PathBuf>,
+    sealed: Option<SealedVault>,
+    unlocked: Option<UnlockedVault>,
+    auto_lock_seconds: u64,
+}
+
+enum Backend {
+    Keyring,
+    Vault(Box<Mutex<Vault>>),
+}
+
+/// 访问密钥的安全存储
+///
+/// 系统钥匙串可用时保存在钥匙串中；否则保存在主密码加密的文件里，
+/// 此时需要先解锁，空闲超过设定时间后会自动重新锁定。
+pub struct CredentialStore {
+    backend: Backend,
+}
+
+impl CredentialStore {
+    /// 优先使用系统钥匙串，不可用时使用应用数据目录下的加密文件
+    pub fn load(app: &AppHandle) -> Self {
+        if keyring_available() {
+            return Self {
+                backend: Backend::Keyring,
+            };
+        }
+
+        info!(
+            operation = OPERATION,
+            "System keyring unavailable, using encrypted credential file"
+        );
+        match app.path().app_data_dir() {
+            Ok(dir) => Self::vault(Some(dir.join(VAULT_FILE))),
+            Err(e) => {
+                warn!(
+                    operation = OPERATION,
+                    "Failed to resolve app data dir, credentials will not persist: {}", e
+                );
+                Self::vault(None)
+            }
+        }
+    }
+
+    /// 使用加密文件存储，`path` 为 `None` 时只保存在内存中
+    pub fn vault(path: Option<PathBuf>) -> Self {
+        let sealed = match path.as_deref().map(read_json::<SealedVault>) {
+            Some(Ok(sealed)) => sealed,
+            Some(Err(e)) => {
+                error!(
+                    operation = OPERATION,
+                    "Failed to load credential vault: {}", e
+                );
+                None
+            }
+            None => None,
+        };
+        let auto_lock_seconds = sealed
+            .as_ref()
+            .map(|sealed| sealed.auto_lock_seconds)
+            .unwrap_or(DEFAULT_AUTO_LOCK_SECONDS);
+
+        Self {
+            backend: Backend::Vault(Box::new(Mutex::new(Vault {
+                path,
+                sealed,
+                unlocked: None,
+                auto_lock_seconds,
+            }))),
+        }
+    }
+
+    pub fn status(&self) -> CredentialStatus {
+        match &self.backend {
+            Backend::Keyring => CredentialStatus {
+                backend: CredentialBackend::Keyring,
+                locked: false,
+                initialized: true,
+                auto_lock_seconds: 0,
+            },
+            Backend::Vault(vault) => {
+                let vault = vault.lock().unwrap();
+                CredentialStatus {
+                    backend: CredentialBackend::Vault,
+                    locked: vault.unlocked.is_none(),
+                    initialized: vault.sealed.is_some(),
+                    auto_lock_seconds: vault.auto_lock_seconds,
+                }
+            }
+        }
+    }
+
+    /// 用主密码解锁，第一次解锁时使用该密码创建加密文件
+    pub fn unlock(&self, password: &str) -> Result<(), AppError> {
+        let Backend::Vault(vault) = &self.backend else {
+            return Ok(());
+        };
+        if password.is_empty() {
+            return Err(AppError::invalid_input("Master password cannot be empty"));
+        }
+
+        let mut vault = vault.lock().unwrap();
+        let unlocked = match &vault.sealed {
+            Some(sealed) => open_vault(sealed, password)?,
+            None => {
+                let mut salt = vec![0u8; SALT_LEN];
+                OsRng.fill_bytes(&mut salt);
+                UnlockedVault {
+                    key: derive_key(password, &salt)?,
+                    salt,
+                    entries: HashMap::new(),
+                    last_used: Instant::now(),
+                }
+            }
+        };
+
+        vault.unlocked = Some(unlocked);
+        if vault.sealed.is_none() {
+            vault.save()?;
+        }
+        Ok(())
+    }
+
+    /// 锁定加密文件，清除内存中的密钥和凭证
+    pub fn lock(&self) {
+        if let Backend::Vault(vault) = &self.backend {
+            vault.lock().unwrap().unlocked = None;
+        }
+    }
+
+    /// 空闲超时时锁定，返回是否执行了锁定
+    pub fn lock_if_idle(&self) -> bool {
+        let Backend::Vault(vault) = &self.backend else {
+            return false;
+        };
+
+        let mut vault = vault.lock().unwrap();
+        let timeout = vault.auto_lock_seconds;
+        match &vault.unlocked {
+            Some(unlocked) if is_idle(unlocked.last_used, timeout, Instant::now()) => {
+                vault.unlocked = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// 设置自动锁定时间（秒），0 表示不自动锁定，需要先解锁
+    pub fn set_auto_lock(&self, seconds: u64) -> Result<(), AppError> {
+        let Backend::Vault(vault) = &self.backend else {
+            return Ok(());
+        };
+
+        let mut vault = vault.lock().unwrap();
+        vault.unlocked_mut()?;
+        vault.auto_lock_seconds = seconds;
+        vault.save()
+    }
+
+    pub fn get(&self, profile_id: &str) -> Result<Credentials, AppError> {
+        match &self.backend {
+            Backend::Keyring => {
+                let secret = keyring_entry(profile_id)?
+                    .get_password()
+                    .map_err(|e| match e {
+                        keyring::Error::NoEntry => missing(profile_id),
+                        e => AppError::internal(format!(
+                            "Failed to read credentials from keyring: {}",
+                            e
+                        )),
+                    })?;
+                serde_json::from_str(&secret).map_err(|e| {
+                    AppError::internal(format!("Failed to parse stored credentials: {}", e))
+                })
+            }
+            Backend::Vault(vault) => {
+                let mut vault = vault.lock().unwrap();
+                let unlocked = vault.unlocked_mut()?;
+                unlocked
+                    .entries
+                    .get(profile_id)
+                    .cloned()
+                    .ok_or_else(|| missing(profile_id))
+            }
+        }
+    }
+
+    pub fn set(&self, profile_id: &str, credentials: &Credentials) -> Result<(), AppError> {
+        match &self.backend {
+            Backend::Keyring => {
+                let secret = serde_json::to_string(credentials)
+                    .map_err(|e| AppError::internal(e.to_string()))?;
+                keyring_entry(profile_id)?
+                    .set_password(&secret)
+                    .map_err(|e| {
+                        AppError::internal(format!("Failed to save credentials to keyring: {}", e))
+                    })
+            }
+            Backend::Vault(vault) => {
+                let mut vault = vault.lock().unwrap();
+                if let Some(mut replaced) = vault
+                    .unlocked_mut()?
+                    .entries
+                    .insert(profile_id.to_string(), credentials.clone())
+                {
+                    replaced.zeroize();
+                }
+                vault.save()
+            }
+        }
+    }
+
+    pub fn delete(&self, profile_id: &str) -> Result<(), AppError> {
+        match &self.backend {
+            Backend::Keyring => match keyring_entry(profile_id)?.delete_credential() {
+                Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+                Err(e) => Err(AppError::internal(format!(
+                    "Failed to delete credentials from keyring: {}",
+                    e
+                ))),
+            },
+            Backend::Vault(vault) => {
+                let mut vault = vault.lock().unwrap();
+                if let Some(mut removed) = vault.unlocked_mut()?.entries.remove(profile_id) {
+                    removed.zeroize();
+                }
+                vault.save()
+            }
+        }
+    }
+
+    /// 后台定时检查空闲时间，超时后锁定并清空客户端缓存
+    pub fn start_auto_lock(self: &Arc<Self>, app: AppHandle) {
+        if matches!(self.backend, Backend::Keyring) {
+            return;
+        }
+
+        let store = self.clone();
+        tauri::async_runtime::spawn(async move {
+            loop {
+                tokio::time::sleep(AUTO_LOCK_CHECK_INTERVAL).await;
+                if store.lock_if_idle() {
+                    app.state::<S3ClientPool>().clear();
+                    logging::clear_secrets();
+                    if let Err(e) = app.emit(CREDENTIALS_LOCKED_EVENT, ()) {
+                        warn!(
+                            operation = OPERATION,
+                            "Failed to emit credentials locked event: {}", e
+                        );
+                    }
+                }
+            }
+        });
+    }
+}
+
+impl Vault {
+    fn unlocked_mut(&mut self) -> Result<&mut UnlockedVault, AppError> {
+        let unlocked = self.unlocked.as_mut().ok_or_else(locked)?;
+        unlocked.last_used = Instant::now();
+        Ok(unlocked)
+    }
+
+    /// 使用新的随机 nonce 重新加密并写入文件
+    fn save(&mut self) -> Result<(), AppError> {
+        let unlocked = self.unlocked.as_ref().ok_or_else(locked)?;
+
+        let plaintext = Zeroizing::new(
+            serde_json::to_vec(&unlocked.entries).map_err(|e| AppError::internal(e.to_string()))?,
+        );
+        let cipher = XChaCha20Poly1305::new(Key::from_slice(&unlocked.key[..]));
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext.as_slice(),
+                    aad: &self.auto_lock_seconds.to_le_bytes(),
+                },
+            )
+            .map_err(|e| AppError::internal(format!("Failed to encrypt credentials: {}", e)))?;
+
+        let sealed = SealedVault {
+            version: VAULT_VERSION,
+            salt: BASE64.encode(&unlocked.salt),
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+            auto_lock_seconds: self.auto_lock_seconds,
+        };
+
+        if let Some(path) = &self.path {
+            write_json_atomic(path, &sealed).map_err(|e| {
+                AppError::new(
+                    ErrorCode::Io,
+                    format!("Failed to save credential vault: {}", e),
+                )
+            })?;
+        }
+        self.sealed = Some(sealed);
+        Ok(())
+    }
+}
+
+fn open_vault(sealed: &SealedVault, password: &str) -> Result<UnlockedVault, AppError> {
+    if sealed.version != VAULT_VERSION {
+        return Err(AppError::internal(format!(
+            "Unsupported credential vault version: {}",
+            sealed.version
+        )));
+    }
+
+    let decode = |value: &str| {
+        BASE64
+            .decode(value)
+            .map_err(|e| AppError::internal(format!("Corrupted credential vault: {}", e)))
+    };
+    let salt = decode(&sealed.salt)?;
+    let nonce = decode(&sealed.nonce)?;
+    let ciphertext = decode(&sealed.ciphertext)?;
+    if nonce.len() != 24 {
+        return Err(AppError::internal(
+            "Corrupted credential vault: invalid nonce",
+        ));
+    }
+
+    let key = derive_key(password, &salt)?;
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(&key[..]));
+    // 认证失败说明密码错误（或文件被篡改）
+    let plaintext = cipher
+        .decrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: ciphertext.as_slice(),
+                aad: &sealed.auto_lock_seconds.to_le_bytes(),
+            },
+        )
+        .map(Zeroizing::new)
+        .map_err(|_| AppError::new(ErrorCode::WrongPassword, "Incorrect master password"))?;
+    let entries = serde_json::from_slice(&plaintext)
+        .map_err(|e| AppError::internal(format!("Corrupted credential vault: {}", e)))?;
+
+    Ok(UnlockedVault {
+        key,
+        salt,
+        entries,
+        last_used: Instant::now(),
+    })
+}
+
+fn derive_key(password: &str, salt: &[u8]) -> Result<Zeroizing<[u8; 32]>, AppError> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, &mut key[..])
+        .map_err(|e| AppError::internal(format!("Failed to derive key: {}", e)))?;
+    Ok(key)
+}
+
+/// 距离上次使用是否已超过自动锁定时间
+pub fn is_idle(last_used: Instant, auto_lock_seconds: u64, now: Instant) -> bool {
+    auto_lock_seconds > 0
+        && now.saturating_duration_since(last_used) >= Duration::from_secs(auto_lock_seconds)
+}
+
+fn locked() -> AppError {
+    AppError::new(ErrorCode::Locked, LOCKED)
+}
+
+fn missing(profile_id: &str) -> AppError {
+    AppError::not_found(format!("No credentials saved for profile {}", profile_id))
+}
+
+fn keyring_entry(profile_id: &str) -> Result<keyring::Entry, AppError> {
+    keyring::Entry::new(KEYRING_SERVICE, profile_id)
+        .map_err(|e| AppError::internal(format!("Failed to open keyring entry: {}", e)))
+}
+
+/// 读取一个不存在的条目来探测系统钥匙串是否可用
+fn keyring_available() -> bool {
+    match keyring_entry("__probe__").map(|entry| entry.get_password()) {
+        Ok(Ok(_)) | Ok(Err(keyring::Error::NoEntry)) => true,
+        Ok(Err(e)) => {
+            warn!(operation = OPERATION, "System keyring probe failed: {}", e);
+            false
+        }
+        Err(e) => {
+            warn!(operation = OPERATION, "{}", e);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_credentials() -> Credentials {
+        Credentials {
+            access_key_id: "test_access_key".to_string(),
+            secret_access_key: "test_secret_key".to_string(),
+            session_token: None,
+            proxy_password: None,
+        }
+    }
+
+    #[test]
+    fn test_vault_lock_and_unlock() {
+        let dir = std::env::temp_dir().join(format!("snowy-vault-test-{}", std::process::id()));
+        let path = dir.join(VAULT_FILE);
+
+        let store = CredentialStore::vault(Some(path.clone()));
+        assert!(store.status().locked);
+        assert!(!store.status().initialized);
+        assert_eq!(store.get("profile-1").unwrap_err().code, ErrorCode::Locked);
+
+        store.unlock("correct horse").unwrap();
+        store.set("profile-1", &create_test_credentials()).unwrap();
+        store.lock();
+        assert!(store.get("profile-1").is_err());
+
+        // The file on disk never contains the secret in plain text
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("test_secret_key"));
+
+        let reopened = CredentialStore::vault(Some(path));
+        assert!(reopened.status().initialized);
+        assert_eq!(
+            reopened.unlock("wrong password").unwrap_err().code,
+            ErrorCode::WrongPassword
+        );
+        reopened.unlock("correct horse").unwrap();
+        let credentials = reopened.get("profile-1").unwrap();
+        assert_eq!(credentials.secret_access_key, "test_secret_key");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_auto_lock_requires_unlock() {
+        let dir = std::env::temp_dir().join(format!("snowy-autolock-test-{}", std::process::id()));
+        let path = dir.join(VAULT_FILE);
+
+        let store = CredentialStore::vault(Some(path.clone()));
+        assert_eq!(store.set_auto_lock(60).unwrap_err().code, ErrorCode::Locked);
+
+        store.unlock("correct horse").unwrap();
+        store.set_auto_lock(120).unwrap();
+        store.lock();
+        assert_eq!(store.set_auto_lock(0).unwrap_err().code, ErrorCode::Locked);
+        assert_eq!(store.status().auto_lock_seconds, 120);
+
+        let reopened = CredentialStore::vault(Some(path.clone()));
+        assert_eq!(reopened.status().auto_lock_seconds, 120);
+        reopened.unlock("correct horse").unwrap();
+
+        // Editing the plain-text setting in the file breaks authentication
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(
+            &path,
+            content.replace("\"autoLockSeconds\": 120", "\"autoLockSeconds\": 0"),
+        )
+        .unwrap();
+        let tampered = CredentialStore::vault(Some(path));
+        assert_eq!(tampered.status().auto_lock_seconds, 0);
+        assert!(tampered.unlock("correct horse").is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_is_idle() {
+        let start = Instant::now();
+        let later = start + Duration::from_secs(60);
+
+        assert!(is_idle(start, 60, later));
+        assert!(!is_idle(start, 61, later));
+        // Zero disables auto-lock
+        assert!(!is_idle(start, 0, later));
+    }
+}
@@ -0,0 +1,340 @@
+use aws_sdk_s3::types::{BucketVersioningStatus, Delete, ObjectIdentifier};
+use aws_sdk_s3::Client;
+use tracing::warn;
+
+use crate::error::AppError;
+use crate::models::s3::DeleteObjectError;
+
+/// 单次 DeleteObjects 请求最多包含的键数量
+pub const DELETE_BATCH_SIZE: usize = 1000;
+
+/// 待删除的对象或对象版本
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeleteTarget {
+    pub key: String,
+    pub version_id: Option<String>,
+    pub size: u64,
+}
+
+impl DeleteTarget {
+    pub fn from_key(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            version_id: None,
+            size: 0,
+        }
+    }
+}
+
+/// 批量删除的结果
+#[derive(Debug, Default)]
+pub struct DeleteOutcome {
+    pub deleted: usize,
+    pub errors: Vec<DeleteObjectError>,
+}
+
+/// 待删除对象的总大小
+pub fn total_size(targets: &[DeleteTarget]) -> u64 {
+    targets.iter().map(|target| target.size).sum()
+}
+
+/// 存储桶是否开启（或曾经开启）版本控制
+///
+/// 服务不支持版本控制接口时按未开启处理，只删除当前对象；
+/// 无权读取版本控制状态时返回错误，避免在开启了版本控制的存储桶上遗留历史版本。
+pub async fn is_versioned(client: &Client, bucket: &str) -> Result<bool, AppError> {
+    let output = match client.get_bucket_versioning().bucket(bucket).send().await {
+        Ok(output) => output,
+        Err(e) => {
+            let error = AppError::from_sdk("Failed to get bucket versioning", &e);
+            if !versioning_unavailable(&error) {
+                return Err(error);
+            }
+            warn!(
+                operation = "delete_prefix",
+                bucket,
+                code = ?error.code,
+                "Bucket versioning status unavailable, deleting current objects only: {}",
+                error
+            );
+            return Ok(false);
+        }
+    };
+
+    Ok(matches!(
+        output.status(),
+        Some(BucketVersioningStatus::Enabled) | Some(BucketVersioningStatus::Suspended)
+    ))
+}
+
+/// 服务不支持 GetBucketVersioning（例如 R2、部分 MinIO 和 Ceph 网关）
+pub fn versioning_unavailable(error: &AppError) -> bool {
+    error.status == Some(501)
+        || matches!(
+            error.s3_code.as_deref(),
+            Some("NotImplemented" | "MethodNotAllowed")
+        )
+}
+
+/// 列出前缀下的所有对象；`versioned` 为 true 时列出所有版本和删除标记
+pub async fn collect_prefix_targets(
+    client: &Client,
+    bucket: &str,
+    prefix: &str,
+    versioned: bool,
+) -> Result<Vec<DeleteTarget>, AppError> {
+    let mut targets = Vec::new();
+
+    if !versioned {
+        let mut continuation_token = None;
+        loop {
+            let output = client
+                .list_objects_v2()
+                .bucket(bucket)
+                .prefix(prefix)
+                .set_continuation_token(continuation_token)
+                .send()
+                .await
+                .map_err(|e| AppError::from_sdk("Failed to list objects", &e))?;
+
+            targets.extend(output.contents().iter().map(|obj| DeleteTarget {
+                key: obj.key().unwrap_or_default().to_string(),
+                version_id: None,
+                size: obj.size().unwrap_or_default().max(0) as u64,
+            }));
+
+            match output.next_continuation_token() {
+                Some(token) if output.is_truncated().unwrap_or(false) => {
+                    continuation_token = Some(token.to_string())
+                }
+                _ => break,
+            }
+        }
+        return Ok(targets);
+    }
+
+    let mut key_marker = None;
+    let mut version_id_marker = None;
+    loop {
+        let output = client
+            .list_object_versions()
+            .bucket(bucket)
+            .prefix(prefix)
+            .set_key_marker(key_marker)
+            .set_version_id_marker(version_id_marker)
+            .send()
+            .await
+            .map_err(|e| AppError::from_sdk("Failed to list object versions", &e))?;
+
+        targets.extend(output.versions().iter().map(|version| DeleteTarget {
+            key: version.key().unwrap_or_default().to_string(),
+            version_id: version.version_id().map(|id| id.to_string()),
+            size: version.size().unwrap_or_default().max(0) as u64,
+        }));
+        targets.extend(output.delete_markers().iter().map(|marker| DeleteTarget {
+            key: marker.key().unwrap_or_default().to_string(),
+            version_id: marker.version_id().map(|id| id.to_string()),
+            size: 0,
+        }));
+
+        if !output.is_truncated().unwrap_or(false) {
+            break;
+        }
+        key_marker = output.next_key_marker().map(|marker| marker.to_string());
+        version_id_marker = output
+            .next_version_id_marker()
+            .map(|marker| marker.to_string());
+        if key_marker.is_none() && version_id_marker.is_none() {
+            break;
+        }
+    }
+
+    Ok(targets)
+}
+
+/// 使用 DeleteObjects 按每批 1000 个键删除，逐个键汇总错误
+pub async fn delete_targets(
+    client: &Client,
+    bucket: &str,
+    targets: &[DeleteTarget],
+) -> Result<DeleteOutcome, AppError> {
+    let mut outcome = DeleteOutcome::default();
+
+    for batch in targets.chunks(DELETE_BATCH_SIZE) {
+        let objects = batch
+            .iter()
+            .map(|target| {
+                ObjectIdentifier::builder()
+                    .key(&target.key)
+                    .set_version_id(target.version_id.clone())
+                    .build()
+                    .map_err(|e| {
+                        AppError::invalid_input(format!(
+                            "Invalid object key '{}': {}",
+                            target.key, e
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>, AppError>>()?;
+
+        let delete = Delete::builder()
+            .set_objects(Some(objects))
+            .quiet(true)
+            .build()
+            .map_err(|e| AppError::internal(format!("Failed to build delete request: {}", e)))?;
+
+        match client
+            .delete_objects()
+            .bucket(bucket)
+            .delete(delete)
+            .send()
+            .await
+        {
+            Ok(output) => {
+                let errors: Vec<DeleteObjectError> = output
+                    .errors()
+                    .iter()
+                    .map(|error| DeleteObjectError {
+                        key: error.key().unwrap_or_default().to_string(),
+                        version_id: error.version_id().map(|id| id.to_string()),
+                        code: error.code().map(|code| code.to_string()),
+                        message: error.message().map(|message| message.to_string()),
+                    })
+                    .collect();
+                outcome.deleted += batch.len() - errors.len();
+                outcome.errors.extend(errors);
+            }
+            // 整批请求失败时，这一批的每个键都记为失败
+            Err(e) => {
+                let error = AppError::from_sdk("Failed to delete objects", &e);
+                outcome
+                    .errors
+                    .extend(batch.iter().map(|target| DeleteObjectError {
+                        key: target.key.clone(),
+                        version_id: target.version_id.clone(),
+                        code: error.s3_code.clone(),
+                        message: Some(error.message.clone()),
+                    }));
+            }
+        }
+    }
+
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+    use crate::test_support::MockS3;
+
+    #[test]
+    fn test_versioning_unavailable() {
+        let not_implemented = AppError {
+            s3_code: Some("NotImplemented".to_string()),
+            status: Some(501),
+            ..AppError::new(ErrorCode::Service, "not implemented")
+        };
+        let denied = AppError::new(ErrorCode::AccessDenied, "access denied");
+        let throttled = AppError::new(ErrorCode::Throttled, "slow down");
+
+        assert!(versioning_unavailable(&not_implemented));
+        // A key that cannot read the status may still be on a versioned bucket
+        assert!(!versioning_unavailable(&denied));
+        assert!(!versioning_unavailable(&throttled));
+        assert!(!versioning_unavailable(&AppError::not_found(
+            "no such bucket"
+        )));
+    }
+
+    #[test]
+    fn test_total_size() {
+        let targets = vec![
+            DeleteTarget {
+                key: "a.jpg".to_string(),
+                version_id: Some("v1".to_string()),
+                size: 100,
+            },
+            DeleteTarget {
+                key: "a.jpg".to_string(),
+                version_id: Some("v2".to_string()),
+                size: 50,
+            },
+            // Delete markers carry no data
+            DeleteTarget {
+                key: "b.jpg".to_string(),
+                version_id: Some("marker".to_string()),
+                size: 0,
+            },
+        ];
+        assert_eq!(total_size(&targets), 150);
+        assert_eq!(total_size(&[]), 0);
+    }
+
+    #[test]
+    fn test_from_key() {
+        let target = DeleteTarget::from_key("thumbs/1.jpg");
+        assert_eq!(target.key, "thumbs/1.jpg");
+        assert!(target.version_id.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_is_versioned_without_versioning_api() {
+        // The mock answers GetBucketVersioning with 501 NotImplemented, like R2
+        let server = MockS3::start().await;
+        let client = server.client().await;
+        assert!(!is_versioned(&client, "photos").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_collect_prefix_targets_pages_through_prefix() {
+        let server = MockS3::start().await;
+        server.set_page_size(2);
+        for key in [
+            "thumbs/1.jpg",
+            "thumbs/2.jpg",
+            "thumbs/3.jpg",
+            "thumbs2/4.jpg",
+        ] {
+            server.put("photos", key, b"jpeg");
+        }
+        let client = server.client().await;
+
+        let targets = collect_prefix_targets(&client, "photos", "thumbs/", false)
+            .await
+            .unwrap();
+        let keys: Vec<&str> = targets.iter().map(|target| target.key.as_str()).collect();
+        assert_eq!(keys, ["thumbs/1.jpg", "thumbs/2.jpg", "thumbs/3.jpg"]);
+        assert_eq!(total_size(&targets), 12);
+    }
+
+    #[tokio::test]
+    async fn test_delete_targets_batches_by_1000_keys() {
+        let server = MockS3::start().await;
+        let targets: Vec<DeleteTarget> = (0..2500)
+            .map(|index| DeleteTarget::from_key(format!("logs/{:04}.txt", index)))
+            .collect();
+        for target in &targets {
+            server.put("photos", &target.key, b"log");
+        }
+        server.put("photos", "keep.txt", b"keep");
+        let client = server.client().await;
+
+        let outcome = delete_targets(&client, "photos", &targets).await.unwrap();
+        assert_eq!(outcome.deleted, 2500);
+        assert!(outcome.errors.is_empty());
+        assert_eq!(server.keys("photos"), ["keep.txt"]);
+
+        let batches: Vec<usize> = server
+            .requests()
+            .iter()
+            .filter(|request| request.param("delete").is_some())
+            .map(|request| {
+                String::from_utf8_lossy(&request.body)
+                    .matches("<Key>")
+                    .count()
+            })
+            .collect();
+        assert_eq!(batches, [1000, 1000, 500]);
+    }
+}
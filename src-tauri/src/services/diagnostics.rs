@@ -0,0 +1,796 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use aws_sdk_s3::config::http::HttpResponse;
+use aws_sdk_s3::error::{DisplayErrorContext, ProvideErrorMetadata, SdkError};
+use aws_smithy_types::date_time::{DateTime, Format};
+use tokio::net::TcpStream;
+use tokio_rustls::rustls;
+use tokio_rustls::rustls::pki_types::ServerName;
+use url::{Host, Url};
+
+use crate::models::diagnostics::{
+    DiagnosticCheck, DiagnosticReport, DiagnosticStatus, DiagnosticStep,
+};
+use crate::models::s3::{AddressingStyle, NetworkSettings, ProxySettings, S3Config};
+use crate::services::s3_client::create_s3_client;
+use crate::services::transport::{self, BoxedIo};
+
+/// 单个网络检查的超时时间
+const STEP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// S3 拒绝与服务器时间相差超过 15 分钟的请求
+const MAX_CLOCK_SKEW_SECONDS: i64 = 15 * 60;
+
+/// 超过这个偏差时提示同步时间
+const WARN_CLOCK_SKEW_SECONDS: i64 = 60;
+
+const ALL_CHECKS: [DiagnosticCheck; 8] = [
+    DiagnosticCheck::Url,
+    DiagnosticCheck::Dns,
+    DiagnosticCheck::Connect,
+    DiagnosticCheck::Tls,
+    DiagnosticCheck::Clock,
+    DiagnosticCheck::Signature,
+    DiagnosticCheck::Region,
+    DiagnosticCheck::Addressing,
+];
+
+/// 单个检查的结论
+struct Outcome {
+    status: DiagnosticStatus,
+    detail: String,
+    hint: Option<String>,
+}
+
+impl Outcome {
+    fn passed(detail: impl Into<String>) -> Self {
+        Self {
+            status: DiagnosticStatus::Passed,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn warning(detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            status: DiagnosticStatus::Warning,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn failed(detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            status: DiagnosticStatus::Failed,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn skipped(detail: impl Into<String>) -> Self {
+        Self {
+            status: DiagnosticStatus::Skipped,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+}
+
+/// 收集各检查项的结果
+#[derive(Default)]
+struct Diagnosis {
+    steps: Vec<DiagnosticStep>,
+}
+
+impl Diagnosis {
+    /// 记录检查结果，返回该检查是否失败
+    fn record(&mut self, check: DiagnosticCheck, started: Instant, outcome: Outcome) -> bool {
+        let failed = outcome.status == DiagnosticStatus::Failed;
+        self.steps.push(DiagnosticStep {
+            check,
+            status: outcome.status,
+            detail: outcome.detail,
+            hint: outcome.hint,
+            duration_ms: started.elapsed().as_millis() as u64,
+        });
+        failed
+    }
+
+    /// 未执行的检查标记为跳过
+    fn finish(mut self) -> DiagnosticReport {
+        for check in ALL_CHECKS {
+            if !self.steps.iter().any(|step| step.check == check) {
+                self.record(
+                    check,
+                    Instant::now(),
+                    Outcome::skipped("Skipped because an earlier check failed"),
+                );
+            }
+        }
+
+        DiagnosticReport {
+            passed: self
+                .steps
+                .iter()
+                .all(|step| step.status != DiagnosticStatus::Failed),
+            steps: self.steps,
+        }
+    }
+}
+
+/// 逐层检查连接：地址、DNS、TCP、TLS、时钟、签名、区域和寻址方式
+///
+/// 网络层检查失败时不再执行后续检查。
+pub async fn diagnose(config: &S3Config) -> DiagnosticReport {
+    let mut diagnosis = Diagnosis::default();
+
+    let started = Instant::now();
+    let url = match check_url(&config.endpoint) {
+        Ok((url, outcome)) => {
+            diagnosis.record(DiagnosticCheck::Url, started, outcome);
+            url
+        }
+        Err(outcome) => {
+            diagnosis.record(DiagnosticCheck::Url, started, outcome);
+            return diagnosis.finish();
+        }
+    };
+
+    // 使用代理时 DNS 和 TCP 检查针对代理服务器，再通过代理建立到服务器的隧道
+    let proxy = url
+        .host_str()
+        .and_then(|host| transport::proxy_for(&config.network, host));
+    let proxy_url = match proxy.map(transport::proxy_url).transpose() {
+        Ok(proxy_url) => proxy_url,
+        Err(e) => {
+            let outcome =
+                Outcome::failed(e.to_string(), "Check the proxy settings of this profile");
+            diagnosis.record(DiagnosticCheck::Dns, Instant::now(), outcome);
+            return diagnosis.finish();
+        }
+    };
+
+    let started = Instant::now();
+    let addrs = match check_dns(proxy_url.as_ref().unwrap_or(&url)).await {
+        Ok((addrs, outcome)) => {
+            diagnosis.record(DiagnosticCheck::Dns, started, outcome);
+            addrs
+        }
+        Err(outcome) => {
+            diagnosis.record(DiagnosticCheck::Dns, started, outcome);
+            return diagnosis.finish();
+        }
+    };
+
+    let tls_config = transport::tls_config(&config.network);
+    let started = Instant::now();
+    let stream = match check_connect(&addrs).await {
+        Ok((stream, outcome)) => match proxy {
+            Some(proxy) => match check_tunnel(stream, proxy, &config.network, &url).await {
+                Ok(io) => {
+                    let detail = format!("{} through proxy {}", outcome.detail, proxy.url);
+                    diagnosis.record(DiagnosticCheck::Connect, started, Outcome::passed(detail));
+                    io
+                }
+                Err(outcome) => {
+                    diagnosis.record(DiagnosticCheck::Connect, started, outcome);
+                    return diagnosis.finish();
+                }
+            },
+            None => {
+                diagnosis.record(DiagnosticCheck::Connect, started, outcome);
+                Box::new(stream)
+            }
+        },
+        Err(outcome) => {
+            diagnosis.record(DiagnosticCheck::Connect, started, outcome);
+            return diagnosis.finish();
+        }
+    };
+
+    let started = Instant::now();
+    let outcome = match tls_config {
+        Ok(tls_config) if url.scheme() == "https" => check_tls(stream, &url, &tls_config).await,
+        Err(e) if url.scheme() == "https" => Outcome::failed(
+            format!("TLS setup failed: {}", e),
+            "Check the CA certificate and pinned certificate settings of this profile",
+        ),
+        _ => Outcome::skipped("Plain HTTP endpoint, no TLS"),
+    };
+    if diagnosis.record(DiagnosticCheck::Tls, started, outcome) {
+        return diagnosis.finish();
+    }
+
+    let started = Instant::now();
+    let outcome = check_clock(config, &url).await;
+    diagnosis.record(DiagnosticCheck::Clock, started, outcome);
+
+    let started = Instant::now();
+    let (outcome, observed_region) = check_signature(config).await;
+    let authenticated = outcome.status != DiagnosticStatus::Failed;
+    diagnosis.record(DiagnosticCheck::Signature, started, outcome);
+
+    let started = Instant::now();
+    let outcome = check_region(&config.region, observed_region.as_deref(), authenticated);
+    diagnosis.record(DiagnosticCheck::Region, started, outcome);
+
+    let started = Instant::now();
+    let outcome = match &config.bucket {
+        Some(_) if !authenticated => Outcome::skipped("Skipped because authentication failed"),
+        Some(bucket) => check_addressing(config, bucket).await,
+        None => Outcome::skipped("No bucket configured"),
+    };
+    diagnosis.record(DiagnosticCheck::Addressing, started, outcome);
+
+    diagnosis.finish()
+}
+
+fn check_url(endpoint: &str) -> Result<(Url, Outcome), Outcome> {
+    const EXAMPLE_HINT: &str =
+        "Enter a full URL including the scheme, for example https://s3.us-east-1.amazonaws.com";
+
+    let url = Url::parse(endpoint.trim())
+        .map_err(|e| Outcome::failed(format!("Invalid endpoint URL: {}", e), EXAMPLE_HINT))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(Outcome::failed(
+            format!("Unsupported scheme: {}", url.scheme()),
+            EXAMPLE_HINT,
+        ));
+    }
+    if url.host().is_none() {
+        return Err(Outcome::failed("Endpoint URL has no host", EXAMPLE_HINT));
+    }
+
+    let outcome = if url.path().trim_matches('/').is_empty() {
+        Outcome::passed(format!(
+            "{}://{}",
+            url.scheme(),
+            url.host_str().unwrap_or_default()
+        ))
+    } else {
+        Outcome::warning(
+            format!("Endpoint contains the path {}", url.path()),
+            "Remove the path from the endpoint; buckets and prefixes are configured separately",
+        )
+    };
+    Ok((url, outcome))
+}
+
+async fn check_dns(url: &Url) -> Result<(Vec<SocketAddr>, Outcome), Outcome> {
+    let port = url.port_or_known_default().unwrap_or(443);
+    let domain = match url.host() {
+        Some(Host::Domain(domain)) => domain.to_string(),
+        Some(Host::Ipv4(ip)) => {
+            let addr = SocketAddr::new(ip.into(), port);
+            return Ok((vec![addr], Outcome::passed("IP address, no lookup needed")));
+        }
+        Some(Host::Ipv6(ip)) => {
+            let addr = SocketAddr::new(ip.into(), port);
+            return Ok((vec![addr], Outcome::passed("IP address, no lookup needed")));
+        }
+        None => {
+            return Err(Outcome::failed(
+                "Endpoint URL has no host",
+                "Check the endpoint",
+            ))
+        }
+    };
+
+    let hint = "Check the host name and your network or DNS settings";
+    let addrs: Vec<SocketAddr> = match tokio::time::timeout(
+        STEP_TIMEOUT,
+        tokio::net::lookup_host((domain.as_str(), port)),
+    )
+    .await
+    {
+        Err(_) => return Err(Outcome::failed("DNS lookup timed out", hint)),
+        Ok(Err(e)) => {
+            return Err(Outcome::failed(
+                format!("Could not resolve {}: {}", domain, e),
+                hint,
+            ))
+        }
+        Ok(Ok(addrs)) => addrs.collect(),
+    };
+    if addrs.is_empty() {
+        return Err(Outcome::failed(
+            format!("{} has no addresses", domain),
+            hint,
+        ));
+    }
+
+    let resolved: Vec<String> = addrs.iter().map(|addr| addr.ip().to_string()).collect();
+    let outcome = Outcome::passed(format!("Resolved {} to {}", domain, resolved.join(", ")));
+    Ok((addrs, outcome))
+}
+
+async fn check_connect(addrs: &[SocketAddr]) -> Result<(TcpStream, Outcome), Outcome> {
+    let mut last_error = None;
+
+    for addr in addrs {
+        match tokio::time::timeout(STEP_TIMEOUT, TcpStream::connect(addr)).await {
+            Ok(Ok(stream)) => {
+                return Ok((stream, Outcome::passed(format!("Connected to {}", addr))));
+            }
+            Ok(Err(e)) => last_error = Some((*addr, e.kind(), e.to_string())),
+            Err(_) => {
+                last_error = Some((
+                    *addr,
+                    std::io::ErrorKind::TimedOut,
+                    "connection timed out".to_string(),
+                ))
+            }
+        }
+    }
+
+    let Some((addr, kind, message)) = last_error else {
+        return Err(Outcome::failed(
+            "No address to connect to",
+            "Check the endpoint",
+        ));
+    };
+    let hint = match kind {
+        std::io::ErrorKind::ConnectionRefused => format!(
+            "Nothing is listening on port {}; check the port in the endpoint",
+            addr.port()
+        ),
+        _ => "Check firewalls, VPN or proxy settings between this computer and the server"
+            .to_string(),
+    };
+    Err(Outcome::failed(
+        format!("Could not connect to {}: {}", addr, message),
+        hint,
+    ))
+}
+
+/// 通过代理建立到服务器的隧道
+async fn check_tunnel(
+    stream: TcpStream,
+    proxy: &ProxySettings,
+    network: &NetworkSettings,
+    url: &Url,
+) -> Result<BoxedIo, Outcome> {
+    let hint = "Check the proxy address, credentials and no-proxy list of this profile";
+    let proxy_tls = transport::proxy_tls_config(network)
+        .map_err(|e| Outcome::failed(format!("TLS setup failed: {}", e), hint))?;
+    let host = url.host_str().unwrap_or_default();
+    let port = url.port_or_known_default().unwrap_or(443);
+    let tunnel = transport::tunnel(
+        stream,
+        proxy,
+        &proxy_tls,
+        host,
+        port,
+        url.scheme() == "https",
+    );
+    match tokio::time::timeout(STEP_TIMEOUT, tunnel).await {
+        Err(_) => Err(Outcome::failed("Proxy tunnel timed out", hint)),
+        Ok(Err(e)) => Err(Outcome::failed(
+            format!("Proxy could not connect to {}:{}: {}", host, port, e),
+            hint,
+        )),
+        Ok(Ok((io, _))) => Ok(io),
+    }
+}
+
+async fn check_tls(stream: BoxedIo, url: &Url, tls_config: &Arc<rustls::ClientConfig>) -> Outcome {
+    let host = match url.host() {
+        Some(Host::Domain(domain)) => domain.to_string(),
+        Some(Host::Ipv4(ip)) => ip.to_string(),
+        Some(Host::Ipv6(ip)) => ip.to_string(),
+        None => return Outcome::skipped("Endpoint URL has no host"),
+    };
+    let server_name = match ServerName::try_from(host.clone()) {
+        Ok(name) => name,
+        Err(e) => {
+            return Outcome::failed(
+                format!("Invalid TLS server name: {}", e),
+                "Check the endpoint",
+            )
+        }
+    };
+
+    // 与 SDK 使用相同的证书设置
+    let connector = tokio_rustls::TlsConnector::from(tls_config.clone());
+
+    match tokio::time::timeout(STEP_TIMEOUT, connector.connect(server_name, stream)).await {
+        Err(_) => Outcome::failed(
+            "TLS handshake timed out",
+            "The server may not speak TLS on this port; try http:// instead",
+        ),
+        Ok(Ok(tls)) => {
+            let version = tls
+                .get_ref()
+                .1
+                .protocol_version()
+                .map(|version| format!("{:?}", version))
+                .unwrap_or_else(|| "unknown version".to_string());
+            Outcome::passed(format!("TLS handshake succeeded ({})", version))
+        }
+        Ok(Err(e)) => {
+            let tls_error = e
+                .get_ref()
+                .and_then(|inner| inner.downcast_ref::<rustls::Error>());
+            let (detail, hint) = describe_tls_error(tls_error, &host, &e.to_string());
+            Outcome::failed(detail, hint)
+        }
+    }
+}
+
+fn describe_tls_error(
+    error: Option<&rustls::Error>,
+    host: &str,
+    message: &str,
+) -> (String, String) {
+    use rustls::CertificateError;
+
+    match error {
+        Some(rustls::Error::InvalidCertificate(cert_error)) => match cert_error {
+            CertificateError::Expired | CertificateError::ExpiredContext { .. } => (
+                "The server certificate has expired".to_string(),
+                "Renew the certificate on the server, or check that the system clock is correct"
+                    .to_string(),
+            ),
+            CertificateError::NotValidYet | CertificateError::NotValidYetContext { .. } => (
+                "The server certificate is not valid yet".to_string(),
+                "Check that the system clock is correct".to_string(),
+            ),
+            CertificateError::NotValidForName | CertificateError::NotValidForNameContext { .. } => (
+                format!("The server certificate is not valid for {}", host),
+                "Check the endpoint host name; use path-style addressing if the certificate does not cover bucket sub-domains"
+                    .to_string(),
+            ),
+            CertificateError::UnknownIssuer => (
+                "The server certificate is self-signed or issued by an untrusted authority"
+                    .to_string(),
+                "Add the CA certificate to the system trust store or to the network settings of this profile"
+                    .to_string(),
+            ),
+            CertificateError::ApplicationVerificationFailure => (
+                "The server certificate does not match the pinned fingerprint".to_string(),
+                "Check the pinned certificate fingerprint of this profile".to_string(),
+            ),
+            other => (
+                format!("Invalid server certificate: {:?}", other),
+                "Check the certificate installed on the server".to_string(),
+            ),
+        },
+        Some(rustls::Error::InvalidMessage(_)) | Some(rustls::Error::InappropriateMessage { .. }) => (
+            format!("TLS handshake failed: {}", message),
+            "The server may not speak TLS on this port; try http:// instead".to_string(),
+        ),
+        _ => (
+            format!("TLS handshake failed: {}", message),
+            "Check the endpoint and any proxy that intercepts HTTPS traffic".to_string(),
+        ),
+    }
+}
+
+async fn check_clock(config: &S3Config, url: &Url) -> Outcome {
+    let hint = "Clock skew cannot be checked; signed requests may still work";
+    let response = match transport::get(&config.network, url.as_str(), STEP_TIMEOUT).await {
+        Ok(response) => response,
+        Err(e) => return Outcome::warning(format!("Unsigned request failed: {}", e), hint),
+    };
+    let Some(date) = response
+        .headers()
+        .get(http::header::DATE)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return Outcome::warning("The server did not return a Date header", hint);
+    };
+    let server_time = match DateTime::from_str(date, Format::HttpDate) {
+        Ok(time) => time,
+        Err(e) => return Outcome::warning(format!("Invalid Date header {}: {}", date, e), hint),
+    };
+
+    let skew = server_time.secs() - DateTime::from(SystemTime::now()).secs();
+    classify_clock_skew(skew)
+}
+
+/// `skew` 为服务器时间减去本地时间（秒）
+fn classify_clock_skew(skew: i64) -> Outcome {
+    let direction = if skew > 0 { "behind" } else { "ahead of" };
+    let detail = format!(
+        "Local clock is {} seconds {} the server",
+        skew.abs(),
+        direction
+    );
+    let hint = "Synchronise the system clock (enable automatic time)";
+
+    if skew.abs() > MAX_CLOCK_SKEW_SECONDS {
+        Outcome::failed(detail, hint)
+    } else if skew.abs() > WARN_CLOCK_SKEW_SECONDS {
+        Outcome::warning(detail, hint)
+    } else {
+        Outcome::passed(detail)
+    }
+}
+
+/// 发送签名请求，返回结论和服务器报告的存储桶区域
+async fn check_signature(config: &S3Config) -> (Outcome, Option<String>) {
+    let client = match create_s3_client(config).await {
+        Ok(client) => client,
+        Err(e) => {
+            return (
+                Outcome::failed(
+                    format!("Failed to create S3 client: {}", e),
+                    "Check the credential settings of this profile",
+                ),
+                None,
+            )
+        }
+    };
+
+    match &config.bucket {
+        Some(bucket) => match client.head_bucket().bucket(bucket).send().await {
+            Ok(output) => (
+                Outcome::passed(format!(
+                    "Signed HEAD request to bucket {} succeeded",
+                    bucket
+                )),
+                output.bucket_region().map(str::to_string),
+            ),
+            Err(e) => (classify_error(&e), error_region(&e)),
+        },
+        None => match client.list_buckets().send().await {
+            Ok(_) => (
+                Outcome::passed("Signed ListBuckets request succeeded"),
+                None,
+            ),
+            Err(e) => (classify_error(&e), error_region(&e)),
+        },
+    }
+}
+
+fn check_region(configured: &str, observed: Option<&str>, authenticated: bool) -> Outcome {
+    let auto = configured.is_empty() || configured == "auto";
+
+    match observed {
+        Some(observed) if auto => Outcome::passed(format!(
+            "Bucket region is {}; the profile lets the service choose",
+            observed
+        )),
+        Some(observed) if observed == configured => {
+            Outcome::passed(format!("Bucket region matches {}", configured))
+        }
+        Some(observed) => Outcome::failed(
+            format!(
+                "The bucket is in {} but the profile uses {}",
+                observed, configured
+            ),
+            format!("Set the region to {}", observed),
+        ),
+        None if authenticated => Outcome::passed(format!(
+            "Region {} was accepted",
+            if auto { "auto" } else { configured }
+        )),
+        None => Outcome::skipped("The server did not report a region"),
+    }
+}
+
+/// 分别用路径方式和虚拟主机方式列举存储桶，判断服务支持哪种方式
+async fn check_addressing(config: &S3Config, bucket: &str) -> Outcome {
+    let current = config.addressing_style;
+    let other = match current {
+        AddressingStyle::Path => AddressingStyle::Virtual,
+        AddressingStyle::Virtual => AddressingStyle::Path,
+    };
+
+    let mut other_config = config.clone();
+    other_config.addressing_style = other;
+    let current_works = bucket_reachable(config, bucket).await;
+    let other_works = bucket_reachable(&other_config, bucket).await;
+
+    match (current_works, other_works) {
+        (true, true) => Outcome::passed("Both path-style and virtual-hosted requests work"),
+        (true, false) => Outcome::passed(format!(
+            "{} requests work; {} requests do not",
+            style_name(current),
+            style_name(other)
+        )),
+        (false, true) => Outcome::failed(
+            format!(
+                "{} requests fail but {} requests work",
+                style_name(current),
+                style_name(other)
+            ),
+            format!("Switch the addressing style to {}", style_name(other)),
+        ),
+        (false, false) => Outcome::failed(
+            format!(
+                "Bucket {} could not be listed with either addressing style",
+                bucket
+            ),
+            "Check the bucket name and that the key may list it",
+        ),
+    }
+}
+
+/// 请求到达了存储桶：成功，或者服务器以权限不足拒绝
+async fn bucket_reachable(config: &S3Config, bucket: &str) -> bool {
+    let Ok(client) = create_s3_client(config).await else {
+        return false;
+    };
+    match client
+        .list_objects_v2()
+        .bucket(bucket)
+        .max_keys(1)
+        .send()
+        .await
+    {
+        Ok(_) => true,
+        Err(e) => e.code() == Some("AccessDenied"),
+    }
+}
+
+fn style_name(style: AddressingStyle) -> &'static str {
+    match style {
+        AddressingStyle::Path => "Path-style",
+        AddressingStyle::Virtual => "Virtual-hosted",
+    }
+}
+
+/// 根据 S3 错误码和 HTTP 状态判断失败原因
+fn classify_error<E>(error: &SdkError<E, HttpResponse>) -> Outcome
+where
+    E: ProvideErrorMetadata + std::error::Error + 'static,
+{
+    match error {
+        SdkError::DispatchFailure(_) | SdkError::TimeoutError(_) => {
+            return Outcome::failed(
+                format!("Request failed: {}", DisplayErrorContext(error)),
+                "Check the network connection and the credential settings",
+            );
+        }
+        SdkError::ConstructionFailure(_) => {
+            return Outcome::failed(
+                format!(
+                    "Could not build the request: {}",
+                    DisplayErrorContext(error)
+                ),
+                "Check the credential settings of this profile",
+            );
+        }
+        _ => {}
+    }
+
+    let status = error
+        .raw_response()
+        .map(|response| response.status().as_u16())
+        .unwrap_or_default();
+    let message = error.message().unwrap_or_default();
+
+    match (error.code(), status) {
+        (Some("SignatureDoesNotMatch"), _) => Outcome::failed(
+            "The request signature does not match",
+            "The secret access key is wrong, or a proxy is modifying requests",
+        ),
+        (Some("InvalidAccessKeyId"), _) => Outcome::failed(
+            "The access key ID is not recognised",
+            "Check the access key ID and that it belongs to this service",
+        ),
+        (Some("RequestTimeTooSkewed"), _) => Outcome::failed(
+            "The server rejected the request time",
+            "Synchronise the system clock (enable automatic time)",
+        ),
+        (Some("AuthorizationHeaderMalformed"), _) | (Some("PermanentRedirect"), _) | (_, 301) => {
+            Outcome::warning(
+                format!("The server expects a different region: {}", message),
+                "Change the region as suggested by the region check",
+            )
+        }
+        (Some("AccessDenied"), _) | (_, 403) => Outcome::warning(
+            "The request was authenticated but access was denied",
+            "The key may lack permission for this request; other operations may still work",
+        ),
+        (Some("NoSuchBucket"), _) | (_, 404) => Outcome::failed(
+            "The bucket does not exist",
+            "Check the bucket name of this profile",
+        ),
+        (Some(code), _) => Outcome::failed(
+            format!("{}: {}", code, message),
+            "Check the endpoint URL and credentials",
+        ),
+        (None, _) => Outcome::failed(
+            format!("HTTP {}: {}", status, DisplayErrorContext(error)),
+            "Check the endpoint URL and credentials",
+        ),
+    }
+}
+
+/// 从 `x-amz-bucket-region` 头或错误消息中取出存储桶实际所在的区域
+fn error_region<E: ProvideErrorMetadata>(error: &SdkError<E, HttpResponse>) -> Option<String> {
+    error
+        .raw_response()
+        .and_then(|response| response.headers().get("x-amz-bucket-region"))
+        .map(str::to_string)
+        .or_else(|| error.message().and_then(expected_region))
+}
+
+/// 解析 `the region 'us-east-1' is wrong; expecting 'eu-west-1'` 中期望的区域
+fn expected_region(message: &str) -> Option<String> {
+    let (_, rest) = message.split_once("expecting '")?;
+    let (region, _) = rest.split_once('\'')?;
+    Some(region.to_string())
+}
+
+/// 将 S3 请求错误转换为带修复建议的说明
+pub fn describe_error<E>(error: &SdkError<E, HttpResponse>) -> String
+where
+    E: ProvideErrorMetadata + std::error::Error + 'static,
+{
+    let outcome = classify_error(error);
+    match outcome.hint {
+        Some(hint) => format!("{}. {}", outcome.detail, hint),
+        None => outcome.detail,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_test_config;
+
+    #[tokio::test]
+    async fn test_invalid_url_skips_remaining_checks() {
+        let mut config = create_test_config();
+        config.endpoint = "localhost:9000".to_string();
+
+        let report = diagnose(&config).await;
+        assert!(!report.passed);
+        assert_eq!(report.steps.len(), ALL_CHECKS.len());
+        assert_eq!(report.steps[0].status, DiagnosticStatus::Failed);
+        assert!(report.steps[1..]
+            .iter()
+            .all(|step| step.status == DiagnosticStatus::Skipped));
+    }
+
+    #[tokio::test]
+    async fn test_connection_refused() {
+        let mut config = create_test_config();
+        config.endpoint = "http://127.0.0.1:1".to_string();
+        config.region = "us-east-1".to_string();
+
+        let report = diagnose(&config).await;
+        let connect = report
+            .steps
+            .iter()
+            .find(|step| step.check == DiagnosticCheck::Connect)
+            .unwrap();
+        assert_eq!(connect.status, DiagnosticStatus::Failed);
+        assert!(connect.hint.as_deref().unwrap().contains("port 1"));
+    }
+
+    #[test]
+    fn test_classify_clock_skew() {
+        assert_eq!(classify_clock_skew(5).status, DiagnosticStatus::Passed);
+        assert_eq!(classify_clock_skew(-120).status, DiagnosticStatus::Warning);
+        assert_eq!(classify_clock_skew(3600).status, DiagnosticStatus::Failed);
+    }
+
+    #[test]
+    fn test_check_region() {
+        assert_eq!(
+            check_region("us-east-1", Some("eu-west-1"), true).status,
+            DiagnosticStatus::Failed
+        );
+        assert_eq!(
+            check_region("auto", Some("eu-west-1"), true).status,
+            DiagnosticStatus::Passed
+        );
+        assert_eq!(
+            check_region("us-east-1", None, false).status,
+            DiagnosticStatus::Skipped
+        );
+    }
+
+    #[test]
+    fn test_expected_region() {
+        let message = "The authorization header is malformed; the region 'us-east-1' is wrong; expecting 'eu-west-1'";
+        assert_eq!(expected_region(message).as_deref(), Some("eu-west-1"));
+        assert_eq!(expected_region("Access Denied"), None);
+    }
+}
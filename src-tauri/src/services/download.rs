@@ -0,0 +1,427 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use aws_sdk_s3::Client;
+use serde::{Deserialize, Serialize};
+use tokio::fs::OpenOptions;
+use tokio::io::AsyncWriteExt;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+use crate::error::{AppError, ErrorCode};
+use crate::services::progress::ProgressReporter;
+use crate::utils::storage::{read_json, write_json_atomic};
+
+/// 下载中断后自动续传的最大次数
+pub const MAX_RESUME_ATTEMPTS: u32 = 5;
+
+/// 未完成下载使用的临时文件后缀
+pub const PART_SUFFIX: &str = ".part";
+
+/// 记录临时文件来源的文件后缀
+pub const SOURCE_SUFFIX: &str = ".part.json";
+
+/// 远端对象的大小和 ETag
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteObject {
+    pub size: u64,
+    pub etag: Option<String>,
+}
+
+/// 临时文件来自哪个对象，保存在 `<目标文件>.part.json` 中
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartSource {
+    pub bucket: String,
+    pub key: String,
+    pub size: u64,
+    pub etag: Option<String>,
+}
+
+impl PartSource {
+    /// 已有的临时文件能否续传：必须来自同一个对象的同一个版本
+    ///
+    /// 远端对象没有 ETag 时无法确认版本，总是从头下载。
+    pub fn can_resume(&self, recorded: Option<&PartSource>) -> bool {
+        self.etag.is_some() && recorded == Some(self)
+    }
+}
+
+/// 返回下载目标对应的临时文件路径
+pub fn part_path(dest: &Path) -> PathBuf {
+    with_suffix(dest, PART_SUFFIX)
+}
+
+/// 返回记录临时文件来源的文件路径
+pub fn source_path(dest: &Path) -> PathBuf {
+    with_suffix(dest, SOURCE_SUFFIX)
+}
+
+fn with_suffix(dest: &Path, suffix: &str) -> PathBuf {
+    let mut name = dest.as_os_str().to_os_string();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// 删除未完成下载的临时文件和来源记录
+pub async fn discard_partial(dest: &Path) -> Result<(), AppError> {
+    remove_if_exists(&part_path(dest)).await?;
+    remove_if_exists(&source_path(dest)).await
+}
+
+/// 从指定偏移开始读取的 Range 头
+pub fn range_from(offset: u64) -> String {
+    format!("bytes={}-", offset)
+}
+
+/// 单次读取的结果
+enum Attempt {
+    /// 数据读取完毕
+    Finished,
+    /// 连接中断，可以从当前位置继续
+    Interrupted(String),
+    /// 远端对象已变化，需要从头下载
+    Changed,
+    /// 被取消令牌中断
+    Cancelled,
+}
+
+/// 可断点续传的对象下载器
+///
+/// 数据先写入 `<目标文件>.part`，通过 Range 请求从已有长度继续下载，
+/// 大小和 ETag 校验通过后才原子地重命名为目标文件。
+/// 通过取消令牌中断时保留临时文件，之后可以继续下载。
+pub struct ObjectDownloader {
+    client: Client,
+    bucket: String,
+    key: String,
+    cancel: CancellationToken,
+}
+
+impl ObjectDownloader {
+    pub fn new(client: Client, bucket: impl Into<String>, key: impl Into<String>) -> Self {
+        Self {
+            client,
+            bucket: bucket.into(),
+            key: key.into(),
+            cancel: CancellationToken::new(),
+        }
+    }
+
+    /// 令牌被取消时停止下载，保留已下载的临时文件
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel = token;
+        self
+    }
+
+    /// 获取远端对象的大小和 ETag
+    pub async fn head(&self) -> Result<RemoteObject, AppError> {
+        let output = self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(&self.key)
+            .send()
+            .await
+            .map_err(|e| AppError::from_sdk("Failed to get object metadata", &e))?;
+
+        Ok(RemoteObject {
+            size: output.content_length().unwrap_or_default().max(0) as u64,
+            etag: output.e_tag().map(|tag| tag.to_string()),
+        })
+    }
+
+    /// 下载对象到 `dest`，已存在的 `.part` 文件会被续传
+    pub async fn download(
+        &self,
+        remote: &RemoteObject,
+        dest: &Path,
+        progress: &ProgressReporter,
+    ) -> Result<(), AppError> {
+        let part = part_path(dest);
+        let source = source_path(dest);
+        self.claim_part(remote, &part, &source).await?;
+        let mut restarted = false;
+        let mut attempts = 0;
+
+        loop {
+            if self.cancel.is_cancelled() {
+                return Err(AppError::interrupted());
+            }
+
+            let offset = match tokio::fs::metadata(&part).await {
+                Ok(metadata) => metadata.len(),
+                Err(_) => 0,
+            };
+
+            // 临时文件比远端对象还大，说明不是同一个对象，重新下载
+            if offset > remote.size {
+                remove_part(&part).await?;
+                continue;
+            }
+
+            progress.set(offset);
+
+            if offset == remote.size {
+                break;
+            }
+
+            if attempts >= MAX_RESUME_ATTEMPTS {
+                return Err(AppError::new(
+                    ErrorCode::Network,
+                    format!(
+                        "Download incomplete after {} attempts: {} of {} bytes",
+                        attempts, offset, remote.size
+                    ),
+                ));
+            }
+
+            match self.fetch_range(remote, &part, offset, progress).await? {
+                // 响应正常结束但数据不完整时，下一轮会继续续传
+                Attempt::Finished => attempts += 1,
+                Attempt::Interrupted(e) => {
+                    attempts += 1;
+                    if attempts >= MAX_RESUME_ATTEMPTS {
+                        return Err(AppError::new(
+                            ErrorCode::Network,
+                            format!("Download interrupted after {} attempts: {}", attempts, e),
+                        ));
+                    }
+                    warn!(
+                        operation = "download",
+                        bucket = %self.bucket,
+                        key = %self.key,
+                        offset,
+                        "Download interrupted, resuming: {}",
+                        e
+                    );
+                    tokio::time::sleep(Duration::from_secs(attempts as u64)).await;
+                }
+                Attempt::Changed => {
+                    if restarted {
+                        return Err(AppError::conflict("Object changed during download"));
+                    }
+                    restarted = true;
+                    remove_part(&part).await?;
+                }
+                Attempt::Cancelled => return Err(AppError::interrupted()),
+            }
+        }
+
+        // 空对象不会发起请求，这里保证临时文件存在
+        if remote.size == 0 {
+            tokio::fs::File::create(&part)
+                .await
+                .map_err(|e| AppError::io("Failed to write file", &e))?;
+        }
+
+        let written = tokio::fs::metadata(&part)
+            .await
+            .map_err(|e| AppError::io("Failed to read file metadata", &e))?
+            .len();
+        if written != remote.size {
+            return Err(AppError::new(
+                ErrorCode::Io,
+                format!(
+                    "Downloaded size mismatch: expected {} bytes, got {}",
+                    remote.size, written
+                ),
+            ));
+        }
+
+        tokio::fs::rename(&part, dest)
+            .await
+            .map_err(|e| AppError::io("Failed to move downloaded file into place", &e))?;
+        remove_if_exists(&source).await?;
+
+        Ok(())
+    }
+
+    /// 检查已有的临时文件是否来自同一个对象版本，不是时删除，然后记录本次下载的来源
+    async fn claim_part(
+        &self,
+        remote: &RemoteObject,
+        part: &Path,
+        source: &Path,
+    ) -> Result<(), AppError> {
+        let expected = PartSource {
+            bucket: self.bucket.clone(),
+            key: self.key.clone(),
+            size: remote.size,
+            etag: remote.etag.clone(),
+        };
+        // 来源记录损坏时按没有记录处理
+        let recorded = read_json::<PartSource>(source).unwrap_or_default();
+
+        if tokio::fs::try_exists(part).await.unwrap_or(false)
+            && !expected.can_resume(recorded.as_ref())
+        {
+            warn!(
+                operation = "download",
+                bucket = %self.bucket,
+                key = %self.key,
+                "Discarding partial download from a different object or version"
+            );
+            remove_part(part).await?;
+        }
+
+        write_json_atomic(source, &expected).map_err(|e| {
+            AppError::new(
+                ErrorCode::Io,
+                format!("Failed to record partial download source: {}", e),
+            )
+        })
+    }
+
+    /// 从 `offset` 开始读取对象剩余内容并追加到临时文件
+    async fn fetch_range(
+        &self,
+        remote: &RemoteObject,
+        part: &Path,
+        offset: u64,
+        progress: &ProgressReporter,
+    ) -> Result<Attempt, AppError> {
+        let mut request = self.client.get_object().bucket(&self.bucket).key(&self.key);
+
+        if offset > 0 {
+            request = request.range(range_from(offset));
+        }
+        // If-Match 保证续传的数据与已下载部分来自同一个对象版本
+        if let Some(etag) = &remote.etag {
+            request = request.if_match(etag);
+        }
+
+        let sent = tokio::select! {
+            sent = request.send() => sent,
+            _ = self.cancel.cancelled() => return Ok(Attempt::Cancelled),
+        };
+
+        let output = match sent {
+            Ok(output) => output,
+            Err(e) => {
+                let precondition_failed = e
+                    .raw_response()
+                    .map(|response| response.status().as_u16() == 412)
+                    .unwrap_or(false);
+                if precondition_failed {
+                    return Ok(Attempt::Changed);
+                }
+                if e.raw_response().is_some() {
+                    return Err(AppError::from_sdk("Failed to download file", &e));
+                }
+                return Ok(Attempt::Interrupted(e.to_string()));
+            }
+        };
+
+        if remote.etag.is_some() && output.e_tag() != remote.etag.as_deref() {
+            return Ok(Attempt::Changed);
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(part)
+            .await
+            .map_err(|e| AppError::io("Failed to write file", &e))?;
+
+        let mut body = output.body;
+        let result = loop {
+            let next = tokio::select! {
+                next = body.try_next() => next,
+                _ = self.cancel.cancelled() => break Attempt::Cancelled,
+            };
+
+            match next {
+                Ok(Some(chunk)) => {
+                    file.write_all(&chunk)
+                        .await
+                        .map_err(|e| AppError::io("Failed to write file", &e))?;
+                    progress.advance(chunk.len() as u64);
+                }
+                Ok(None) => break Attempt::Finished,
+                Err(e) => break Attempt::Interrupted(e.to_string()),
+            }
+        };
+
+        file.flush()
+            .await
+            .map_err(|e| AppError::io("Failed to write file", &e))?;
+
+        Ok(result)
+    }
+}
+
+async fn remove_part(part: &Path) -> Result<(), AppError> {
+    tokio::fs::remove_file(part)
+        .await
+        .map_err(|e| AppError::io("Failed to remove partial download", &e))
+}
+
+async fn remove_if_exists(path: &Path) -> Result<(), AppError> {
+    match tokio::fs::remove_file(path).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(AppError::io("Failed to remove partial download", &e))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_path() {
+        assert_eq!(
+            part_path(Path::new("/tmp/video.mp4")),
+            PathBuf::from("/tmp/video.mp4.part")
+        );
+        assert_eq!(
+            part_path(Path::new("archive")),
+            PathBuf::from("archive.part")
+        );
+    }
+
+    #[test]
+    fn test_source_path() {
+        assert_eq!(
+            source_path(Path::new("/tmp/video.mp4")),
+            PathBuf::from("/tmp/video.mp4.part.json")
+        );
+    }
+
+    #[test]
+    fn test_can_resume() {
+        let expected = PartSource {
+            bucket: "media".to_string(),
+            key: "video.mp4".to_string(),
+            size: 1024,
+            etag: Some("\"abc\"".to_string()),
+        };
+        assert!(expected.can_resume(Some(&expected.clone())));
+        assert!(!expected.can_resume(None));
+
+        let older = PartSource {
+            etag: Some("\"old\"".to_string()),
+            ..expected.clone()
+        };
+        assert!(!expected.can_resume(Some(&older)));
+
+        let other = PartSource {
+            key: "other.mp4".to_string(),
+            ..expected.clone()
+        };
+        assert!(!expected.can_resume(Some(&other)));
+
+        let no_etag = PartSource {
+            etag: None,
+            ..expected
+        };
+        assert!(!no_etag.can_resume(Some(&no_etag.clone())));
+    }
+
+    #[test]
+    fn test_range_from() {
+        assert_eq!(range_from(0), "bytes=0-");
+        assert_eq!(range_from(1048576), "bytes=1048576-");
+    }
+}
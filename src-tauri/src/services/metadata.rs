@@ -0,0 +1,280 @@
+use std::collections::{BTreeMap, HashMap};
+
+use aws_sdk_s3::operation::head_object::HeadObjectOutput;
+use aws_sdk_s3::types::{MetadataDirective, ServerSideEncryption, StorageClass};
+use aws_sdk_s3::Client;
+use aws_smithy_types::date_time::{DateTime, Format};
+
+use crate::error::AppError;
+use crate::models::s3::{MetadataChanges, ObjectMetadata, UpdatePrefixMetadataResult};
+use crate::services::batch::for_each_object;
+use crate::services::copy::{copy_multipart, ObjectLocation, MAX_COPY_OBJECT_SIZE};
+use crate::services::delete::DeleteTarget;
+use crate::services::multipart::DEFAULT_CONCURRENCY;
+use crate::services::progress::ProgressReporter;
+
+/// 读取对象的元数据；`version_id` 为空时读取当前版本
+pub async fn head_metadata(
+    client: &Client,
+    location: &ObjectLocation,
+    version_id: Option<&str>,
+) -> Result<ObjectMetadata, AppError> {
+    let output = client
+        .head_object()
+        .bucket(&location.bucket)
+        .key(&location.key)
+        .set_version_id(version_id.map(str::to_string))
+        .send()
+        .await
+        .map_err(|e| AppError::from_sdk("Failed to read object metadata", &e))?;
+
+    Ok(to_metadata(&location.key, &output))
+}
+
+fn to_metadata(key: &str, output: &HeadObjectOutput) -> ObjectMetadata {
+    ObjectMetadata {
+        key: key.to_string(),
+        size: output.content_length().unwrap_or_default().max(0) as u64,
+        last_modified: output.last_modified().map(|date| date.to_string()),
+        etag: output.e_tag().map(str::to_string),
+        version_id: output.version_id().map(str::to_string),
+        storage_class: output
+            .storage_class()
+            .map(|class| class.as_str().to_string()),
+        content_type: output.content_type().map(str::to_string),
+        cache_control: output.cache_control().map(str::to_string),
+        content_disposition: output.content_disposition().map(str::to_string),
+        content_encoding: output.content_encoding().map(str::to_string),
+        content_language: output.content_language().map(str::to_string),
+        expires: output.expires_string().map(str::to_string),
+        user_metadata: output
+            .metadata()
+            .map(|metadata| {
+                metadata
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        server_side_encryption: output
+            .server_side_encryption()
+            .map(|sse| sse.as_str().to_string()),
+        sse_kms_key_id: output.ssekms_key_id().map(str::to_string),
+        bucket_key_enabled: output.bucket_key_enabled(),
+    }
+}
+
+/// 把修改合并到现有元数据上：未指定的字段保持不变，空字符串删除该字段
+pub fn apply_changes(
+    metadata: &mut ObjectMetadata,
+    changes: &MetadataChanges,
+) -> Result<(), AppError> {
+    fn merge(field: &mut Option<String>, change: &Option<String>) {
+        if let Some(value) = change {
+            let value = value.trim();
+            *field = (!value.is_empty()).then(|| value.to_string());
+        }
+    }
+
+    merge(&mut metadata.content_type, &changes.content_type);
+    merge(&mut metadata.cache_control, &changes.cache_control);
+    merge(
+        &mut metadata.content_disposition,
+        &changes.content_disposition,
+    );
+    merge(&mut metadata.content_encoding, &changes.content_encoding);
+    merge(&mut metadata.content_language, &changes.content_language);
+
+    if let Some(user_metadata) = &changes.user_metadata {
+        let mut replaced = BTreeMap::new();
+        for (key, value) in user_metadata {
+            // 服务端按小写保存 x-amz-meta-* 的键，前缀由 SDK 添加
+            let key = key.trim().to_ascii_lowercase();
+            let key = key.strip_prefix("x-amz-meta-").unwrap_or(&key).to_string();
+            if key.is_empty() {
+                return Err(AppError::invalid_input("Metadata key cannot be empty"));
+            }
+            if !key
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.'))
+            {
+                return Err(AppError::invalid_input(format!(
+                    "Invalid metadata key: {}",
+                    key
+                )));
+            }
+            if !value.is_ascii() {
+                return Err(AppError::invalid_input(format!(
+                    "Metadata value for {} must be ASCII",
+                    key
+                )));
+            }
+            replaced.insert(key, value.clone());
+        }
+        metadata.user_metadata = replaced;
+    }
+    Ok(())
+}
+
+/// 把对象复制到自身并替换元数据
+///
+/// 存储类别和服务端加密设置沿用原对象；超过 5 GiB 的对象使用分片复制。
+pub async fn rewrite_metadata(
+    client: &Client,
+    bucket: &str,
+    metadata: &ObjectMetadata,
+) -> Result<(), AppError> {
+    let location = ObjectLocation::new(bucket, &metadata.key);
+    if metadata.size > MAX_COPY_OBJECT_SIZE {
+        return copy_multipart(client, &location, &location, metadata.size, metadata, None).await;
+    }
+
+    client
+        .copy_object()
+        .copy_source(location.copy_source())
+        .bucket(&location.bucket)
+        .key(&location.key)
+        .metadata_directive(MetadataDirective::Replace)
+        .set_content_type(metadata.content_type.clone())
+        .set_cache_control(metadata.cache_control.clone())
+        .set_content_disposition(metadata.content_disposition.clone())
+        .set_content_encoding(metadata.content_encoding.clone())
+        .set_content_language(metadata.content_language.clone())
+        .set_expires(expires(metadata))
+        .set_metadata(user_metadata(metadata))
+        .set_storage_class(storage_class(metadata))
+        .set_server_side_encryption(server_side_encryption(metadata))
+        .set_ssekms_key_id(metadata.sse_kms_key_id.clone())
+        .set_bucket_key_enabled(metadata.bucket_key_enabled)
+        .send()
+        .await
+        .map_err(|e| AppError::from_sdk("Failed to update object metadata", &e))?;
+    Ok(())
+}
+
+/// 修改前缀下所有对象的元数据，单个对象失败不影响其他对象
+pub async fn update_prefix_metadata(
+    client: &Client,
+    bucket: &str,
+    targets: &[DeleteTarget],
+    changes: &MetadataChanges,
+    progress: &ProgressReporter,
+) -> UpdatePrefixMetadataResult {
+    let keys: Vec<String> = targets.iter().map(|target| target.key.clone()).collect();
+    let (updated, failed) = for_each_object(&keys, DEFAULT_CONCURRENCY, Some(progress), |key| {
+        let client = client.clone();
+        let location = ObjectLocation::new(bucket, key);
+        let changes = changes.clone();
+        async move { update_object(&client, &location, &changes).await }
+    })
+    .await;
+
+    UpdatePrefixMetadataResult {
+        transfer_id: progress.transfer_id().to_string(),
+        object_count: targets.len(),
+        updated_count: updated.len(),
+        failed,
+    }
+}
+
+/// 读取对象当前的元数据，合并修改后写回
+pub async fn update_object(
+    client: &Client,
+    location: &ObjectLocation,
+    changes: &MetadataChanges,
+) -> Result<(), AppError> {
+    let mut metadata = head_metadata(client, location, None).await?;
+    apply_changes(&mut metadata, changes)?;
+    rewrite_metadata(client, &location.bucket, &metadata).await
+}
+
+/// `Expires` 头转换为日期，无法解析的值会被丢弃
+pub fn expires(metadata: &ObjectMetadata) -> Option<DateTime> {
+    metadata
+        .expires
+        .as_deref()
+        .and_then(|value| DateTime::from_str(value, Format::HttpDate).ok())
+}
+
+pub fn user_metadata(metadata: &ObjectMetadata) -> Option<HashMap<String, String>> {
+    (!metadata.user_metadata.is_empty()).then(|| {
+        metadata
+            .user_metadata
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    })
+}
+
+pub fn storage_class(metadata: &ObjectMetadata) -> Option<StorageClass> {
+    metadata.storage_class.as_deref().map(StorageClass::from)
+}
+
+pub fn server_side_encryption(metadata: &ObjectMetadata) -> Option<ServerSideEncryption> {
+    metadata
+        .server_side_encryption
+        .as_deref()
+        .map(ServerSideEncryption::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> ObjectMetadata {
+        ObjectMetadata {
+            key: "docs/report.pdf".to_string(),
+            size: 1024,
+            content_type: Some("application/octet-stream".to_string()),
+            cache_control: Some("no-cache".to_string()),
+            content_disposition: Some("attachment".to_string()),
+            user_metadata: BTreeMap::from([("owner".to_string(), "alice".to_string())]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_apply_changes() {
+        let mut metadata = sample();
+        let changes = MetadataChanges {
+            content_type: Some("application/pdf".to_string()),
+            cache_control: Some(String::new()),
+            user_metadata: Some(BTreeMap::from([(
+                "X-Amz-Meta-Project".to_string(),
+                "q3".to_string(),
+            )])),
+            ..Default::default()
+        };
+
+        apply_changes(&mut metadata, &changes).unwrap();
+        assert_eq!(metadata.content_type.as_deref(), Some("application/pdf"));
+        assert_eq!(metadata.cache_control, None);
+        assert_eq!(metadata.content_disposition.as_deref(), Some("attachment"));
+        assert_eq!(
+            metadata.user_metadata,
+            BTreeMap::from([("project".to_string(), "q3".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_apply_changes_rejects_invalid_keys() {
+        let mut metadata = sample();
+        let changes = MetadataChanges {
+            user_metadata: Some(BTreeMap::from([("bad key".to_string(), "1".to_string())])),
+            ..Default::default()
+        };
+
+        assert!(apply_changes(&mut metadata, &changes).is_err());
+        assert_eq!(metadata, sample());
+    }
+
+    #[test]
+    fn test_expires() {
+        let mut metadata = sample();
+        metadata.expires = Some("Wed, 21 Oct 2026 07:28:00 GMT".to_string());
+        assert_eq!(expires(&metadata).map(|date| date.secs()), Some(1792567680));
+
+        metadata.expires = Some("soon".to_string());
+        assert_eq!(expires(&metadata), None);
+    }
+}
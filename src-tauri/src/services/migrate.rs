@@ -0,0 +1,573 @@
+use std::sync::Arc;
+
+use aws_sdk_s3::operation::get_object::GetObjectOutput;
+use aws_sdk_s3::operation::head_object::HeadObjectOutput;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{
+    ChecksumAlgorithm, ChecksumMode, ChecksumType, CompletedMultipartUpload, CompletedPart,
+};
+use aws_sdk_s3::Client;
+use bytes::{Bytes, BytesMut};
+use tokio::task::{JoinError, JoinSet};
+use tracing::warn;
+
+use crate::error::{AppError, ErrorCode};
+use crate::models::s3::{CopyObjectError, MigrateObjectsResult};
+use crate::services::copy::{replace_prefix, ObjectLocation};
+use crate::services::multipart::{effective_part_size, MultipartOptions};
+use crate::services::progress::ProgressReporter;
+
+/// 传输时分片的最大大小，只有分片数超过上限时才使用更大的分片
+pub const MAX_MIGRATE_PART_SIZE: u64 = 64 * 1024 * 1024;
+/// 单个对象传输时在内存中缓冲的最大字节数（在途分片加正在读取的分片）
+pub const MAX_BUFFERED_BYTES: u64 = 256 * 1024 * 1024;
+
+/// 源前缀下待传输的对象
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceObject {
+    pub key: String,
+    pub size: u64,
+    pub etag: Option<String>,
+}
+
+/// 两个 ETag 是否相同，忽略引号
+pub fn etags_match(a: Option<&str>, b: Option<&str>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.trim_matches('"') == b.trim_matches('"'),
+        _ => false,
+    }
+}
+
+/// 对象的附加校验值（算法，值），服务端未返回时为空
+fn checksums(head: &HeadObjectOutput) -> Vec<(&'static str, &str)> {
+    [
+        ("CRC32", head.checksum_crc32()),
+        ("CRC32C", head.checksum_crc32_c()),
+        ("CRC64NVME", head.checksum_crc64_nvme()),
+        ("SHA1", head.checksum_sha1()),
+        ("SHA256", head.checksum_sha256()),
+    ]
+    .into_iter()
+    .filter_map(|(algorithm, value)| value.map(|value| (algorithm, value)))
+    .collect()
+}
+
+/// 目标对象使用的校验算法
+///
+/// 沿用源对象的整体（非分片组合）校验算法，下次传输时两边的校验值才能直接比较；
+/// 源对象没有整体校验值时使用 CRC32。
+fn checksum_algorithm(source: &GetObjectOutput) -> ChecksumAlgorithm {
+    let full_object = |value: Option<&str>| value.is_some_and(|value| !value.contains('-'));
+    if full_object(source.checksum_crc64_nvme()) {
+        ChecksumAlgorithm::Crc64Nvme
+    } else if full_object(source.checksum_crc32_c()) {
+        ChecksumAlgorithm::Crc32C
+    } else {
+        ChecksumAlgorithm::Crc32
+    }
+}
+
+/// 列出源前缀下的所有对象
+pub async fn list_source(
+    client: &Client,
+    bucket: &str,
+    prefix: &str,
+) -> Result<Vec<SourceObject>, AppError> {
+    let mut objects = Vec::new();
+    let mut continuation_token = None;
+    loop {
+        let output = client
+            .list_objects_v2()
+            .bucket(bucket)
+            .prefix(prefix)
+            .set_continuation_token(continuation_token)
+            .send()
+            .await
+            .map_err(|e| AppError::from_sdk("Failed to list source objects", &e))?;
+
+        objects.extend(output.contents().iter().map(|obj| SourceObject {
+            key: obj.key().unwrap_or_default().to_string(),
+            size: obj.size().unwrap_or_default().max(0) as u64,
+            etag: obj.e_tag().map(str::to_string),
+        }));
+
+        match output.next_continuation_token() {
+            Some(token) if output.is_truncated().unwrap_or(false) => {
+                continuation_token = Some(token.to_string())
+            }
+            _ => break,
+        }
+    }
+    Ok(objects)
+}
+
+/// 在两个客户端（可以属于不同的服务商和账号）之间流式传输对象
+///
+/// 数据不经过本地磁盘。小于一个分片的对象整体读入内存后上传，
+/// 更大的对象边下载边分片上传，内存占用不超过分片大小 ×（并发数 + 1）。
+/// 分片大小不超过 `MAX_MIGRATE_PART_SIZE`，并发数会被调低，使缓冲的数据不超过 `MAX_BUFFERED_BYTES`。
+///
+/// `checksums` 为 true 时目标对象带上整体校验值，分片上传的对象在下次传输时也能按校验值跳过；
+/// 目标服务不支持附加校验值时应为 false。
+pub struct ObjectMigrator {
+    source: Client,
+    dest: Client,
+    options: MultipartOptions,
+    checksums: bool,
+    progress: ProgressReporter,
+}
+
+impl ObjectMigrator {
+    pub fn new(
+        source: Client,
+        dest: Client,
+        options: MultipartOptions,
+        checksums: bool,
+        progress: ProgressReporter,
+    ) -> Self {
+        Self {
+            source,
+            dest,
+            options,
+            checksums,
+            progress,
+        }
+    }
+
+    /// 传输源前缀下的所有对象，单个对象失败时继续传输其余对象
+    pub async fn migrate_prefix(
+        &self,
+        source: &ObjectLocation,
+        dest: &ObjectLocation,
+        objects: &[SourceObject],
+    ) -> MigrateObjectsResult {
+        let mut result = MigrateObjectsResult {
+            transfer_id: self.progress.transfer_id().to_string(),
+            object_count: objects.len(),
+            total_size: objects.iter().map(|object| object.size).sum(),
+            transferred_count: 0,
+            skipped_count: 0,
+            failed: Vec::new(),
+        };
+
+        for object in objects {
+            let from = ObjectLocation::new(&source.bucket, &object.key);
+            let to = ObjectLocation::new(
+                &dest.bucket,
+                replace_prefix(&source.key, &dest.key, &object.key),
+            );
+
+            let migrated = match self.is_up_to_date(&from, &to, object).await {
+                Ok(true) => {
+                    self.progress.advance(object.size);
+                    result.skipped_count += 1;
+                    continue;
+                }
+                Ok(false) => self.migrate_object(&from, &to, object.size).await,
+                Err(e) => Err(e),
+            };
+            match migrated {
+                Ok(()) => result.transferred_count += 1,
+                Err(error) => {
+                    warn!(
+                        operation = "migrate_objects",
+                        key = %object.key,
+                        code = ?error.code,
+                        "{}",
+                        error
+                    );
+                    result.failed.push(CopyObjectError {
+                        source_key: from.key,
+                        dest_key: to.key,
+                        error,
+                    });
+                }
+            }
+        }
+
+        match result.failed.first() {
+            Some(failure) => self.progress.fail(&failure.error),
+            None => self.progress.complete(),
+        }
+        result
+    }
+
+    /// 目标位置是否已有大小相同、ETag 或附加校验值也相同的对象
+    async fn is_up_to_date(
+        &self,
+        source: &ObjectLocation,
+        dest: &ObjectLocation,
+        object: &SourceObject,
+    ) -> Result<bool, AppError> {
+        let existing = match head_with_checksums(&self.dest, dest).await {
+            Ok(existing) => existing,
+            Err(e) if e.code == ErrorCode::NotFound => return Ok(false),
+            Err(e) => return Err(e),
+        };
+        if existing.content_length().unwrap_or_default().max(0) as u64 != object.size {
+            return Ok(false);
+        }
+        if etags_match(existing.e_tag(), object.etag.as_deref()) {
+            return Ok(true);
+        }
+
+        // 分片上传的 ETag 与分片大小有关，不同服务之间一般不同，再比较附加校验值
+        let dest_checksums = checksums(&existing);
+        if dest_checksums.is_empty() {
+            return Ok(false);
+        }
+        let original = head_with_checksums(&self.source, source).await?;
+        Ok(checksums(&original)
+            .iter()
+            .any(|checksum| dest_checksums.contains(checksum)))
+    }
+
+    /// 传输单个对象，保留内容类型和元数据
+    pub async fn migrate_object(
+        &self,
+        source: &ObjectLocation,
+        dest: &ObjectLocation,
+        size: u64,
+    ) -> Result<(), AppError> {
+        // 请求源对象的校验值，SDK 会用它校验下载的数据
+        let output = self
+            .source
+            .get_object()
+            .bucket(&source.bucket)
+            .key(&source.key)
+            .checksum_mode(ChecksumMode::Enabled)
+            .send()
+            .await
+            .map_err(|e| AppError::from_sdk("Failed to read source object", &e))?;
+        let algorithm = self.checksums.then(|| checksum_algorithm(&output));
+
+        let part_size =
+            effective_part_size(size, self.options.part_size.min(MAX_MIGRATE_PART_SIZE));
+        if size <= part_size {
+            let data = output
+                .body
+                .collect()
+                .await
+                .map_err(|e| read_error(&e))?
+                .into_bytes();
+            let length = data.len() as u64;
+            self.dest
+                .put_object()
+                .bucket(&dest.bucket)
+                .key(&dest.key)
+                .set_content_type(output.content_type)
+                .set_cache_control(output.cache_control)
+                .set_content_disposition(output.content_disposition)
+                .set_content_encoding(output.content_encoding)
+                .set_content_language(output.content_language)
+                .set_metadata(output.metadata)
+                .set_checksum_algorithm(algorithm)
+                .body(ByteStream::from(data))
+                .send()
+                .await
+                .map_err(|e| AppError::from_sdk("Failed to upload object", &e))?;
+            self.progress.advance(length);
+            return Ok(());
+        }
+
+        let created = self
+            .dest
+            .create_multipart_upload()
+            .bucket(&dest.bucket)
+            .key(&dest.key)
+            .set_content_type(output.content_type)
+            .set_cache_control(output.cache_control)
+            .set_content_disposition(output.content_disposition)
+            .set_content_encoding(output.content_encoding)
+            .set_content_language(output.content_language)
+            .set_metadata(output.metadata)
+            .set_checksum_algorithm(algorithm.clone())
+            .set_checksum_type(algorithm.as_ref().map(|_| ChecksumType::FullObject))
+            .send()
+            .await
+            .map_err(|e| AppError::from_sdk("Failed to create multipart upload", &e))?;
+        let upload_id = created.upload_id().map(str::to_string).ok_or_else(|| {
+            AppError::new(
+                ErrorCode::Service,
+                "Multipart upload id missing from response",
+            )
+        })?;
+
+        let context = Arc::new(PartContext {
+            client: self.dest.clone(),
+            dest: dest.clone(),
+            upload_id: upload_id.clone(),
+            checksum_algorithm: algorithm,
+            progress: self.progress.clone(),
+        });
+        let result = match self.stream_parts(&context, output.body, part_size).await {
+            Ok(parts) => self
+                .dest
+                .complete_multipart_upload()
+                .bucket(&dest.bucket)
+                .key(&dest.key)
+                .upload_id(&upload_id)
+                .multipart_upload(
+                    CompletedMultipartUpload::builder()
+                        .set_parts(Some(parts))
+                        .build(),
+                )
+                .send()
+                .await
+                .map(|_| ())
+                .map_err(|e| AppError::from_sdk("Failed to complete multipart upload", &e)),
+            Err(e) => Err(e),
+        };
+
+        if result.is_err() {
+            if let Err(e) = self
+                .dest
+                .abort_multipart_upload()
+                .bucket(&dest.bucket)
+                .key(&dest.key)
+                .upload_id(&upload_id)
+                .send()
+                .await
+            {
+                warn!(
+                    operation = "migrate_objects",
+                    bucket = %dest.bucket,
+                    key = %dest.key,
+                    upload_id,
+                    "Failed to abort multipart upload: {}",
+                    e
+                );
+            }
+        }
+        result
+    }
+
+    /// 把下载流切成分片上传，在途的分片达到并发数时暂停读取
+    async fn stream_parts(
+        &self,
+        context: &Arc<PartContext>,
+        mut body: ByteStream,
+        part_size: u64,
+    ) -> Result<Vec<CompletedPart>, AppError> {
+        let concurrency = buffered_concurrency(part_size, self.options.concurrency);
+        let part_size = part_size as usize;
+        let mut buffer = BytesMut::with_capacity(part_size);
+        let mut tasks = JoinSet::new();
+        let mut parts = Vec::new();
+        let mut part_number = 0;
+
+        loop {
+            let chunk = body.try_next().await.map_err(|e| read_error(&e))?;
+            let finished = chunk.is_none();
+            if let Some(chunk) = chunk {
+                buffer.extend_from_slice(&chunk);
+            }
+
+            while buffer.len() >= part_size || (finished && !buffer.is_empty()) {
+                let data = buffer.split_to(part_size.min(buffer.len())).freeze();
+                part_number += 1;
+                while tasks.len() >= concurrency {
+                    if let Some(joined) = tasks.join_next().await {
+                        parts.push(joined_part(joined)?);
+                    }
+                }
+                tasks.spawn(upload_part(context.clone(), part_number, data));
+            }
+
+            if finished {
+                break;
+            }
+        }
+
+        while let Some(joined) = tasks.join_next().await {
+            parts.push(joined_part(joined)?);
+        }
+        parts.sort_by_key(|part| part.part_number());
+        Ok(parts)
+    }
+}
+
+/// 在途分片数，使在途分片和正在读取的分片总共不超过 `MAX_BUFFERED_BYTES`，至少为 1
+fn buffered_concurrency(part_size: u64, requested: usize) -> usize {
+    let limit = (MAX_BUFFERED_BYTES / part_size.max(1)).saturating_sub(1) as usize;
+    requested.min(limit).max(1)
+}
+
+/// 分片上传期间各个任务共享的上下文
+struct PartContext {
+    client: Client,
+    dest: ObjectLocation,
+    upload_id: String,
+    checksum_algorithm: Option<ChecksumAlgorithm>,
+    progress: ProgressReporter,
+}
+
+async fn upload_part(
+    context: Arc<PartContext>,
+    part_number: i32,
+    data: Bytes,
+) -> Result<CompletedPart, AppError> {
+    let length = data.len() as u64;
+    let output = context
+        .client
+        .upload_part()
+        .bucket(&context.dest.bucket)
+        .key(&context.dest.key)
+        .upload_id(&context.upload_id)
+        .part_number(part_number)
+        .content_length(length as i64)
+        .set_checksum_algorithm(context.checksum_algorithm.clone())
+        .body(ByteStream::from(data))
+        .send()
+        .await
+        .map_err(|e| AppError::from_sdk(&format!("Failed to upload part {}", part_number), &e))?;
+    context.progress.advance(length);
+
+    Ok(CompletedPart::builder()
+        .part_number(part_number)
+        .set_e_tag(output.e_tag().map(str::to_string))
+        .set_checksum_crc32(output.checksum_crc32().map(str::to_string))
+        .set_checksum_crc32_c(output.checksum_crc32_c().map(str::to_string))
+        .set_checksum_crc64_nvme(output.checksum_crc64_nvme().map(str::to_string))
+        .build())
+}
+
+fn joined_part(
+    joined: Result<Result<CompletedPart, AppError>, JoinError>,
+) -> Result<CompletedPart, AppError> {
+    joined.map_err(|e| AppError::internal(format!("Part upload task failed: {}", e)))?
+}
+
+async fn head_with_checksums(
+    client: &Client,
+    location: &ObjectLocation,
+) -> Result<HeadObjectOutput, AppError> {
+    client
+        .head_object()
+        .bucket(&location.bucket)
+        .key(&location.key)
+        .checksum_mode(ChecksumMode::Enabled)
+        .send()
+        .await
+        .map_err(|e| AppError::from_sdk("Failed to read object", &e))
+}
+
+fn read_error(error: &impl std::fmt::Display) -> AppError {
+    AppError::new(
+        ErrorCode::Network,
+        format!("Failed to read source object: {}", error),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::multipart::MIN_PART_SIZE;
+    use crate::test_support::MockS3;
+
+    #[test]
+    fn test_etags_match() {
+        assert!(etags_match(Some("\"abc\""), Some("abc")));
+        assert!(!etags_match(Some("\"abc\""), Some("\"abd\"")));
+        assert!(!etags_match(None, Some("abc")));
+        assert!(!etags_match(None, None));
+    }
+
+    #[test]
+    fn test_buffered_concurrency() {
+        assert_eq!(buffered_concurrency(16 * 1024 * 1024, 4), 4);
+        // Large parts lower the number of parts in flight
+        assert_eq!(buffered_concurrency(MAX_MIGRATE_PART_SIZE, 8), 3);
+        // At least one part is always uploaded
+        assert_eq!(buffered_concurrency(MAX_BUFFERED_BYTES, 4), 1);
+        assert_eq!(buffered_concurrency(MIN_PART_SIZE, 0), 1);
+    }
+
+    #[test]
+    fn test_checksums() {
+        let head = HeadObjectOutput::builder()
+            .checksum_crc32("AAAAAA==")
+            .checksum_sha256("c2hhMjU2")
+            .build();
+        assert_eq!(
+            checksums(&head),
+            vec![("CRC32", "AAAAAA=="), ("SHA256", "c2hhMjU2")]
+        );
+        assert!(checksums(&HeadObjectOutput::builder().build()).is_empty());
+    }
+
+    #[test]
+    fn test_checksum_algorithm() {
+        let output = GetObjectOutput::builder()
+            .checksum_crc64_nvme("AAAAAAAAAAA=")
+            .checksum_crc32("AAAAAA==")
+            .build();
+        assert_eq!(checksum_algorithm(&output), ChecksumAlgorithm::Crc64Nvme);
+
+        // Composite checksums of multipart objects can't be reproduced with other part sizes
+        let output = GetObjectOutput::builder()
+            .checksum_crc32_c("AAAAAA==-3")
+            .build();
+        assert_eq!(checksum_algorithm(&output), ChecksumAlgorithm::Crc32);
+        assert_eq!(
+            checksum_algorithm(&GetObjectOutput::builder().build()),
+            ChecksumAlgorithm::Crc32
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migrate_skips_unchanged_multipart_objects() {
+        let server = MockS3::start().await;
+        let large: Vec<u8> = (0..2 * MIN_PART_SIZE + 1024)
+            .map(|index| (index % 251) as u8)
+            .collect();
+        server.put("source", "backup/large.bin", &large);
+        server.put("source", "backup/small.txt", b"small");
+        let client = server.client().await;
+        let from = ObjectLocation::new("source", "backup/");
+        let to = ObjectLocation::new("dest", "archive/");
+        let objects = list_source(&client, "source", "backup/").await.unwrap();
+        let options = MultipartOptions {
+            part_size: MIN_PART_SIZE,
+            concurrency: 2,
+        };
+        let total: u64 = objects.iter().map(|object| object.size).sum();
+
+        let migrator = ObjectMigrator::new(
+            client.clone(),
+            client.clone(),
+            options,
+            true,
+            ProgressReporter::detached(total),
+        );
+        let result = migrator.migrate_prefix(&from, &to, &objects).await;
+        assert_eq!(result.transferred_count, 2);
+        assert!(result.failed.is_empty());
+
+        // The multipart ETag differs from the source, the full-object CRC32 doesn't
+        let (source_etag, source_crc32) = server
+            .object_checksums("source", "backup/large.bin")
+            .unwrap();
+        let (dest_etag, dest_crc32) = server
+            .object_checksums("dest", "archive/large.bin")
+            .unwrap();
+        assert!(dest_etag.ends_with("-3\""));
+        assert_ne!(dest_etag, source_etag);
+        assert_eq!(dest_crc32, source_crc32);
+
+        let uploads = server.requests().len();
+        let migrator = ObjectMigrator::new(
+            client.clone(),
+            client.clone(),
+            options,
+            true,
+            ProgressReporter::detached(total),
+        );
+        let result = migrator.migrate_prefix(&from, &to, &objects).await;
+        assert_eq!(result.skipped_count, 2);
+        assert_eq!(result.transferred_count, 0);
+        assert!(server.requests()[uploads..]
+            .iter()
+            .all(|request| request.method == "HEAD"));
+    }
+}
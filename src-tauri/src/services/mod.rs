@@ -1 +1,25 @@
+pub mod batch;
+pub mod client_pool;
+pub mod copy;
+pub mod credential_provider;
+pub mod credential_store;
+pub mod delete;
+pub mod diagnostics;
+pub mod download;
+pub mod inspector;
+pub mod logging;
+pub mod metadata;
+pub mod migrate;
+pub mod multipart;
+pub mod network;
+pub mod permissions;
+pub mod profile_import;
+pub mod profile_store;
+pub mod progress;
+pub mod provider;
 pub mod s3_client;
+pub mod tagging;
+pub mod transfer_manager;
+pub mod transport;
+pub mod upload;
+pub mod versioning;
@@ -0,0 +1,523 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use aws_sdk_s3::primitives::{ByteStream, Length};
+use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart};
+use aws_sdk_s3::Client;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+use crate::error::{AppError, ErrorCode};
+use crate::models::transfer::{MultipartCheckpoint, UploadedPart};
+use crate::services::progress::{track_upload, ProgressReporter};
+
+/// 单次 PutObject 允许的最大对象大小（5 GiB）
+pub const MAX_SINGLE_PUT_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+/// 分片的最小大小（最后一个分片除外）
+pub const MIN_PART_SIZE: u64 = 5 * 1024 * 1024;
+/// 分片的最大大小（5 GiB）
+pub const MAX_PART_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+/// 单个分片上传允许的最大分片数
+pub const MAX_PARTS: u64 = 10_000;
+
+/// 默认的分片上传阈值，超过该大小的文件使用分片上传
+pub const DEFAULT_MULTIPART_THRESHOLD: u64 = 64 * 1024 * 1024;
+/// 默认分片大小
+pub const DEFAULT_PART_SIZE: u64 = 16 * 1024 * 1024;
+/// 默认同时上传的分片数
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// 分片上传参数
+#[derive(Debug, Clone, Copy)]
+pub struct MultipartOptions {
+    pub part_size: u64,
+    pub concurrency: usize,
+}
+
+impl Default for MultipartOptions {
+    fn default() -> Self {
+        Self {
+            part_size: DEFAULT_PART_SIZE,
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+}
+
+/// 单个分片在文件中的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartRange {
+    pub part_number: i32,
+    pub offset: u64,
+    pub length: u64,
+}
+
+/// 计算实际使用的分片大小，限制在 `[MIN_PART_SIZE, MAX_PART_SIZE]` 内且分片数不超过上限
+pub fn effective_part_size(file_size: u64, requested: u64) -> u64 {
+    requested
+        .clamp(MIN_PART_SIZE, MAX_PART_SIZE)
+        .max(file_size.div_ceil(MAX_PARTS))
+}
+
+/// 文件的修改时间（Unix 毫秒），平台不支持时返回 `None`
+fn modified_millis(metadata: &std::fs::Metadata) -> Option<u64> {
+    let modified = metadata.modified().ok()?;
+    let elapsed = modified.duration_since(std::time::UNIX_EPOCH).ok()?;
+    u64::try_from(elapsed.as_millis()).ok()
+}
+
+/// 按分片大小切分文件
+pub fn plan_parts(file_size: u64, part_size: u64) -> Vec<PartRange> {
+    let mut parts = Vec::new();
+    let mut offset = 0;
+
+    while offset < file_size {
+        let length = part_size.min(file_size - offset);
+        parts.push(PartRange {
+            part_number: parts.len() as i32 + 1,
+            offset,
+            length,
+        });
+        offset += length;
+    }
+
+    parts
+}
+
+/// 分片上传期间各个任务共享的上下文
+struct UploadContext {
+    client: Client,
+    bucket: String,
+    key: String,
+    upload_id: String,
+    path: PathBuf,
+    progress: Option<ProgressReporter>,
+}
+
+/// 断点更新时的回调
+pub type CheckpointCallback = Arc<dyn Fn(&MultipartCheckpoint) + Send + Sync>;
+
+/// 从磁盘流式上传大文件的分片上传器
+///
+/// 每个分片直接从文件的对应区间读取，内存占用只与并发数相关；
+/// 任何一步失败都会调用 `abort_multipart_upload` 清理已上传的分片。
+/// 通过取消令牌中断时保留已上传的分片，之后可以用断点信息继续上传。
+pub struct MultipartUploader {
+    client: Client,
+    bucket: String,
+    key: String,
+    options: MultipartOptions,
+    progress: Option<ProgressReporter>,
+    cancel: Option<CancellationToken>,
+    checkpoint: Option<MultipartCheckpoint>,
+    on_checkpoint: Option<CheckpointCallback>,
+}
+
+impl MultipartUploader {
+    pub fn new(
+        client: Client,
+        bucket: impl Into<String>,
+        key: impl Into<String>,
+        options: MultipartOptions,
+    ) -> Self {
+        Self {
+            client,
+            bucket: bucket.into(),
+            key: key.into(),
+            options,
+            progress: None,
+            cancel: None,
+            checkpoint: None,
+            on_checkpoint: None,
+        }
+    }
+
+    /// 上传过程中通过 `reporter` 报告进度
+    pub fn with_progress(mut self, reporter: ProgressReporter) -> Self {
+        self.progress = Some(reporter);
+        self
+    }
+
+    /// 令牌被取消时停止上传，但不清理已上传的分片
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// 从已有的断点继续上传，并在每个分片完成后回调最新的断点
+    pub fn with_checkpoint(
+        mut self,
+        checkpoint: Option<MultipartCheckpoint>,
+        on_checkpoint: CheckpointCallback,
+    ) -> Self {
+        self.checkpoint = checkpoint;
+        self.on_checkpoint = Some(on_checkpoint);
+        self
+    }
+
+    /// 上传本地文件，返回上传的字节数
+    pub async fn upload_file(
+        &self,
+        path: &Path,
+        content_type: Option<&str>,
+    ) -> Result<u64, AppError> {
+        let metadata = tokio::fs::metadata(path)
+            .await
+            .map_err(|e| AppError::io("Failed to read file metadata", &e))?;
+        let file_size = metadata.len();
+        let modified = modified_millis(&metadata);
+
+        let checkpoint = match &self.checkpoint {
+            Some(checkpoint)
+                if checkpoint.file_size == file_size && checkpoint.modified == modified =>
+            {
+                checkpoint.clone()
+            }
+            stale => {
+                // 断点之后本地文件被修改过，已上传的分片不能再用
+                if let Some(stale) = stale {
+                    self.abort_upload(&stale.upload_id).await;
+                }
+                let upload_id = self.create(content_type).await?;
+                let checkpoint = MultipartCheckpoint {
+                    upload_id,
+                    part_size: effective_part_size(file_size, self.options.part_size),
+                    file_size,
+                    modified,
+                    parts: Vec::new(),
+                };
+                self.save_checkpoint(&checkpoint);
+                checkpoint
+            }
+        };
+
+        let context = Arc::new(UploadContext {
+            client: self.client.clone(),
+            bucket: self.bucket.clone(),
+            key: self.key.clone(),
+            upload_id: checkpoint.upload_id.clone(),
+            path: path.to_path_buf(),
+            progress: self.progress.clone(),
+        });
+
+        let result = match self.upload_parts(&context, file_size, checkpoint).await {
+            Ok(parts) => self.complete(&context, parts).await,
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = result {
+            // 被取消时由调用方决定保留还是清理分片
+            if !self.is_cancelled() {
+                self.abort_upload(&context.upload_id).await;
+            }
+            return Err(e);
+        }
+
+        Ok(file_size)
+    }
+
+    /// 放弃分片上传并清理已上传的分片
+    pub async fn abort_upload(&self, upload_id: &str) {
+        if let Err(e) = self
+            .client
+            .abort_multipart_upload()
+            .bucket(&self.bucket)
+            .key(&self.key)
+            .upload_id(upload_id)
+            .send()
+            .await
+        {
+            warn!(
+                operation = "upload",
+                bucket = %self.bucket,
+                key = %self.key,
+                upload_id,
+                "Failed to abort multipart upload: {}",
+                e
+            );
+        }
+    }
+
+    async fn create(&self, content_type: Option<&str>) -> Result<String, AppError> {
+        let mut create_request = self
+            .client
+            .create_multipart_upload()
+            .bucket(&self.bucket)
+            .key(&self.key);
+
+        if let Some(content_type) = content_type {
+            create_request = create_request.content_type(content_type);
+        }
+
+        let output = create_request
+            .send()
+            .await
+            .map_err(|e| AppError::from_sdk("Failed to create multipart upload", &e))?;
+
+        output.upload_id().map(|id| id.to_string()).ok_or_else(|| {
+            AppError::new(
+                ErrorCode::Service,
+                "Multipart upload id missing from response",
+            )
+        })
+    }
+
+    /// 并发上传尚未完成的分片，同时在途的分片数不超过 `concurrency`
+    async fn upload_parts(
+        &self,
+        context: &Arc<UploadContext>,
+        file_size: u64,
+        mut checkpoint: MultipartCheckpoint,
+    ) -> Result<Vec<CompletedPart>, AppError> {
+        let concurrency = self.options.concurrency.max(1);
+        let cancel = self.cancel.clone().unwrap_or_default();
+
+        let plan = plan_parts(file_size, checkpoint.part_size);
+        let mut pending = Vec::new();
+        for part in plan {
+            let done = checkpoint
+                .parts
+                .iter()
+                .any(|uploaded| uploaded.part_number == part.part_number);
+            if done {
+                // 续传时已完成的分片计入进度
+                if let Some(progress) = &self.progress {
+                    progress.advance(part.length);
+                }
+            } else {
+                pending.push(part);
+            }
+        }
+
+        let mut pending = pending.into_iter();
+        let mut tasks = JoinSet::new();
+
+        loop {
+            while tasks.len() < concurrency {
+                match pending.next() {
+                    Some(part) => {
+                        tasks.spawn(upload_part(context.clone(), part));
+                    }
+                    None => break,
+                }
+            }
+
+            let joined = tokio::select! {
+                joined = tasks.join_next() => joined,
+                _ = cancel.cancelled() => return Err(AppError::interrupted()),
+            };
+
+            let Some(joined) = joined else {
+                break;
+            };
+
+            match joined {
+                Ok(Ok(part)) => {
+                    checkpoint.parts.push(part);
+                    self.save_checkpoint(&checkpoint);
+                }
+                Ok(Err(e)) => return Err(e),
+                Err(e) => {
+                    return Err(AppError::internal(format!(
+                        "Part upload task failed: {}",
+                        e
+                    )))
+                }
+            }
+        }
+
+        let mut parts: Vec<CompletedPart> = checkpoint
+            .parts
+            .into_iter()
+            .map(|part| {
+                CompletedPart::builder()
+                    .part_number(part.part_number)
+                    .set_e_tag(part.etag)
+                    .build()
+            })
+            .collect();
+        parts.sort_by_key(|part| part.part_number());
+        Ok(parts)
+    }
+
+    async fn complete(
+        &self,
+        context: &UploadContext,
+        parts: Vec<CompletedPart>,
+    ) -> Result<(), AppError> {
+        let multipart_upload = CompletedMultipartUpload::builder()
+            .set_parts(Some(parts))
+            .build();
+
+        self.client
+            .complete_multipart_upload()
+            .bucket(&context.bucket)
+            .key(&context.key)
+            .upload_id(&context.upload_id)
+            .multipart_upload(multipart_upload)
+            .send()
+            .await
+            .map_err(|e| AppError::from_sdk("Failed to complete multipart upload", &e))?;
+
+        Ok(())
+    }
+
+    fn save_checkpoint(&self, checkpoint: &MultipartCheckpoint) {
+        if let Some(on_checkpoint) = &self.on_checkpoint {
+            on_checkpoint(checkpoint);
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .map(|token| token.is_cancelled())
+            .unwrap_or(false)
+    }
+}
+
+/// 上传单个分片，分片内容直接从文件区间流式读取
+async fn upload_part(
+    context: Arc<UploadContext>,
+    part: PartRange,
+) -> Result<UploadedPart, AppError> {
+    let body = ByteStream::read_from()
+        .path(&context.path)
+        .offset(part.offset)
+        .length(Length::Exact(part.length))
+        .build()
+        .await
+        .map_err(|e| {
+            AppError::new(
+                ErrorCode::Io,
+                format!("Failed to read part {}: {}", part.part_number, e),
+            )
+        })?;
+
+    let body = match &context.progress {
+        Some(progress) => track_upload(body, progress),
+        None => body,
+    };
+
+    let output = context
+        .client
+        .upload_part()
+        .bucket(&context.bucket)
+        .key(&context.key)
+        .upload_id(&context.upload_id)
+        .part_number(part.part_number)
+        .content_length(part.length as i64)
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| {
+            AppError::from_sdk(&format!("Failed to upload part {}", part.part_number), &e)
+        })?;
+
+    Ok(UploadedPart {
+        part_number: part.part_number,
+        etag: output.e_tag().map(|tag| tag.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::test_support::MockS3;
+
+    #[tokio::test]
+    async fn test_upload_file_restarts_when_source_changed() {
+        let mock = MockS3::start().await;
+        let client = mock.client().await;
+        let path =
+            std::env::temp_dir().join(format!("snowy-multipart-test-{}", std::process::id()));
+        let body = vec![7u8; (MIN_PART_SIZE + 1024) as usize];
+        std::fs::write(&path, &body).unwrap();
+
+        // Checkpoint recorded for an older, smaller version of the file
+        let stale = MultipartCheckpoint {
+            upload_id: "stale-upload".to_string(),
+            part_size: MIN_PART_SIZE,
+            file_size: 1024,
+            modified: None,
+            parts: vec![UploadedPart {
+                part_number: 1,
+                etag: Some("\"stale\"".to_string()),
+            }],
+        };
+        let saved = Arc::new(Mutex::new(Vec::new()));
+        let recorded = saved.clone();
+        let uploader = MultipartUploader::new(client, "bucket", "file.bin", Default::default())
+            .with_checkpoint(
+                Some(stale),
+                Arc::new(move |checkpoint: &MultipartCheckpoint| {
+                    recorded.lock().unwrap().push(checkpoint.clone());
+                }),
+            );
+
+        let size = uploader.upload_file(&path, None).await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(size, body.len() as u64);
+        let requests = mock.requests();
+        assert!(requests.iter().any(|request| {
+            request.method == "DELETE" && request.param("uploadId") == Some("stale-upload")
+        }));
+        assert!(requests.iter().all(|request| request.method == "DELETE"
+            || request.param("uploadId") != Some("stale-upload")));
+        let fresh = saved.lock().unwrap()[0].clone();
+        assert_ne!(fresh.upload_id, "stale-upload");
+        assert_eq!(fresh.file_size, body.len() as u64);
+        assert!(mock.keys("bucket").contains(&"file.bin".to_string()));
+    }
+
+    #[test]
+    fn test_effective_part_size() {
+        // Requested size below the S3 minimum is raised
+        assert_eq!(effective_part_size(100 * 1024 * 1024, 1024), MIN_PART_SIZE);
+        // Normal request is preserved
+        assert_eq!(
+            effective_part_size(100 * 1024 * 1024, DEFAULT_PART_SIZE),
+            DEFAULT_PART_SIZE
+        );
+        // Huge files grow the part size to stay within the part count limit
+        let file_size = 500 * 1024 * 1024 * 1024;
+        let part_size = effective_part_size(file_size, DEFAULT_PART_SIZE);
+        assert!(file_size.div_ceil(part_size) <= MAX_PARTS);
+        // Requested size above the S3 maximum is capped
+        assert_eq!(
+            effective_part_size(100 * 1024 * 1024, 64 * 1024 * 1024 * 1024),
+            MAX_PART_SIZE
+        );
+    }
+
+    #[test]
+    fn test_plan_parts() {
+        let parts = plan_parts(25, 10);
+        assert_eq!(
+            parts,
+            vec![
+                PartRange {
+                    part_number: 1,
+                    offset: 0,
+                    length: 10
+                },
+                PartRange {
+                    part_number: 2,
+                    offset: 10,
+                    length: 10
+                },
+                PartRange {
+                    part_number: 3,
+                    offset: 20,
+                    length: 5
+                },
+            ]
+        );
+
+        assert_eq!(plan_parts(20, 10).len(), 2);
+        assert!(plan_parts(0, 10).is_empty());
+    }
+}
@@ -0,0 +1,198 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use aws_sdk_s3::config::http::HttpRequest;
+use aws_sdk_s3::config::retry::RetryConfig;
+use aws_sdk_s3::config::timeout::TimeoutConfig;
+use aws_sdk_s3::config::{Builder, HttpClient, RuntimeComponents, SharedHttpClient};
+use aws_smithy_http_client::tls::rustls_provider::CryptoMode;
+use aws_smithy_http_client::tls::Provider;
+use aws_smithy_runtime_api::client::connector_metadata::ConnectorMetadata;
+use aws_smithy_runtime_api::client::http::{
+    HttpConnector, HttpConnectorFuture, HttpConnectorSettings, SharedHttpConnector,
+};
+use tokio::time::Instant;
+
+use crate::error::AppError;
+use crate::models::s3::{NetworkSettings, RetryMode};
+use crate::services::transport;
+
+/// 将超时、重试、限速、代理和证书设置应用到客户端配置，未设置的项保留 SDK 默认值
+pub fn apply(mut builder: Builder, settings: &NetworkSettings) -> Result<Builder, AppError> {
+    let secs = |value: Option<u64>| value.filter(|secs| *secs > 0).map(Duration::from_secs);
+    let connect_timeout = secs(settings.connect_timeout_secs);
+    let read_timeout = secs(settings.read_timeout_secs);
+    let operation_timeout = secs(settings.operation_timeout_secs);
+    if connect_timeout.is_some() || read_timeout.is_some() || operation_timeout.is_some() {
+        // 只覆盖设置了的项，其余沿用默认的超时配置
+        let mut timeout_config = TimeoutConfig::builder();
+        timeout_config
+            .set_connect_timeout(connect_timeout)
+            .set_read_timeout(read_timeout)
+            .set_operation_timeout(operation_timeout);
+        builder = builder.timeout_config(timeout_config.build());
+    }
+
+    let max_attempts = settings.max_attempts.filter(|attempts| *attempts > 0);
+    if settings.retry_mode != RetryMode::Standard || max_attempts.is_some() {
+        let retry_config = match settings.retry_mode {
+            RetryMode::Standard => RetryConfig::standard(),
+            RetryMode::Adaptive => RetryConfig::adaptive(),
+        };
+        builder = builder.retry_config(match max_attempts {
+            Some(attempts) => retry_config.with_max_attempts(attempts),
+            None => retry_config,
+        });
+    }
+
+    let http_client = transport::http_client(settings)?;
+    match settings.max_requests_per_second.filter(|rate| *rate > 0) {
+        Some(per_second) => {
+            builder = builder.http_client(RateLimitedClient {
+                inner: http_client.unwrap_or_else(default_http_client),
+                limiter: Arc::new(RateLimiter::new(per_second)),
+            });
+        }
+        None => {
+            if let Some(http_client) = http_client {
+                builder = builder.http_client(http_client);
+            }
+        }
+    }
+
+    Ok(builder)
+}
+
+/// 校验保存到配置中的网络设置
+pub fn validate(settings: &NetworkSettings) -> Result<(), AppError> {
+    let timeouts = [
+        settings.connect_timeout_secs,
+        settings.read_timeout_secs,
+        settings.operation_timeout_secs,
+    ];
+    if timeouts.contains(&Some(0)) {
+        return Err(AppError::invalid_input(
+            "Timeouts must be at least 1 second",
+        ));
+    }
+    if settings.max_attempts == Some(0) {
+        return Err(AppError::invalid_input("Max attempts must be at least 1"));
+    }
+    if settings.max_requests_per_second == Some(0) {
+        return Err(AppError::invalid_input(
+            "Request rate limit must be at least 1 per second",
+        ));
+    }
+    transport::validate(settings)
+}
+
+/// 与 SDK 默认相同的 HTTPS 客户端，需要包装时使用
+fn default_http_client() -> SharedHttpClient {
+    aws_smithy_http_client::Builder::new()
+        .tls_provider(Provider::Rustls(CryptoMode::AwsLc))
+        .build_https()
+}
+
+/// 按固定间隔依次放行请求，限制每秒的请求数
+#[derive(Debug)]
+pub struct RateLimiter {
+    interval: Duration,
+    /// 下一个请求最早可以发送的时间
+    next: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(per_second: u32) -> Self {
+        Self {
+            interval: Duration::from_secs(1) / per_second.max(1),
+            next: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// 等待轮到当前请求
+    pub async fn acquire(&self) {
+        let slot = {
+            let mut next = self.next.lock().unwrap();
+            let slot = (*next).max(Instant::now());
+            *next = slot + self.interval;
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+/// 在发送前经过限速器的 HTTP 客户端
+///
+/// 限速器在客户端创建时生成，同一配置的所有请求（包括批量删除、上传的每个分片和重试）共用。
+#[derive(Debug)]
+struct RateLimitedClient {
+    inner: SharedHttpClient,
+    limiter: Arc<RateLimiter>,
+}
+
+impl HttpClient for RateLimitedClient {
+    fn http_connector(
+        &self,
+        settings: &HttpConnectorSettings,
+        components: &RuntimeComponents,
+    ) -> SharedHttpConnector {
+        SharedHttpConnector::new(RateLimitedConnector {
+            inner: self.inner.http_connector(settings, components),
+            limiter: self.limiter.clone(),
+        })
+    }
+
+    fn connector_metadata(&self) -> Option<ConnectorMetadata> {
+        self.inner.connector_metadata()
+    }
+}
+
+#[derive(Debug)]
+struct RateLimitedConnector {
+    inner: SharedHttpConnector,
+    limiter: Arc<RateLimiter>,
+}
+
+impl HttpConnector for RateLimitedConnector {
+    fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+        let inner = self.inner.clone();
+        let limiter = self.limiter.clone();
+        HttpConnectorFuture::new(async move {
+            limiter.acquire().await;
+            inner.call(request).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(validate(&NetworkSettings::default()).is_ok());
+
+        let settings = NetworkSettings {
+            max_attempts: Some(0),
+            ..Default::default()
+        };
+        assert!(validate(&settings).is_err());
+
+        let settings = NetworkSettings {
+            read_timeout_secs: Some(0),
+            ..Default::default()
+        };
+        assert!(validate(&settings).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_spaces_requests() {
+        let limiter = RateLimiter::new(50);
+        let started = Instant::now();
+        for _ in 0..3 {
+            limiter.acquire().await;
+        }
+        // 第一个请求立即放行，之后每个间隔 20ms
+        assert!(started.elapsed() >= Duration::from_millis(40));
+    }
+}
@@ -0,0 +1,204 @@
+use aws_sdk_s3::config::http::HttpResponse;
+use aws_sdk_s3::error::{ProvideErrorMetadata, SdkError};
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::Client;
+
+use crate::error::AppError;
+use crate::models::s3::{BucketPermissions, Capability};
+use crate::services::copy;
+use crate::utils::id::new_id;
+
+/// 探测写入权限时创建的标记对象的名称前缀
+const PROBE_MARKER: &str = ".snowy-oss-permission-probe";
+
+/// 用不改变已有数据的请求探测存储桶（或前缀）的权限
+///
+/// 列举最多 1 个键，HEAD 已有对象，写入一个唯一的空标记对象后立即删除（写入失败时不探测删除），
+/// 再读取存储桶 ACL 和策略。网络错误直接返回，其他错误视为无法判断。
+pub async fn probe(
+    client: &Client,
+    bucket: &str,
+    prefix: Option<&str>,
+) -> Result<BucketPermissions, AppError> {
+    let prefix = normalize_prefix(prefix);
+
+    let (list, sample_key) = match client
+        .list_objects_v2()
+        .bucket(bucket)
+        .prefix(&prefix)
+        .max_keys(1)
+        .send()
+        .await
+    {
+        Ok(output) => (
+            Capability::Allowed,
+            output
+                .contents()
+                .first()
+                .and_then(|object| object.key())
+                .map(str::to_string),
+        ),
+        Err(e) => (capability_of(&e)?, None),
+    };
+
+    let marker_key = format!("{}{}", prefix, new_id(PROBE_MARKER));
+    let (write, marker_version) = match client
+        .put_object()
+        .bucket(bucket)
+        .key(&marker_key)
+        .body(ByteStream::from_static(b""))
+        .send()
+        .await
+    {
+        Ok(output) => (Capability::Allowed, output.version_id().map(str::to_string)),
+        Err(e) => (capability_of(&e)?, None),
+    };
+
+    // 优先读取已有对象，前缀下没有对象时读取刚写入的标记
+    let read_key =
+        sample_key.or_else(|| (write == Capability::Allowed).then(|| marker_key.clone()));
+    let read = match read_key {
+        Some(key) => match client.head_object().bucket(bucket).key(key).send().await {
+            Ok(_) => Capability::Allowed,
+            Err(e) => capability_of(&e)?,
+        },
+        None => Capability::Unknown,
+    };
+
+    // 只删除刚写入的标记（开启版本控制时删除其具体版本），没有写入时无法判断删除权限，
+    // 删除不存在的键在开启版本控制的存储桶中会留下删除标记
+    let delete = match write {
+        Capability::Allowed => match client
+            .delete_object()
+            .bucket(bucket)
+            .key(&marker_key)
+            .set_version_id(marker_version)
+            .send()
+            .await
+        {
+            Ok(_) => Capability::Allowed,
+            Err(e) => capability_of(&e)?,
+        },
+        _ => Capability::Unknown,
+    };
+    let leftover_key =
+        (write == Capability::Allowed && delete != Capability::Allowed).then_some(marker_key);
+
+    let read_acl = match client.get_bucket_acl().bucket(bucket).send().await {
+        Ok(_) => Capability::Allowed,
+        Err(e) => capability_of(&e)?,
+    };
+    let read_policy = match client.get_bucket_policy().bucket(bucket).send().await {
+        Ok(_) => Capability::Allowed,
+        // 有权限读取，只是没有设置策略
+        Err(e) if e.code() == Some("NoSuchBucketPolicy") => Capability::Allowed,
+        Err(e) => capability_of(&e)?,
+    };
+
+    Ok(BucketPermissions {
+        list,
+        read,
+        write,
+        delete,
+        read_acl,
+        read_policy,
+        leftover_key,
+    })
+}
+
+/// 去掉开头的 `/` 后补全结尾的 `/`，标记对象写在该“文件夹”下
+fn normalize_prefix(prefix: Option<&str>) -> String {
+    copy::normalize_prefix(prefix.unwrap_or_default().trim_start_matches('/'))
+}
+
+/// 权限不足返回 `Denied`，网络错误返回 `Err`，其他错误无法判断权限
+fn capability_of<E>(error: &SdkError<E, HttpResponse>) -> Result<Capability, AppError>
+where
+    E: ProvideErrorMetadata + std::error::Error + 'static,
+{
+    if matches!(
+        error,
+        SdkError::DispatchFailure(_) | SdkError::TimeoutError(_) | SdkError::ConstructionFailure(_)
+    ) {
+        return Err(AppError::from_sdk("Failed to probe permissions", error));
+    }
+
+    let status = error
+        .raw_response()
+        .map(|response| response.status().as_u16());
+    Ok(match (error.code(), status) {
+        (Some("AccessDenied" | "AllAccessDisabled"), _) | (_, Some(403)) => Capability::Denied,
+        _ => Capability::Unknown,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::MockS3;
+
+    #[tokio::test]
+    async fn test_probe_removes_marker() {
+        let mock = MockS3::start().await;
+        mock.put("bucket", "photos/a.jpg", b"data");
+
+        let permissions = probe(&mock.client().await, "bucket", Some("photos"))
+            .await
+            .unwrap();
+
+        assert_eq!(permissions.list, Capability::Allowed);
+        assert_eq!(permissions.read, Capability::Allowed);
+        assert_eq!(permissions.write, Capability::Allowed);
+        assert_eq!(permissions.delete, Capability::Allowed);
+        // The mock does not implement ACL or policy reads
+        assert_eq!(permissions.read_acl, Capability::Unknown);
+        assert_eq!(permissions.leftover_key, None);
+        assert_eq!(mock.keys("bucket"), vec!["photos/a.jpg"]);
+    }
+
+    #[tokio::test]
+    async fn test_probe_skips_delete_when_write_denied() {
+        let mock = MockS3::start().await;
+        mock.deny("PUT");
+
+        let permissions = probe(&mock.client().await, "bucket", None).await.unwrap();
+
+        assert_eq!(permissions.write, Capability::Denied);
+        assert_eq!(permissions.read, Capability::Unknown);
+        assert_eq!(permissions.delete, Capability::Unknown);
+        assert!(mock
+            .requests()
+            .iter()
+            .all(|request| request.method != "DELETE"));
+    }
+
+    #[tokio::test]
+    async fn test_probe_reports_leftover_marker() {
+        let mock = MockS3::start().await;
+        mock.deny("DELETE");
+
+        let permissions = probe(&mock.client().await, "bucket", Some("logs"))
+            .await
+            .unwrap();
+
+        assert_eq!(permissions.write, Capability::Allowed);
+        assert_eq!(permissions.delete, Capability::Denied);
+        let leftover = permissions.leftover_key.unwrap();
+        assert!(leftover.starts_with("logs/.snowy-oss-permission-probe"));
+        assert_eq!(mock.keys("bucket"), vec![leftover]);
+    }
+
+    #[test]
+    fn test_normalize_prefix() {
+        assert_eq!(normalize_prefix(None), "");
+        assert_eq!(normalize_prefix(Some("")), "");
+        assert_eq!(normalize_prefix(Some("photos")), "photos/");
+        assert_eq!(normalize_prefix(Some("/photos/2024/")), "photos/2024/");
+    }
+
+    #[test]
+    fn test_capability_serialization() {
+        let json = serde_json::to_string(&Capability::Allowed).unwrap();
+        assert_eq!(json, "\"allowed\"");
+    }
+}
@@ -0,0 +1,786 @@
+use std::path::{Path, PathBuf};
+
+use crate::error::AppError;
+use crate::models::profile::{
+    mask_key, ImportCandidate, ImportSource, ProfileInput, SkippedImport,
+};
+use crate::models::s3::{
+    AddressingStyle, ChecksumMode, Compatibility, CredentialSource, NetworkSettings, RetryMode,
+    S3Config,
+};
+use crate::services::provider::compatibility_defaults;
+use crate::utils::ini::{parse_ini, IniSection};
+
+/// 读取配置文件并转换为待导入的配置
+///
+/// `path` 为 `None` 时读取该工具的默认位置，AWS 会同时读取 credentials 和 config。
+pub fn load_source(
+    source: ImportSource,
+    path: Option<&Path>,
+    home_dir: &Path,
+) -> Result<Vec<ProfileInput>, AppError> {
+    let paths = match path {
+        Some(path) => vec![path.to_path_buf()],
+        None => default_paths(source, home_dir),
+    };
+
+    let mut contents = Vec::new();
+    for path in &paths {
+        match std::fs::read_to_string(path) {
+            Ok(content) => contents.push(content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => {
+                return Err(AppError::io(
+                    &format!("Failed to read {}", path.display()),
+                    &e,
+                ))
+            }
+        }
+    }
+    if contents.is_empty() {
+        let tried: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+        return Err(AppError::not_found(format!(
+            "No config file found at {}",
+            tried.join(", ")
+        )));
+    }
+
+    match source {
+        ImportSource::Aws => Ok(parse_aws(&contents)),
+        ImportSource::Rclone => parse_rclone(&contents[0]),
+        ImportSource::S3cmd => Ok(parse_s3cmd(&contents[0])),
+    }
+}
+
+/// 各工具配置文件的默认位置，支持对应的环境变量
+pub fn default_paths(source: ImportSource, home_dir: &Path) -> Vec<PathBuf> {
+    let env_path = |name: &str| std::env::var_os(name).map(PathBuf::from);
+
+    match source {
+        ImportSource::Aws => vec![
+            env_path("AWS_SHARED_CREDENTIALS_FILE")
+                .unwrap_or_else(|| home_dir.join(".aws").join("credentials")),
+            env_path("AWS_CONFIG_FILE").unwrap_or_else(|| home_dir.join(".aws").join("config")),
+        ],
+        ImportSource::Rclone => {
+            if let Some(path) = env_path("RCLONE_CONFIG") {
+                return vec![path];
+            }
+            let mut paths = vec![home_dir.join(".config").join("rclone").join("rclone.conf")];
+            if let Some(app_data) = env_path("APPDATA") {
+                paths.push(app_data.join("rclone").join("rclone.conf"));
+            }
+            paths
+        }
+        ImportSource::S3cmd => {
+            vec![env_path("S3CMD_CONFIG").unwrap_or_else(|| home_dir.join(".s3cfg"))]
+        }
+    }
+}
+
+/// 解析 AWS 共享凭证和配置文件，同名配置合并
+pub fn parse_aws(contents: &[String]) -> Vec<ProfileInput> {
+    let mut merged: Vec<IniSection> = Vec::new();
+
+    for content in contents {
+        for section in parse_ini(content) {
+            // config 文件中使用 "[profile name]"，credentials 文件中使用 "[name]"
+            let name = section
+                .name
+                .strip_prefix("profile ")
+                .unwrap_or(&section.name)
+                .trim()
+                .to_string();
+            if name.starts_with("sso-session ") || name.starts_with("services ") {
+                continue;
+            }
+            match merged.iter_mut().find(|s| s.name == name) {
+                Some(existing) => existing.entries.extend(section.entries),
+                None => merged.push(IniSection {
+                    name,
+                    entries: section.entries,
+                }),
+            }
+        }
+    }
+
+    merged
+        .into_iter()
+        .map(|section| {
+            let region = section.get("region").unwrap_or("us-east-1").to_string();
+            let endpoint = section
+                .get("s3.endpoint_url")
+                .or_else(|| section.get("endpoint_url"))
+                .map(normalize_endpoint);
+            let addressing_style = match section.get("s3.addressing_style") {
+                Some("path") => AddressingStyle::Path,
+                Some("virtual") => AddressingStyle::Virtual,
+                // auto 或未设置：AWS 本身使用虚拟主机方式，其他兼容服务使用路径方式
+                _ if endpoint.is_none() => AddressingStyle::Virtual,
+                _ => AddressingStyle::Path,
+            };
+            let access_key_id = value(&section, "aws_access_key_id");
+            // 没有静态密钥但配置了角色、SSO 或外部进程时，交给 SDK 按命名配置解析
+            let delegated = [
+                "role_arn",
+                "credential_process",
+                "sso_session",
+                "sso_start_url",
+            ]
+            .iter()
+            .any(|key| section.get(key).is_some());
+            let credential_source = match section.get("aws_session_token") {
+                Some(token) => CredentialSource::SessionToken {
+                    session_token: token.to_string(),
+                },
+                None if access_key_id.is_empty() && delegated => CredentialSource::Profile {
+                    name: section.name.clone(),
+                },
+                None => CredentialSource::Static,
+            };
+            let endpoint = endpoint.unwrap_or_else(|| aws_endpoint(&region));
+
+            let mut compatibility = compatibility_defaults(&endpoint).compatibility;
+            match section.get("request_checksum_calculation") {
+                Some(value) if value.eq_ignore_ascii_case("when_required") => {
+                    compatibility.request_checksum = ChecksumMode::WhenRequired
+                }
+                Some(value) if value.eq_ignore_ascii_case("when_supported") => {
+                    compatibility.request_checksum = ChecksumMode::WhenSupported
+                }
+                _ => {}
+            }
+            if let Some(enabled) = flag(&section, "s3.payload_signing_enabled") {
+                compatibility.unsigned_payload = !enabled;
+            }
+            compatibility.dual_stack = flag(&section, "s3.use_dualstack_endpoint")
+                .or_else(|| flag(&section, "use_dualstack_endpoint"))
+                .unwrap_or_default();
+            compatibility.accelerate =
+                flag(&section, "s3.use_accelerate_endpoint").unwrap_or_default();
+
+            // legacy 模式按标准重试处理
+            let network = NetworkSettings {
+                retry_mode: match section.get("retry_mode") {
+                    Some(value) if value.eq_ignore_ascii_case("adaptive") => RetryMode::Adaptive,
+                    _ => RetryMode::Standard,
+                },
+                max_attempts: section
+                    .get("max_attempts")
+                    .and_then(|value| value.parse().ok()),
+                ..Default::default()
+            };
+
+            ProfileInput {
+                name: section.name.clone(),
+                config: S3Config {
+                    endpoint,
+                    region,
+                    access_key_id,
+                    secret_access_key: value(&section, "aws_secret_access_key"),
+                    bucket: None,
+                    custom_path: None,
+                    addressing_style,
+                    credential_source,
+                    compatibility,
+                    network,
+                },
+            }
+        })
+        .collect()
+}
+
+/// 解析 rclone.conf 中 `type = s3` 的远端
+pub fn parse_rclone(content: &str) -> Result<Vec<ProfileInput>, AppError> {
+    if content.contains("RCLONE_ENCRYPT_V0:") {
+        return Err(AppError::invalid_input(
+            "Encrypted rclone configs are not supported, run `rclone config show` and import the output instead",
+        ));
+    }
+
+    Ok(parse_ini(content)
+        .into_iter()
+        .filter(|section| section.get("type") == Some("s3"))
+        .map(|section| {
+            let provider = section.get("provider").unwrap_or("Other");
+            let region = section.get("region").unwrap_or("us-east-1").to_string();
+            let endpoint = section
+                .get("endpoint")
+                .filter(|endpoint| !endpoint.is_empty())
+                .map(normalize_endpoint)
+                .unwrap_or_else(|| aws_endpoint(&region));
+            // rclone 默认使用路径方式，AWS 提供商除外
+            let addressing_style = match section.get("force_path_style") {
+                Some(value) if value.eq_ignore_ascii_case("false") => AddressingStyle::Virtual,
+                Some(_) => AddressingStyle::Path,
+                None if provider == "AWS" => AddressingStyle::Virtual,
+                None => AddressingStyle::Path,
+            };
+            let compatibility = Compatibility {
+                unsigned_payload: flag(&section, "use_unsigned_payload").unwrap_or_default(),
+                dual_stack: flag(&section, "use_dual_stack").unwrap_or_default(),
+                accelerate: flag(&section, "use_accelerate_endpoint").unwrap_or_default(),
+                ..compatibility_defaults(&endpoint).compatibility
+            };
+
+            ProfileInput {
+                name: section.name.clone(),
+                config: S3Config {
+                    endpoint,
+                    region,
+                    access_key_id: value(&section, "access_key_id"),
+                    secret_access_key: value(&section, "secret_access_key"),
+                    bucket: None,
+                    custom_path: None,
+                    addressing_style,
+                    credential_source: CredentialSource::Static,
+                    compatibility,
+                    network: Default::default(),
+                },
+            }
+        })
+        .collect())
+}
+
+/// 解析 s3cmd 的 `.s3cfg`
+pub fn parse_s3cmd(content: &str) -> Vec<ProfileInput> {
+    parse_ini(content)
+        .into_iter()
+        .map(|section| {
+            let host_base = section.get("host_base").unwrap_or("s3.amazonaws.com");
+            let use_https = !section
+                .get("use_https")
+                .is_some_and(|value| value.eq_ignore_ascii_case("false"));
+            let scheme = if use_https { "https" } else { "http" };
+            // host_bucket 中带有 %(bucket)s 时使用虚拟主机方式
+            let addressing_style = match section.get("host_bucket") {
+                Some(host_bucket) if host_bucket.contains("%(bucket)s") => AddressingStyle::Virtual,
+                _ => AddressingStyle::Path,
+            };
+            let region = match section.get("bucket_location") {
+                None | Some("") | Some("US") => "us-east-1".to_string(),
+                Some(location) => location.to_string(),
+            };
+            let name = if section.name == "default" {
+                "s3cmd".to_string()
+            } else {
+                format!("s3cmd-{}", section.name)
+            };
+
+            let endpoint = format!("{}://{}", scheme, host_base);
+
+            ProfileInput {
+                name,
+                config: S3Config {
+                    compatibility: compatibility_defaults(&endpoint).compatibility,
+                    endpoint,
+                    region,
+                    access_key_id: value(&section, "access_key"),
+                    secret_access_key: value(&section, "secret_key"),
+                    bucket: None,
+                    custom_path: None,
+                    addressing_style,
+                    credential_source: CredentialSource::Static,
+                    network: Default::default(),
+                },
+            }
+        })
+        .collect()
+}
+
+/// 生成导入预览，标记重名和缺少密钥的配置
+pub fn preview(inputs: &[ProfileInput], existing_names: &[String]) -> Vec<ImportCandidate> {
+    inputs
+        .iter()
+        .map(|input| {
+            let problem = if input.config.credential_source.uses_static_keys()
+                && (input.config.access_key_id.is_empty()
+                    || input.config.secret_access_key.is_empty())
+            {
+                Some("Missing access key or secret key".to_string())
+            } else {
+                None
+            };
+
+            ImportCandidate {
+                name: input.name.clone(),
+                endpoint: input.config.endpoint.clone(),
+                region: input.config.region.clone(),
+                access_key_hint: mask_key(&input.config.access_key_id),
+                addressing_style: input.config.addressing_style,
+                conflict: existing_names.contains(&input.name),
+                problem,
+            }
+        })
+        .collect()
+}
+
+/// 在名称后追加 " (2)"、" (3)"……直到不与已有名称重复
+pub fn unique_name(name: &str, existing_names: &[String]) -> String {
+    (2..)
+        .map(|n| format!("{} ({})", name, n))
+        .find(|candidate| !existing_names.contains(candidate))
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// 导出为 AWS 配置文件格式（`~/.aws/config` 也接受访问密钥），返回内容和跳过的配置
+///
+/// AssumeRole 配置导出为带 `role_arn` 的配置加上保存访问密钥的 `<名称>-source` 配置；
+/// 环境变量、命名配置和匿名访问在 AWS 配置文件中没有对应写法，会被跳过。
+pub fn export_aws(profiles: &[(String, S3Config)]) -> (String, Vec<SkippedImport>) {
+    let mut output = String::new();
+    let mut skipped = Vec::new();
+
+    for (name, config) in profiles {
+        let name = section_name(name);
+        let Some(credentials) = aws_credentials(&name, config) else {
+            skipped.push(SkippedImport {
+                name,
+                reason: format!(
+                    "{} credentials cannot be exported to an AWS config file",
+                    credential_kind(&config.credential_source)
+                ),
+            });
+            continue;
+        };
+
+        output.push_str(&format!("[profile {}]\n", name));
+        output.push_str(&credentials);
+        push_aws_settings(&mut output, config);
+
+        if matches!(
+            config.credential_source,
+            CredentialSource::AssumeRole { .. }
+        ) {
+            output.push_str(&format!("[profile {}-source]\n", name));
+            output.push_str(&aws_keys(config));
+            output.push('\n');
+        }
+    }
+
+    (output, skipped)
+}
+
+/// AWS 配置中的凭证设置，无法表示的凭证来源返回 `None`
+fn aws_credentials(name: &str, config: &S3Config) -> Option<String> {
+    match &config.credential_source {
+        CredentialSource::Static => Some(aws_keys(config)),
+        CredentialSource::SessionToken { session_token } => Some(format!(
+            "{}aws_session_token = {}\n",
+            aws_keys(config),
+            session_token
+        )),
+        CredentialSource::AssumeRole {
+            role_arn,
+            session_name,
+            external_id,
+            mfa_serial,
+            duration_seconds,
+            ..
+        } => {
+            let mut lines = format!(
+                "role_arn = {}\nsource_profile = {}-source\n",
+                role_arn, name
+            );
+            if let Some(session_name) = session_name {
+                lines.push_str(&format!("role_session_name = {}\n", session_name));
+            }
+            if let Some(external_id) = external_id {
+                lines.push_str(&format!("external_id = {}\n", external_id));
+            }
+            if let Some(mfa_serial) = mfa_serial {
+                lines.push_str(&format!("mfa_serial = {}\n", mfa_serial));
+            }
+            if let Some(duration_seconds) = duration_seconds {
+                lines.push_str(&format!("duration_seconds = {}\n", duration_seconds));
+            }
+            Some(lines)
+        }
+        CredentialSource::Environment
+        | CredentialSource::Profile { .. }
+        | CredentialSource::Anonymous => None,
+    }
+}
+
+fn aws_keys(config: &S3Config) -> String {
+    format!(
+        "aws_access_key_id = {}\naws_secret_access_key = {}\n",
+        config.access_key_id, config.secret_access_key
+    )
+}
+
+/// AWS 配置中除凭证以外的连接设置，以空行结束
+fn push_aws_settings(output: &mut String, config: &S3Config) {
+    let addressing_style = match config.addressing_style {
+        AddressingStyle::Path => "path",
+        AddressingStyle::Virtual => "virtual",
+    };
+    output.push_str(&format!("region = {}\n", config.region));
+    output.push_str(&format!("endpoint_url = {}\n", config.endpoint));
+    if config.compatibility.request_checksum == ChecksumMode::WhenRequired {
+        output.push_str("request_checksum_calculation = when_required\n");
+    }
+    if config.network.retry_mode == RetryMode::Adaptive {
+        output.push_str("retry_mode = adaptive\n");
+    }
+    if let Some(max_attempts) = config.network.max_attempts {
+        output.push_str(&format!("max_attempts = {}\n", max_attempts));
+    }
+    output.push_str(&format!(
+        "s3 =\n  addressing_style = {}\n",
+        addressing_style
+    ));
+    if config.compatibility.unsigned_payload {
+        output.push_str("  payload_signing_enabled = false\n");
+    }
+    if config.compatibility.dual_stack {
+        output.push_str("  use_dualstack_endpoint = true\n");
+    }
+    if config.compatibility.accelerate {
+        output.push_str("  use_accelerate_endpoint = true\n");
+    }
+    output.push('\n');
+}
+
+/// 导出为 rclone.conf 格式，返回内容和跳过的配置
+///
+/// 环境变量和命名配置使用 `env_auth`，匿名访问不写密钥；AssumeRole 配置会被跳过。
+pub fn export_rclone(profiles: &[(String, S3Config)]) -> (String, Vec<SkippedImport>) {
+    let mut output = String::new();
+    let mut skipped = Vec::new();
+
+    for (name, config) in profiles {
+        let name = section_name(name);
+        let mut credentials = String::new();
+        match &config.credential_source {
+            CredentialSource::Static | CredentialSource::SessionToken { .. } => {
+                credentials.push_str(&format!("access_key_id = {}\n", config.access_key_id));
+                credentials.push_str(&format!(
+                    "secret_access_key = {}\n",
+                    config.secret_access_key
+                ));
+                if let CredentialSource::SessionToken { session_token } = &config.credential_source
+                {
+                    credentials.push_str(&format!("session_token = {}\n", session_token));
+                }
+            }
+            CredentialSource::Environment => credentials.push_str("env_auth = true\n"),
+            CredentialSource::Profile { name } => {
+                credentials.push_str(&format!("env_auth = true\nprofile = {}\n", name));
+            }
+            // 不填密钥时 rclone 匿名访问
+            CredentialSource::Anonymous => {}
+            CredentialSource::AssumeRole { .. } => {
+                skipped.push(SkippedImport {
+                    name,
+                    reason: format!(
+                        "{} credentials cannot be exported to rclone.conf",
+                        credential_kind(&config.credential_source)
+                    ),
+                });
+                continue;
+            }
+        }
+
+        output.push_str(&format!("[{}]\n", name));
+        output.push_str("type = s3\nprovider = Other\n");
+        output.push_str(&credentials);
+        output.push_str(&format!("region = {}\n", config.region));
+        output.push_str(&format!("endpoint = {}\n", config.endpoint));
+        output.push_str(&format!(
+            "force_path_style = {}\n",
+            config.addressing_style == AddressingStyle::Path
+        ));
+        if config.compatibility.unsigned_payload {
+            output.push_str("use_unsigned_payload = true\n");
+        }
+        if config.compatibility.dual_stack {
+            output.push_str("use_dual_stack = true\n");
+        }
+        if config.compatibility.accelerate {
+            output.push_str("use_accelerate_endpoint = true\n");
+        }
+        output.push('\n');
+    }
+
+    (output, skipped)
+}
+
+/// 跳过原因中使用的凭证来源名称
+fn credential_kind(source: &CredentialSource) -> &'static str {
+    match source {
+        CredentialSource::Static => "Static",
+        CredentialSource::SessionToken { .. } => "Session token",
+        CredentialSource::AssumeRole { .. } => "AssumeRole",
+        CredentialSource::Environment => "Environment",
+        CredentialSource::Profile { .. } => "AWS profile",
+        CredentialSource::Anonymous => "Anonymous",
+    }
+}
+
+fn value(section: &IniSection, key: &str) -> String {
+    section.get(key).unwrap_or_default().to_string()
+}
+
+/// 读取 `true`/`false` 取值的开关
+fn flag(section: &IniSection, key: &str) -> Option<bool> {
+    match section.get(key)? {
+        value if value.eq_ignore_ascii_case("true") => Some(true),
+        value if value.eq_ignore_ascii_case("false") => Some(false),
+        _ => None,
+    }
+}
+
+fn aws_endpoint(region: &str) -> String {
+    format!("https://s3.{}.amazonaws.com", region)
+}
+
+fn normalize_endpoint(endpoint: &str) -> String {
+    if endpoint.contains("://") {
+        endpoint.to_string()
+    } else {
+        format!("https://{}", endpoint)
+    }
+}
+
+/// 小节名中不能出现空白和方括号
+fn section_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_whitespace() || c == '[' || c == ']' {
+                '-'
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_test_config;
+
+    #[test]
+    fn test_parse_aws() {
+        let credentials = "\
+[default]
+aws_access_key_id = AKIADEFAULT
+aws_secret_access_key = default-secret
+
+[minio]
+aws_access_key_id = minioadmin
+aws_secret_access_key = minio-secret
+"
+        .to_string();
+        let config = "\
+[default]
+region = eu-west-1
+s3 =
+  use_dualstack_endpoint = true
+
+[profile minio]
+endpoint_url = localhost:9000
+request_checksum_calculation = when_supported
+retry_mode = adaptive
+max_attempts = 5
+s3 =
+  addressing_style = path
+  payload_signing_enabled = false
+
+[profile admin]
+role_arn = arn:aws:iam::123456789012:role/admin
+source_profile = default
+"
+        .to_string();
+
+        let profiles = parse_aws(&[credentials, config]);
+        assert_eq!(profiles.len(), 3);
+
+        assert_eq!(profiles[0].name, "default");
+        assert_eq!(profiles[0].config.region, "eu-west-1");
+        assert_eq!(
+            profiles[0].config.endpoint,
+            "https://s3.eu-west-1.amazonaws.com"
+        );
+        assert_eq!(
+            profiles[0].config.addressing_style,
+            AddressingStyle::Virtual
+        );
+        assert!(profiles[0].config.compatibility.dual_stack);
+
+        assert_eq!(profiles[1].name, "minio");
+        assert_eq!(profiles[1].config.endpoint, "https://localhost:9000");
+        assert_eq!(profiles[1].config.secret_access_key, "minio-secret");
+        assert_eq!(profiles[1].config.addressing_style, AddressingStyle::Path);
+        assert_eq!(
+            profiles[1].config.compatibility.request_checksum,
+            ChecksumMode::WhenSupported
+        );
+        assert!(profiles[1].config.compatibility.unsigned_payload);
+        assert_eq!(profiles[1].config.network.retry_mode, RetryMode::Adaptive);
+        assert_eq!(profiles[1].config.network.max_attempts, Some(5));
+
+        assert_eq!(
+            profiles[2].config.credential_source,
+            CredentialSource::Profile {
+                name: "admin".to_string()
+            }
+        );
+        assert!(preview(&profiles, &[])[2].problem.is_none());
+    }
+
+    #[test]
+    fn test_parse_rclone() {
+        let content = "\
+[r2]
+type = s3
+provider = Cloudflare
+access_key_id = r2-key
+secret_access_key = r2-secret
+region = auto
+endpoint = https://account.r2.cloudflarestorage.com
+
+[gdrive]
+type = drive
+
+[aws]
+type = s3
+provider = AWS
+env_auth = true
+region = us-west-2
+";
+        let profiles = parse_rclone(content).unwrap();
+        assert_eq!(profiles.len(), 2);
+        assert_eq!(profiles[0].name, "r2");
+        assert_eq!(profiles[0].config.region, "auto");
+        assert_eq!(profiles[0].config.addressing_style, AddressingStyle::Path);
+        assert_eq!(
+            profiles[0].config.compatibility.request_checksum,
+            ChecksumMode::WhenRequired
+        );
+        assert_eq!(
+            profiles[1].config.endpoint,
+            "https://s3.us-west-2.amazonaws.com"
+        );
+        assert_eq!(
+            profiles[1].config.addressing_style,
+            AddressingStyle::Virtual
+        );
+
+        let candidates = preview(&profiles, &["r2".to_string()]);
+        assert!(candidates[0].conflict);
+        assert!(candidates[0].problem.is_none());
+        assert!(candidates[1].problem.is_some(), "env_auth has no keys");
+
+        assert!(parse_rclone("RCLONE_ENCRYPT_V0:\nabc").is_err());
+    }
+
+    #[test]
+    fn test_parse_s3cmd() {
+        let content = "\
+[default]
+access_key = DOKEY
+secret_key = do-secret
+host_base = nyc3.digitaloceanspaces.com
+host_bucket = %(bucket)s.nyc3.digitaloceanspaces.com
+bucket_location = nyc3
+use_https = True
+";
+        let profiles = parse_s3cmd(content);
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].name, "s3cmd");
+        assert_eq!(
+            profiles[0].config.endpoint,
+            "https://nyc3.digitaloceanspaces.com"
+        );
+        assert_eq!(profiles[0].config.region, "nyc3");
+        assert_eq!(
+            profiles[0].config.addressing_style,
+            AddressingStyle::Virtual
+        );
+    }
+
+    #[test]
+    fn test_unique_name() {
+        let existing = vec!["r2".to_string(), "r2 (2)".to_string()];
+        assert_eq!(unique_name("r2", &existing), "r2 (3)");
+        assert_eq!(unique_name("minio", &existing), "minio (2)");
+    }
+
+    #[test]
+    fn test_export_roundtrip() {
+        let profiles = parse_rclone(
+            "[my r2]\ntype = s3\naccess_key_id = k\nsecret_access_key = s\nregion = auto\nendpoint = https://r2.example.com\n",
+        )
+        .unwrap();
+        let mut exported: Vec<(String, S3Config)> =
+            profiles.into_iter().map(|p| (p.name, p.config)).collect();
+        exported[0].1.compatibility.unsigned_payload = true;
+        exported[0].1.network.max_attempts = Some(8);
+
+        let rclone = parse_rclone(&export_rclone(&exported).0).unwrap();
+        assert_eq!(rclone[0].name, "my-r2");
+        assert_eq!(rclone[0].config.endpoint, "https://r2.example.com");
+        assert_eq!(rclone[0].config.addressing_style, AddressingStyle::Path);
+        assert!(rclone[0].config.compatibility.unsigned_payload);
+
+        let aws = parse_aws(&[export_aws(&exported).0]);
+        assert_eq!(aws[0].name, "my-r2");
+        assert_eq!(aws[0].config.secret_access_key, "s");
+        assert_eq!(aws[0].config.region, "auto");
+        assert_eq!(aws[0].config.addressing_style, AddressingStyle::Path);
+        assert_eq!(aws[0].config.compatibility, exported[0].1.compatibility);
+        assert_eq!(aws[0].config.network, exported[0].1.network);
+    }
+
+    #[test]
+    fn test_export_credential_sources() {
+        let config = |credential_source| S3Config {
+            credential_source,
+            ..create_test_config()
+        };
+        let exported = vec![
+            (
+                "sts".to_string(),
+                config(CredentialSource::SessionToken {
+                    session_token: "token-value".to_string(),
+                }),
+            ),
+            (
+                "role".to_string(),
+                config(CredentialSource::AssumeRole {
+                    role_arn: "arn:aws:iam::123456789012:role/admin".to_string(),
+                    session_name: None,
+                    external_id: Some("external".to_string()),
+                    mfa_serial: None,
+                    mfa_code: None,
+                    duration_seconds: None,
+                    sts_endpoint: None,
+                }),
+            ),
+            ("env".to_string(), config(CredentialSource::Environment)),
+            ("public".to_string(), config(CredentialSource::Anonymous)),
+        ];
+
+        let (aws, skipped) = export_aws(&exported);
+        assert!(aws.contains("aws_session_token = token-value\n"));
+        assert!(aws.contains("source_profile = role-source\n"));
+        assert!(aws.contains("[profile role-source]\naws_access_key_id = test_access_key\n"));
+        assert!(!aws.contains("[profile env]"));
+        let skipped: Vec<&str> = skipped.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(skipped, ["env", "public"]);
+        let parsed = parse_aws(&[aws]);
+        assert_eq!(
+            parsed[0].config.credential_source,
+            exported[0].1.credential_source
+        );
+
+        let (rclone, skipped) = export_rclone(&exported);
+        assert!(rclone.contains("session_token = token-value\n"));
+        assert!(rclone.contains("[env]\ntype = s3\nprovider = Other\nenv_auth = true\n"));
+        assert!(rclone.contains("[public]\ntype = s3\nprovider = Other\nregion"));
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].name, "role");
+    }
+}
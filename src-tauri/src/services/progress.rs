@@ -0,0 +1,278 @@
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use aws_sdk_s3::primitives::ByteStream;
+use aws_smithy_types::body::SdkBody;
+use bytes::Bytes;
+use http_body::{Body, Frame, SizeHint};
+use tauri::{AppHandle, Emitter};
+use tracing::warn;
+
+use crate::error::AppError;
+use crate::models::transfer::{TransferDirection, TransferProgress, TransferStatus};
+use crate::utils::id::new_id;
+
+/// 传输进度事件名
+pub const TRANSFER_PROGRESS_EVENT: &str = "transfer-progress";
+
+/// 两次进度事件之间的最小间隔
+const EMIT_INTERVAL: Duration = Duration::from_millis(200);
+
+/// 生成传输 ID
+pub fn new_transfer_id(direction: TransferDirection) -> String {
+    match direction {
+        TransferDirection::Upload => new_id("upload"),
+        TransferDirection::Download => new_id("download"),
+        TransferDirection::Copy => new_id("copy"),
+        TransferDirection::Batch => new_id("batch"),
+    }
+}
+
+/// 根据已传输字节数和耗时计算速率（字节/秒）和剩余时间（秒）
+pub fn compute_rate(bytes_done: u64, total_bytes: u64, elapsed: Duration) -> (f64, Option<f64>) {
+    let secs = elapsed.as_secs_f64();
+    if secs <= 0.0 || bytes_done == 0 {
+        return (0.0, None);
+    }
+
+    let rate = bytes_done as f64 / secs;
+    let remaining = total_bytes.saturating_sub(bytes_done) as f64;
+
+    (rate, Some(remaining / rate))
+}
+
+struct ReporterInner {
+    /// 测试中为空，此时不发送事件
+    app: Option<AppHandle>,
+    transfer_id: String,
+    direction: TransferDirection,
+    bucket_name: String,
+    object_key: String,
+    total_bytes: u64,
+    bytes_done: AtomicU64,
+    started_at: Instant,
+    last_emit: Mutex<Option<Instant>>,
+}
+
+/// 向前端发送单个传输的进度事件
+///
+/// 可以在多个分片任务之间克隆共享，事件按 `EMIT_INTERVAL` 节流。
+#[derive(Clone)]
+pub struct ProgressReporter {
+    inner: Arc<ReporterInner>,
+}
+
+impl ProgressReporter {
+    pub fn new(
+        app: AppHandle,
+        transfer_id: impl Into<String>,
+        direction: TransferDirection,
+        bucket_name: impl Into<String>,
+        object_key: impl Into<String>,
+        total_bytes: u64,
+    ) -> Self {
+        Self::create(
+            Some(app),
+            transfer_id,
+            direction,
+            bucket_name,
+            object_key,
+            total_bytes,
+        )
+    }
+
+    /// 不发送事件的进度，用于测试只依赖字节计数的代码
+    #[cfg(test)]
+    pub fn detached(total_bytes: u64) -> Self {
+        Self::create(
+            None,
+            "test-transfer",
+            TransferDirection::Copy,
+            "",
+            "",
+            total_bytes,
+        )
+    }
+
+    fn create(
+        app: Option<AppHandle>,
+        transfer_id: impl Into<String>,
+        direction: TransferDirection,
+        bucket_name: impl Into<String>,
+        object_key: impl Into<String>,
+        total_bytes: u64,
+    ) -> Self {
+        Self {
+            inner: Arc::new(ReporterInner {
+                app,
+                transfer_id: transfer_id.into(),
+                direction,
+                bucket_name: bucket_name.into(),
+                object_key: object_key.into(),
+                total_bytes,
+                bytes_done: AtomicU64::new(0),
+                started_at: Instant::now(),
+                last_emit: Mutex::new(None),
+            }),
+        }
+    }
+
+    pub fn transfer_id(&self) -> &str {
+        &self.inner.transfer_id
+    }
+
+    /// 当前已完成的字节数
+    pub fn bytes_done(&self) -> u64 {
+        self.inner
+            .bytes_done
+            .load(Ordering::Relaxed)
+            .min(self.inner.total_bytes)
+    }
+
+    /// 设置已完成的字节数（例如续传时已经存在的部分）
+    pub fn set(&self, bytes_done: u64) {
+        self.inner.bytes_done.store(bytes_done, Ordering::Relaxed);
+        self.emit_throttled();
+    }
+
+    /// 累加已传输的字节数
+    pub fn advance(&self, bytes: u64) {
+        self.inner.bytes_done.fetch_add(bytes, Ordering::Relaxed);
+        self.emit_throttled();
+    }
+
+    /// 发送传输完成事件
+    pub fn complete(&self) {
+        self.inner
+            .bytes_done
+            .store(self.inner.total_bytes, Ordering::Relaxed);
+        self.emit(TransferStatus::Completed, None);
+    }
+
+    /// 发送传输失败事件
+    pub fn fail(&self, error: &AppError) {
+        self.emit(TransferStatus::Failed, Some(error.clone()));
+    }
+
+    fn emit_throttled(&self) {
+        {
+            let mut last_emit = self.inner.last_emit.lock().unwrap();
+            let now = Instant::now();
+            if let Some(last) = *last_emit {
+                if now.duration_since(last) < EMIT_INTERVAL {
+                    return;
+                }
+            }
+            *last_emit = Some(now);
+        }
+
+        self.emit(TransferStatus::Running, None);
+    }
+
+    fn emit(&self, status: TransferStatus, error: Option<AppError>) {
+        let inner = &self.inner;
+        let Some(app) = &inner.app else {
+            return;
+        };
+        // 重试时同一段数据可能被重复计数，这里不让进度超过总大小
+        let bytes_done = self.bytes_done();
+        let (bytes_per_second, eta_seconds) =
+            compute_rate(bytes_done, inner.total_bytes, inner.started_at.elapsed());
+
+        let payload = TransferProgress {
+            transfer_id: inner.transfer_id.clone(),
+            direction: inner.direction,
+            status,
+            bucket_name: inner.bucket_name.clone(),
+            object_key: inner.object_key.clone(),
+            bytes_done,
+            total_bytes: inner.total_bytes,
+            bytes_per_second,
+            eta_seconds,
+            error,
+        };
+
+        if let Err(e) = app.emit(TRANSFER_PROGRESS_EVENT, payload) {
+            warn!(
+                operation = "transfer",
+                "Failed to emit transfer progress: {}", e
+            );
+        }
+    }
+}
+
+/// 包装请求体，在数据被发送时累加上传进度
+struct ProgressBody {
+    inner: SdkBody,
+    progress: ProgressReporter,
+}
+
+impl Body for ProgressBody {
+    type Data = Bytes;
+    type Error = aws_smithy_types::body::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let poll = Pin::new(&mut self.inner).poll_frame(cx);
+        if let Poll::Ready(Some(Ok(frame))) = &poll {
+            if let Some(data) = frame.data_ref() {
+                self.progress.advance(data.len() as u64);
+            }
+        }
+        poll
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// 为上传请求体附加进度统计
+pub fn track_upload(body: ByteStream, progress: &ProgressReporter) -> ByteStream {
+    let progress = progress.clone();
+    body.map(move |inner| {
+        SdkBody::from_body_1_x(ProgressBody {
+            inner,
+            progress: progress.clone(),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_rate() {
+        let (rate, eta) = compute_rate(50, 150, Duration::from_secs(5));
+        assert_eq!(rate, 10.0);
+        assert_eq!(eta, Some(10.0));
+
+        // Nothing transferred yet: no rate and no ETA
+        let (rate, eta) = compute_rate(0, 150, Duration::from_secs(5));
+        assert_eq!(rate, 0.0);
+        assert_eq!(eta, None);
+
+        // Finished transfer has zero remaining time
+        let (_, eta) = compute_rate(150, 150, Duration::from_secs(3));
+        assert_eq!(eta, Some(0.0));
+    }
+
+    #[test]
+    fn test_new_transfer_id_is_unique() {
+        let first = new_transfer_id(TransferDirection::Upload);
+        let second = new_transfer_id(TransferDirection::Upload);
+        assert!(first.starts_with("upload-"));
+        assert_ne!(first, second);
+        assert!(new_transfer_id(TransferDirection::Download).starts_with("download-"));
+    }
+}
@@ -0,0 +1,639 @@
+use std::collections::HashMap;
+
+use crate::error::AppError;
+use crate::models::provider::{
+    BuildProviderConfigRequest, ProviderMatch, ProviderPreset, ProviderQuirk, ProviderRegion,
+};
+use crate::models::s3::{
+    AddressingStyle, ChecksumMode, Compatibility, CompatibilityDefaults, S3Config,
+};
+
+/// 不认识的 S3 兼容服务使用的设置：路径方式，只在必要时计算校验和，
+/// 避免旧版本网关（MinIO、Ceph 等）无法处理 aws-chunked 上传
+const GENERIC_COMPATIBILITY: Compatibility = Compatibility {
+    request_checksum: ChecksumMode::WhenRequired,
+    unsigned_payload: false,
+    dual_stack: false,
+    accelerate: false,
+};
+
+/// 内置的服务商预设
+pub const PRESETS: &[ProviderPreset] = &[
+    ProviderPreset {
+        id: "aws",
+        name: "Amazon S3",
+        endpoint_template: "https://s3.{region}.amazonaws.com",
+        parameters: &[],
+        regions: &[
+            ProviderRegion {
+                id: "us-east-1",
+                name: "美国东部（弗吉尼亚北部）",
+            },
+            ProviderRegion {
+                id: "us-east-2",
+                name: "美国东部（俄亥俄）",
+            },
+            ProviderRegion {
+                id: "us-west-1",
+                name: "美国西部（加利福尼亚北部）",
+            },
+            ProviderRegion {
+                id: "us-west-2",
+                name: "美国西部（俄勒冈）",
+            },
+            ProviderRegion {
+                id: "ca-central-1",
+                name: "加拿大（中部）",
+            },
+            ProviderRegion {
+                id: "sa-east-1",
+                name: "南美洲（圣保罗）",
+            },
+            ProviderRegion {
+                id: "eu-west-1",
+                name: "欧洲（爱尔兰）",
+            },
+            ProviderRegion {
+                id: "eu-west-2",
+                name: "欧洲（伦敦）",
+            },
+            ProviderRegion {
+                id: "eu-central-1",
+                name: "欧洲（法兰克福）",
+            },
+            ProviderRegion {
+                id: "ap-east-1",
+                name: "亚太地区（香港）",
+            },
+            ProviderRegion {
+                id: "ap-south-1",
+                name: "亚太地区（孟买）",
+            },
+            ProviderRegion {
+                id: "ap-northeast-1",
+                name: "亚太地区（东京）",
+            },
+            ProviderRegion {
+                id: "ap-northeast-2",
+                name: "亚太地区（首尔）",
+            },
+            ProviderRegion {
+                id: "ap-southeast-1",
+                name: "亚太地区（新加坡）",
+            },
+            ProviderRegion {
+                id: "ap-southeast-2",
+                name: "亚太地区（悉尼）",
+            },
+            ProviderRegion {
+                id: "cn-north-1",
+                name: "中国（北京）",
+            },
+            ProviderRegion {
+                id: "cn-northwest-1",
+                name: "中国（宁夏）",
+            },
+        ],
+        default_region: "us-east-1",
+        custom_region: true,
+        host_suffix: Some(".amazonaws.com"),
+        addressing_style: AddressingStyle::Virtual,
+        compatibility: Compatibility {
+            request_checksum: ChecksumMode::WhenSupported,
+            unsigned_payload: false,
+            dual_stack: false,
+            accelerate: false,
+        },
+        quirks: &[ProviderQuirk::DualStackAccelerate],
+    },
+    ProviderPreset {
+        id: "aliyun",
+        name: "阿里云 OSS",
+        endpoint_template: "https://oss-{region}.aliyuncs.com",
+        parameters: &[],
+        regions: &[
+            ProviderRegion {
+                id: "cn-hangzhou",
+                name: "华东1（杭州）",
+            },
+            ProviderRegion {
+                id: "cn-shanghai",
+                name: "华东2（上海）",
+            },
+            ProviderRegion {
+                id: "cn-qingdao",
+                name: "华北1（青岛）",
+            },
+            ProviderRegion {
+                id: "cn-beijing",
+                name: "华北2（北京）",
+            },
+            ProviderRegion {
+                id: "cn-zhangjiakou",
+                name: "华北3（张家口）",
+            },
+            ProviderRegion {
+                id: "cn-shenzhen",
+                name: "华南1（深圳）",
+            },
+            ProviderRegion {
+                id: "cn-guangzhou",
+                name: "华南3（广州）",
+            },
+            ProviderRegion {
+                id: "cn-chengdu",
+                name: "西南1（成都）",
+            },
+            ProviderRegion {
+                id: "cn-hongkong",
+                name: "中国香港",
+            },
+            ProviderRegion {
+                id: "ap-northeast-1",
+                name: "日本（东京）",
+            },
+            ProviderRegion {
+                id: "ap-southeast-1",
+                name: "新加坡",
+            },
+            ProviderRegion {
+                id: "us-west-1",
+                name: "美国（硅谷）",
+            },
+            ProviderRegion {
+                id: "us-east-1",
+                name: "美国（弗吉尼亚）",
+            },
+            ProviderRegion {
+                id: "eu-central-1",
+                name: "德国（法兰克福）",
+            },
+        ],
+        default_region: "cn-hangzhou",
+        custom_region: true,
+        host_suffix: Some(".aliyuncs.com"),
+        addressing_style: AddressingStyle::Virtual,
+        compatibility: GENERIC_COMPATIBILITY,
+        quirks: &[
+            ProviderQuirk::VirtualHostOnly,
+            ProviderQuirk::LegacyChecksum,
+        ],
+    },
+    ProviderPreset {
+        id: "tencent",
+        name: "腾讯云 COS",
+        endpoint_template: "https://cos.{region}.myqcloud.com",
+        parameters: &[],
+        regions: &[
+            ProviderRegion {
+                id: "ap-beijing",
+                name: "北京",
+            },
+            ProviderRegion {
+                id: "ap-nanjing",
+                name: "南京",
+            },
+            ProviderRegion {
+                id: "ap-shanghai",
+                name: "上海",
+            },
+            ProviderRegion {
+                id: "ap-guangzhou",
+                name: "广州",
+            },
+            ProviderRegion {
+                id: "ap-chengdu",
+                name: "成都",
+            },
+            ProviderRegion {
+                id: "ap-chongqing",
+                name: "重庆",
+            },
+            ProviderRegion {
+                id: "ap-hongkong",
+                name: "中国香港",
+            },
+            ProviderRegion {
+                id: "ap-singapore",
+                name: "新加坡",
+            },
+            ProviderRegion {
+                id: "ap-tokyo",
+                name: "东京",
+            },
+            ProviderRegion {
+                id: "na-siliconvalley",
+                name: "硅谷",
+            },
+            ProviderRegion {
+                id: "na-ashburn",
+                name: "弗吉尼亚",
+            },
+            ProviderRegion {
+                id: "eu-frankfurt",
+                name: "法兰克福",
+            },
+        ],
+        default_region: "ap-guangzhou",
+        custom_region: true,
+        host_suffix: Some(".myqcloud.com"),
+        addressing_style: AddressingStyle::Virtual,
+        compatibility: GENERIC_COMPATIBILITY,
+        quirks: &[
+            ProviderQuirk::VirtualHostOnly,
+            ProviderQuirk::BucketNameAppId,
+            ProviderQuirk::LegacyChecksum,
+        ],
+    },
+    ProviderPreset {
+        id: "r2",
+        name: "Cloudflare R2",
+        endpoint_template: "https://{account}.r2.cloudflarestorage.com",
+        parameters: &["account"],
+        regions: &[ProviderRegion {
+            id: "auto",
+            name: "自动",
+        }],
+        default_region: "auto",
+        custom_region: false,
+        host_suffix: Some(".r2.cloudflarestorage.com"),
+        addressing_style: AddressingStyle::Path,
+        compatibility: GENERIC_COMPATIBILITY,
+        quirks: &[ProviderQuirk::RegionAuto],
+    },
+    ProviderPreset {
+        id: "minio",
+        name: "MinIO",
+        endpoint_template: "{endpoint}",
+        parameters: &["endpoint"],
+        regions: &[ProviderRegion {
+            id: "us-east-1",
+            name: "us-east-1",
+        }],
+        default_region: "us-east-1",
+        custom_region: true,
+        host_suffix: None,
+        addressing_style: AddressingStyle::Path,
+        compatibility: GENERIC_COMPATIBILITY,
+        quirks: &[ProviderQuirk::LegacyChecksum],
+    },
+];
+
+/// 按 id 查找预设
+pub fn find_preset(id: &str) -> Option<&'static ProviderPreset> {
+    PRESETS.iter().find(|preset| preset.id == id)
+}
+
+/// 根据预设和参数生成配置，访问密钥留空由用户填写
+pub fn build_config(request: &BuildProviderConfigRequest) -> Result<S3Config, AppError> {
+    let preset = find_preset(&request.provider_id)
+        .ok_or_else(|| AppError::not_found(format!("Unknown provider: {}", request.provider_id)))?;
+
+    let region = request
+        .region
+        .as_deref()
+        .map(str::trim)
+        .filter(|region| !region.is_empty())
+        .unwrap_or(preset.default_region);
+    if !is_label(region) {
+        return Err(AppError::invalid_input(format!(
+            "Invalid region: {}",
+            region
+        )));
+    }
+    if !preset.custom_region && !preset.regions.iter().any(|r| r.id == region) {
+        return Err(AppError::invalid_input(format!(
+            "Region {} is not available for {}",
+            region, preset.name
+        )));
+    }
+
+    let mut endpoint = preset.endpoint_template.replace("{region}", region);
+    for name in preset.parameters {
+        let value = request
+            .parameters
+            .get(*name)
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| AppError::invalid_input(format!("Missing parameter: {}", name)))?;
+        let placeholder = format!("{{{}}}", name);
+
+        // 整个模板就是一个参数时，参数本身是完整的服务地址
+        let value = if endpoint == placeholder {
+            normalize_url(value)?
+        } else if is_label(value) {
+            value.to_string()
+        } else {
+            return Err(AppError::invalid_input(format!(
+                "Invalid {}: {}",
+                name, value
+            )));
+        };
+        endpoint = endpoint.replace(&placeholder, &value);
+    }
+    // AWS 中国区域使用独立的域名
+    if preset.id == "aws" && region.starts_with("cn-") {
+        endpoint.push_str(".cn");
+    }
+
+    Ok(S3Config {
+        endpoint,
+        region: region.to_string(),
+        access_key_id: String::new(),
+        secret_access_key: String::new(),
+        bucket: request
+            .bucket
+            .as_deref()
+            .map(str::trim)
+            .filter(|bucket| !bucket.is_empty())
+            .map(str::to_string),
+        custom_path: None,
+        addressing_style: preset.addressing_style,
+        credential_source: Default::default(),
+        compatibility: preset.compatibility.clone(),
+        network: Default::default(),
+    })
+}
+
+/// 根据服务地址识别服务商，尽量从地址中取出区域和模板参数
+pub fn detect_provider(endpoint: &str) -> Option<ProviderMatch> {
+    let host = endpoint_host(endpoint);
+    if host.is_empty() {
+        return None;
+    }
+
+    for preset in PRESETS {
+        let template_host = endpoint_host(preset.endpoint_template);
+        // 只有一个占位符的模板可以匹配任意地址，无法用于识别
+        if template_host.starts_with('{') && template_host.ends_with('}') {
+            continue;
+        }
+        if let Some(mut parameters) = match_template(&template_host, &host) {
+            return Some(ProviderMatch {
+                provider_id: preset.id.to_string(),
+                region: parameters.remove("region"),
+                parameters,
+            });
+        }
+    }
+
+    PRESETS
+        .iter()
+        .find(|preset| {
+            preset.host_suffix.is_some_and(|suffix| {
+                host.ends_with(suffix) || host.ends_with(&format!("{}.cn", suffix))
+            })
+        })
+        .map(|preset| ProviderMatch {
+            provider_id: preset.id.to_string(),
+            region: None,
+            parameters: HashMap::new(),
+        })
+}
+
+/// 取出地址中的主机名（不含协议、端口和路径），统一转为小写
+pub fn endpoint_host(endpoint: &str) -> String {
+    let without_scheme = endpoint
+        .split_once("://")
+        .map_or(endpoint, |(_, rest)| rest);
+    let authority = without_scheme.split('/').next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    let host = match host.rsplit_once(':') {
+        Some((host, port)) if port.chars().all(|c| c.is_ascii_digit()) => host,
+        _ => host,
+    };
+    host.trim_end_matches('.').to_ascii_lowercase()
+}
+
+/// 是否为 AWS S3 的官方地址
+pub fn is_aws_endpoint(endpoint: &str) -> bool {
+    let host = endpoint_host(endpoint);
+    host.ends_with(".amazonaws.com") || host.ends_with(".amazonaws.com.cn")
+}
+
+/// 根据服务地址推荐寻址方式和兼容性设置
+///
+/// 识别出服务商时使用预设中的设置，否则按普通 S3 兼容服务处理。
+pub fn compatibility_defaults(endpoint: &str) -> CompatibilityDefaults {
+    match detect_provider(endpoint).and_then(|found| find_preset(&found.provider_id)) {
+        Some(preset) => CompatibilityDefaults {
+            addressing_style: preset.addressing_style,
+            compatibility: preset.compatibility.clone(),
+        },
+        None => CompatibilityDefaults {
+            addressing_style: AddressingStyle::Path,
+            compatibility: GENERIC_COMPATIBILITY,
+        },
+    }
+}
+
+/// 检查兼容性设置是否适用于该服务地址
+pub fn validate_compatibility(
+    endpoint: &str,
+    compatibility: &Compatibility,
+) -> Result<(), AppError> {
+    if (compatibility.dual_stack || compatibility.accelerate) && !is_aws_endpoint(endpoint) {
+        return Err(AppError::invalid_input(
+            "Dual-stack and accelerated endpoints are only available on AWS S3",
+        ));
+    }
+    Ok(())
+}
+
+/// 将主机名与模板匹配，返回各占位符的取值
+///
+/// 占位符的取值一直延伸到下一段字面量，不能为空，也不能跨越 `.`。
+fn match_template(template: &str, host: &str) -> Option<HashMap<String, String>> {
+    let mut values = HashMap::new();
+    let mut pattern = template;
+    let mut rest = host;
+
+    while let Some(start) = pattern.find('{') {
+        rest = rest.strip_prefix(&pattern[..start])?;
+        let end = start + pattern[start..].find('}')?;
+        let name = &pattern[start + 1..end];
+        pattern = &pattern[end + 1..];
+
+        let literal = &pattern[..pattern.find('{').unwrap_or(pattern.len())];
+        let value_end = if literal.is_empty() {
+            rest.len()
+        } else {
+            rest.find(literal)?
+        };
+        let value = &rest[..value_end];
+        if value.is_empty() || value.contains('.') {
+            return None;
+        }
+        values.insert(name.to_string(), value.to_string());
+        rest = &rest[value_end..];
+    }
+
+    (rest == pattern).then_some(values)
+}
+
+/// 区域、账户 ID 等只能包含字母、数字和连字符
+fn is_label(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+/// 补全协议并去掉末尾的 `/`
+fn normalize_url(value: &str) -> Result<String, AppError> {
+    let url = if value.contains("://") {
+        value.to_string()
+    } else {
+        format!("https://{}", value)
+    };
+    let scheme_ok = url.starts_with("http://") || url.starts_with("https://");
+    if !scheme_ok || endpoint_host(&url).is_empty() {
+        return Err(AppError::invalid_input(format!(
+            "Invalid endpoint: {}",
+            value
+        )));
+    }
+    Ok(url.trim_end_matches('/').to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_request(provider_id: &str, region: Option<&str>) -> BuildProviderConfigRequest {
+        BuildProviderConfigRequest {
+            provider_id: provider_id.to_string(),
+            region: region.map(str::to_string),
+            parameters: HashMap::new(),
+            bucket: None,
+        }
+    }
+
+    #[test]
+    fn test_endpoint_host() {
+        assert_eq!(
+            endpoint_host("https://s3.us-west-2.amazonaws.com"),
+            "s3.us-west-2.amazonaws.com"
+        );
+        assert_eq!(endpoint_host("http://localhost:9000/"), "localhost");
+        assert_eq!(
+            endpoint_host("OSS-CN-HANGZHOU.aliyuncs.com"),
+            "oss-cn-hangzhou.aliyuncs.com"
+        );
+        assert_eq!(endpoint_host("http://[::1]:9000"), "[::1]");
+    }
+
+    #[test]
+    fn test_preset_parameters_match_templates() {
+        for preset in PRESETS {
+            let template = preset.endpoint_template;
+            for name in preset.parameters {
+                assert!(template.contains(&format!("{{{}}}", name)), "{}", preset.id);
+            }
+            assert!(
+                preset.custom_region
+                    || preset.regions.iter().any(|r| r.id == preset.default_region),
+                "{}",
+                preset.id
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_config() {
+        let config = build_config(&build_request("aliyun", Some("cn-shanghai"))).unwrap();
+        assert_eq!(config.endpoint, "https://oss-cn-shanghai.aliyuncs.com");
+        assert_eq!(config.addressing_style, AddressingStyle::Virtual);
+
+        let config = build_config(&build_request("aws", Some("cn-north-1"))).unwrap();
+        assert_eq!(config.endpoint, "https://s3.cn-north-1.amazonaws.com.cn");
+
+        let config = build_config(&build_request("tencent", None)).unwrap();
+        assert_eq!(config.region, "ap-guangzhou");
+
+        let mut request = build_request("r2", None);
+        assert!(build_config(&request).is_err(), "account is required");
+        request
+            .parameters
+            .insert("account".to_string(), "abc123".to_string());
+        let config = build_config(&request).unwrap();
+        assert_eq!(config.endpoint, "https://abc123.r2.cloudflarestorage.com");
+        assert_eq!(config.region, "auto");
+
+        request.region = Some("us-east-1".to_string());
+        assert!(build_config(&request).is_err(), "R2 only supports auto");
+
+        let mut request = build_request("minio", None);
+        request
+            .parameters
+            .insert("endpoint".to_string(), "minio.local:9000/".to_string());
+        let config = build_config(&request).unwrap();
+        assert_eq!(config.endpoint, "https://minio.local:9000");
+        assert_eq!(config.addressing_style, AddressingStyle::Path);
+
+        assert!(build_config(&build_request("aws", Some("us-east-1/evil"))).is_err());
+        assert!(build_config(&build_request("unknown", None)).is_err());
+    }
+
+    #[test]
+    fn test_detect_provider() {
+        let found = detect_provider("https://oss-cn-hangzhou.aliyuncs.com").unwrap();
+        assert_eq!(found.provider_id, "aliyun");
+        assert_eq!(found.region.as_deref(), Some("cn-hangzhou"));
+
+        let found = detect_provider("https://cos.ap-beijing.myqcloud.com/").unwrap();
+        assert_eq!(found.provider_id, "tencent");
+        assert_eq!(found.region.as_deref(), Some("ap-beijing"));
+
+        let found = detect_provider("https://abc123.r2.cloudflarestorage.com").unwrap();
+        assert_eq!(found.provider_id, "r2");
+        assert_eq!(found.region, None);
+        assert_eq!(
+            found.parameters.get("account").map(String::as_str),
+            Some("abc123")
+        );
+
+        let found = detect_provider("https://s3.amazonaws.com").unwrap();
+        assert_eq!(found.provider_id, "aws");
+        assert_eq!(found.region, None);
+
+        let found = detect_provider("s3.cn-north-1.amazonaws.com.cn").unwrap();
+        assert_eq!(found.provider_id, "aws");
+
+        assert!(detect_provider("http://localhost:9000").is_none());
+    }
+
+    #[test]
+    fn test_compatibility_defaults() {
+        let aws = compatibility_defaults("https://s3.us-east-1.amazonaws.com");
+        assert_eq!(aws.addressing_style, AddressingStyle::Virtual);
+        assert_eq!(
+            aws.compatibility.request_checksum,
+            ChecksumMode::WhenSupported
+        );
+
+        let oss = compatibility_defaults("https://oss-cn-hangzhou.aliyuncs.com");
+        assert_eq!(oss.addressing_style, AddressingStyle::Virtual);
+        assert_eq!(
+            oss.compatibility.request_checksum,
+            ChecksumMode::WhenRequired
+        );
+
+        let minio = compatibility_defaults("http://localhost:9000");
+        assert_eq!(minio.addressing_style, AddressingStyle::Path);
+        assert_eq!(
+            minio.compatibility.request_checksum,
+            ChecksumMode::WhenRequired
+        );
+        assert!(!minio.compatibility.unsigned_payload);
+    }
+
+    #[test]
+    fn test_dual_stack_requires_aws() {
+        let compatibility = Compatibility {
+            dual_stack: true,
+            ..Default::default()
+        };
+        assert!(
+            validate_compatibility("https://s3.us-east-1.amazonaws.com", &compatibility).is_ok()
+        );
+        assert!(validate_compatibility("http://localhost:9000", &compatibility).is_err());
+    }
+}
@@ -1,10 +1,25 @@
-use aws_sdk_s3::{Client, Error};
-use aws_sdk_s3::config::Region;
+use aws_runtime::auth::PayloadSigningOverride;
+use aws_sdk_s3::config::interceptors::BeforeTransmitInterceptorContextMut;
+use aws_sdk_s3::config::{
+    ConfigBag, Intercept, Region, RequestChecksumCalculation, ResponseChecksumValidation,
+    RuntimeComponents,
+};
+use aws_sdk_s3::error::BoxError;
+use aws_sdk_s3::Client;
 
-use crate::models::s3::S3Config;
+use crate::error::AppError;
+use crate::models::s3::{AddressingStyle, ChecksumMode, S3Config};
+use crate::services::credential_provider::credentials_provider;
+use crate::services::inspector::HttpRecorder;
+use crate::services::logging::register_secrets;
+use crate::services::network;
+use crate::services::provider::is_aws_endpoint;
 
 /// 创建 S3 客户端
-pub async fn create_s3_client(config: &S3Config) -> Result<Client, Error> {
+pub async fn create_s3_client(config: &S3Config) -> Result<Client, AppError> {
+    // 客户端的错误信息可能带有密钥，写入日志前替换
+    register_secrets(config);
+
     // Handle empty or default region for R2 and other S3-compatible services
     let region_str = if config.region.is_empty() || config.region == "auto" {
         "us-east-1".to_string() // Default fallback region
@@ -14,41 +29,81 @@ pub async fn create_s3_client(config: &S3Config) -> Result<Client, Error> {
 
     let region = Region::new(region_str);
 
-    let shared_config = aws_config::defaults(aws_config::BehaviorVersion::latest())
-        .region(region)
-        .endpoint_url(&config.endpoint)
-        .credentials_provider(aws_sdk_s3::config::Credentials::new(
-            &config.access_key_id,
-            &config.secret_access_key,
-            None,
-            None,
-            "custom",
-        ))
-        .load()
-        .await;
-
-    let s3_config = aws_sdk_s3::config::Builder::from(&shared_config)
-        .force_path_style(true)
-        .build();
-
-    Ok(Client::from_conf(s3_config))
+    let compatibility = &config.compatibility;
+    let mut loader =
+        aws_config::defaults(aws_config::BehaviorVersion::latest()).region(region.clone());
+
+    // 双栈和加速地址由 SDK 根据区域生成，不能与自定义地址同时使用
+    let sdk_endpoint =
+        (compatibility.dual_stack || compatibility.accelerate) && is_aws_endpoint(&config.endpoint);
+    if !sdk_endpoint {
+        loader = loader.endpoint_url(&config.endpoint);
+    }
+
+    // 匿名访问时不对请求签名
+    let loader = match credentials_provider(config, &region)? {
+        Some(provider) => loader.credentials_provider(provider),
+        None => loader.no_credentials(),
+    };
+
+    let shared_config = loader.load().await;
+
+    let (checksum_calculation, checksum_validation) = match compatibility.request_checksum {
+        ChecksumMode::WhenSupported => (
+            RequestChecksumCalculation::WhenSupported,
+            ResponseChecksumValidation::WhenSupported,
+        ),
+        ChecksumMode::WhenRequired => (
+            RequestChecksumCalculation::WhenRequired,
+            ResponseChecksumValidation::WhenRequired,
+        ),
+    };
+
+    let mut s3_config = aws_sdk_s3::config::Builder::from(&shared_config)
+        .force_path_style(config.addressing_style == AddressingStyle::Path)
+        .request_checksum_calculation(checksum_calculation)
+        .response_checksum_validation(checksum_validation)
+        .use_dual_stack(compatibility.dual_stack)
+        .accelerate(compatibility.accelerate)
+        // 请求记录默认关闭，打开后对已创建的客户端同样生效
+        .interceptor(HttpRecorder);
+    if compatibility.unsigned_payload {
+        s3_config = s3_config.interceptor(UnsignedPayload);
+    }
+    let s3_config = network::apply(s3_config, &config.network)?;
+
+    Ok(Client::from_conf(s3_config.build()))
+}
+
+/// 对所有请求使用 `UNSIGNED-PAYLOAD` 签名，跳过计算请求体的 SHA-256
+#[derive(Debug)]
+struct UnsignedPayload;
+
+impl Intercept for UnsignedPayload {
+    fn name(&self) -> &'static str {
+        "UnsignedPayload"
+    }
+
+    fn modify_before_signing(
+        &self,
+        _context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        // 流式校验和上传已经使用不签名的 aws-chunked 格式，不能覆盖
+        if cfg.load::<PayloadSigningOverride>().is_none() {
+            cfg.interceptor_state()
+                .store_put(PayloadSigningOverride::unsigned_payload());
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tokio_test;
-
-    fn create_test_config() -> S3Config {
-        S3Config {
-            endpoint: "https://test.r2.cloudflarestorage.com".to_string(),
-            region: "auto".to_string(),
-            access_key_id: "test_access_key".to_string(),
-            secret_access_key: "test_secret_key".to_string(),
-            bucket: None,
-            custom_path: None,
-        }
-    }
+    use crate::models::s3::RetryMode;
+    use crate::test_support::create_test_config;
 
     #[tokio::test]
     async fn test_create_s3_client() {
@@ -79,6 +134,32 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_create_s3_client_with_compatibility() {
+        let mut config = create_test_config();
+        config.compatibility.request_checksum = ChecksumMode::WhenRequired;
+        config.compatibility.unsigned_payload = true;
+        let result = create_s3_client(&config).await;
+        assert!(
+            result.is_ok(),
+            "S3 client creation with compatibility settings should succeed"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_s3_client_with_network_settings() {
+        let mut config = create_test_config();
+        config.network.connect_timeout_secs = Some(10);
+        config.network.retry_mode = RetryMode::Adaptive;
+        config.network.max_attempts = Some(5);
+        config.network.max_requests_per_second = Some(20);
+        let result = create_s3_client(&config).await;
+        assert!(
+            result.is_ok(),
+            "S3 client creation with network settings should succeed"
+        );
+    }
+
     #[test]
     fn test_region_handling() {
         let test_cases = vec![
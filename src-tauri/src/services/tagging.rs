@@ -0,0 +1,312 @@
+use std::collections::HashSet;
+
+use aws_sdk_s3::types::{Tag, Tagging};
+use aws_sdk_s3::Client;
+use tracing::warn;
+
+use crate::error::AppError;
+use crate::models::s3::{ObjectTag, S3Object, TagFilter, TagObjectsResult};
+use crate::services::batch::for_each_object;
+use crate::services::copy::ObjectLocation;
+use crate::services::multipart::DEFAULT_CONCURRENCY;
+use crate::services::progress::ProgressReporter;
+
+/// 每个对象最多的标签数量
+pub const MAX_TAGS: usize = 10;
+
+/// 列举时并发读取标签的请求数，GetObjectTagging 很轻，可以比传输的并发更高
+pub const TAG_FETCH_CONCURRENCY: usize = 16;
+
+/// 检查标签是否符合 S3 的限制：最多 10 个，键 1-128 个字符且不重复，值最多 256 个字符
+pub fn validate_tags(tags: &[ObjectTag]) -> Result<(), AppError> {
+    if tags.len() > MAX_TAGS {
+        return Err(AppError::invalid_input(format!(
+            "An object can have at most {} tags",
+            MAX_TAGS
+        )));
+    }
+    let mut keys = HashSet::new();
+    for tag in tags {
+        let key_length = tag.key.chars().count();
+        if key_length == 0 || key_length > 128 {
+            return Err(AppError::invalid_input(format!(
+                "Tag key must be 1 to 128 characters: '{}'",
+                tag.key
+            )));
+        }
+        if tag.value.chars().count() > 256 {
+            return Err(AppError::invalid_input(format!(
+                "Tag value for {} must be at most 256 characters",
+                tag.key
+            )));
+        }
+        if !keys.insert(tag.key.as_str()) {
+            return Err(AppError::invalid_input(format!(
+                "Duplicate tag key: {}",
+                tag.key
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// 合并标签：`changes` 覆盖同名标签或追加到末尾，再删除 `remove_keys` 中的键
+pub fn merge_tags(
+    existing: Vec<ObjectTag>,
+    changes: &[ObjectTag],
+    remove_keys: &[String],
+) -> Vec<ObjectTag> {
+    let mut merged = existing;
+    for change in changes {
+        match merged.iter_mut().find(|tag| tag.key == change.key) {
+            Some(tag) => tag.value = change.value.clone(),
+            None => merged.push(change.clone()),
+        }
+    }
+    merged.retain(|tag| !remove_keys.contains(&tag.key));
+    merged
+}
+
+/// 编码为 `x-amz-tagging` 头的格式，没有标签时返回 `None`
+pub fn encode_tags(tags: &[ObjectTag]) -> Option<String> {
+    if tags.is_empty() {
+        return None;
+    }
+    let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+    for tag in tags {
+        serializer.append_pair(&tag.key, &tag.value);
+    }
+    Some(serializer.finish())
+}
+
+impl TagFilter {
+    /// 标签中是否有匹配的键（和值）
+    pub fn matches(&self, tags: &[ObjectTag]) -> bool {
+        tags.iter().any(|tag| {
+            tag.key == self.key && self.value.as_ref().is_none_or(|value| &tag.value == value)
+        })
+    }
+}
+
+/// 读取对象的标签
+pub async fn get_tags(
+    client: &Client,
+    location: &ObjectLocation,
+    version_id: Option<&str>,
+) -> Result<Vec<ObjectTag>, AppError> {
+    let output = client
+        .get_object_tagging()
+        .bucket(&location.bucket)
+        .key(&location.key)
+        .set_version_id(version_id.map(str::to_string))
+        .send()
+        .await
+        .map_err(|e| AppError::from_sdk("Failed to read object tags", &e))?;
+
+    Ok(output
+        .tag_set()
+        .iter()
+        .map(|tag| ObjectTag {
+            key: tag.key().to_string(),
+            value: tag.value().to_string(),
+        })
+        .collect())
+}
+
+/// 替换对象的全部标签；标签为空时删除所有标签
+pub async fn put_tags(
+    client: &Client,
+    location: &ObjectLocation,
+    version_id: Option<&str>,
+    tags: &[ObjectTag],
+) -> Result<(), AppError> {
+    validate_tags(tags)?;
+    if tags.is_empty() {
+        return delete_tags(client, location, version_id).await;
+    }
+
+    let tag_set = tags
+        .iter()
+        .map(|tag| {
+            Tag::builder()
+                .key(&tag.key)
+                .value(&tag.value)
+                .build()
+                .map_err(|e| AppError::invalid_input(format!("Invalid tag '{}': {}", tag.key, e)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let tagging = Tagging::builder()
+        .set_tag_set(Some(tag_set))
+        .build()
+        .map_err(|e| AppError::invalid_input(format!("Invalid tags: {}", e)))?;
+
+    client
+        .put_object_tagging()
+        .bucket(&location.bucket)
+        .key(&location.key)
+        .set_version_id(version_id.map(str::to_string))
+        .tagging(tagging)
+        .send()
+        .await
+        .map_err(|e| AppError::from_sdk("Failed to update object tags", &e))?;
+    Ok(())
+}
+
+/// 删除对象的全部标签
+pub async fn delete_tags(
+    client: &Client,
+    location: &ObjectLocation,
+    version_id: Option<&str>,
+) -> Result<(), AppError> {
+    client
+        .delete_object_tagging()
+        .bucket(&location.bucket)
+        .key(&location.key)
+        .set_version_id(version_id.map(str::to_string))
+        .send()
+        .await
+        .map_err(|e| AppError::from_sdk("Failed to delete object tags", &e))?;
+    Ok(())
+}
+
+/// 为列举结果并发读取标签
+///
+/// 读取失败的对象（例如服务不支持标签）保留 `tags: None`，不影响列举本身。
+pub async fn attach_tags(client: &Client, bucket: &str, objects: &mut [S3Object]) {
+    let keys: Vec<String> = objects.iter().map(|object| object.key.clone()).collect();
+    let (fetched, failed) = for_each_object(&keys, TAG_FETCH_CONCURRENCY, None, |key| {
+        let client = client.clone();
+        let location = ObjectLocation::new(bucket, key);
+        async move { get_tags(&client, &location, None).await }
+    })
+    .await;
+
+    for failure in failed {
+        warn!(
+            operation = "list_objects",
+            bucket,
+            key = %failure.key,
+            "Failed to read object tags: {}",
+            failure.error
+        );
+    }
+    for (index, tags) in fetched {
+        objects[index].tags = Some(tags);
+    }
+}
+
+/// 批量修改多个对象的标签，单个对象失败不影响其他对象
+pub async fn tag_objects(
+    client: &Client,
+    bucket: &str,
+    keys: &[String],
+    changes: &TagChanges,
+    progress: &ProgressReporter,
+) -> TagObjectsResult {
+    let (updated, failed) = for_each_object(keys, DEFAULT_CONCURRENCY, Some(progress), |key| {
+        let client = client.clone();
+        let location = ObjectLocation::new(bucket, key);
+        let changes = changes.clone();
+        async move { changes.apply(&client, &location).await }
+    })
+    .await;
+
+    TagObjectsResult {
+        transfer_id: progress.transfer_id().to_string(),
+        object_count: keys.len(),
+        updated_count: updated.len(),
+        failed,
+    }
+}
+
+/// 批量标签修改：替换全部标签，或合并新标签并删除指定的键
+#[derive(Debug, Clone)]
+pub struct TagChanges {
+    pub tags: Vec<ObjectTag>,
+    pub remove_keys: Vec<String>,
+    pub replace: bool,
+}
+
+impl TagChanges {
+    /// 检查修改本身是否合法，避免每个对象都因同一个错误失败
+    pub fn validate(&self) -> Result<(), AppError> {
+        validate_tags(&self.tags)?;
+        if self.replace && !self.remove_keys.is_empty() {
+            return Err(AppError::invalid_input(
+                "removeKeys cannot be combined with replace",
+            ));
+        }
+        Ok(())
+    }
+
+    async fn apply(&self, client: &Client, location: &ObjectLocation) -> Result<(), AppError> {
+        let tags = if self.replace {
+            self.tags.clone()
+        } else {
+            let existing = get_tags(client, location, None).await?;
+            merge_tags(existing, &self.tags, &self.remove_keys)
+        };
+        put_tags(client, location, None, &tags).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag(key: &str, value: &str) -> ObjectTag {
+        ObjectTag {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_merge_tags() {
+        let existing = vec![tag("env", "dev"), tag("team", "infra"), tag("tmp", "1")];
+        let merged = merge_tags(
+            existing,
+            &[tag("env", "prod"), tag("retention", "90d")],
+            &["tmp".to_string()],
+        );
+        assert_eq!(
+            merged,
+            vec![
+                tag("env", "prod"),
+                tag("team", "infra"),
+                tag("retention", "90d")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_tags() {
+        assert!(validate_tags(&[tag("env", "prod")]).is_ok());
+        assert!(validate_tags(&[tag("", "x")]).is_err());
+        assert!(validate_tags(&[tag("env", "a"), tag("env", "b")]).is_err());
+        assert!(validate_tags(&[tag("env", &"x".repeat(257))]).is_err());
+
+        let too_many: Vec<_> = (0..11).map(|i| tag(&format!("k{}", i), "v")).collect();
+        assert!(validate_tags(&too_many).is_err());
+    }
+
+    #[test]
+    fn test_tag_filter_and_encoding() {
+        let tags = vec![tag("env", "prod"), tag("owner", "data team")];
+        let by_key = TagFilter {
+            key: "owner".to_string(),
+            value: None,
+        };
+        let by_value = TagFilter {
+            key: "env".to_string(),
+            value: Some("dev".to_string()),
+        };
+        assert!(by_key.matches(&tags));
+        assert!(!by_value.matches(&tags));
+        assert_eq!(
+            encode_tags(&tags).as_deref(),
+            Some("env=prod&owner=data+team")
+        );
+        assert_eq!(encode_tags(&[]), None);
+    }
+}
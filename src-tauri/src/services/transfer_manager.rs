@@ -0,0 +1,639 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, warn};
+
+use crate::error::{AppError, ErrorCode};
+use crate::models::s3::{DownloadFileRequest, UploadFileRequest};
+use crate::models::transfer::{
+    JobState, MultipartCheckpoint, TransferDirection, TransferJob, TransferJobInfo, TransferTask,
+};
+use crate::services::client_pool::S3ClientPool;
+use crate::services::credential_store::CredentialStore;
+use crate::services::download::{discard_partial, ObjectDownloader};
+use crate::services::multipart::MultipartUploader;
+use crate::services::profile_store::ProfileStore;
+use crate::services::progress::{new_transfer_id, ProgressReporter};
+use crate::services::upload::{upload_path, UploadControl};
+use crate::utils::storage::{read_json, write_json_atomic};
+
+/// 任务状态变化事件名
+pub const TRANSFER_UPDATED_EVENT: &str = "transfer-updated";
+
+/// 默认同时运行的任务数
+pub const DEFAULT_MAX_CONCURRENT: usize = 3;
+
+/// 队列持久化文件名
+const QUEUE_FILE: &str = "transfers.json";
+
+/// 日志中的操作名
+const OPERATION: &str = "transfer";
+
+/// 持久化到磁盘的队列内容
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistedQueue {
+    #[serde(rename = "maxConcurrent")]
+    max_concurrent: usize,
+    jobs: Vec<TransferJob>,
+}
+
+struct ManagerState {
+    jobs: Vec<TransferJob>,
+    max_concurrent: usize,
+    tokens: HashMap<String, CancellationToken>,
+}
+
+/// 后台传输队列
+///
+/// 管理排队、运行、暂停、失败和已完成的上传下载任务，限制同时运行的任务数，
+/// 并把队列（包括分片上传断点）持久化到应用数据目录，重启后未完成的任务会继续执行。
+pub struct TransferManager {
+    app: AppHandle,
+    store_path: Option<PathBuf>,
+    state: Mutex<ManagerState>,
+}
+
+/// 重启后恢复队列：中断的运行中任务重新排队
+pub fn restore_jobs(jobs: Vec<TransferJob>) -> Vec<TransferJob> {
+    jobs.into_iter()
+        .map(|mut job| {
+            if job.state == JobState::Running {
+                job.state = JobState::Queued;
+            }
+            job
+        })
+        .collect()
+}
+
+impl TransferManager {
+    /// 从应用数据目录加载队列
+    pub fn load(app: AppHandle) -> Arc<Self> {
+        let store_path = match app.path().app_data_dir() {
+            Ok(dir) => Some(dir.join(QUEUE_FILE)),
+            Err(e) => {
+                warn!(
+                    operation = OPERATION,
+                    "Failed to resolve app data dir, transfer queue will not persist: {}", e
+                );
+                None
+            }
+        };
+
+        let persisted = match store_path.as_deref().map(read_json::<PersistedQueue>) {
+            Some(Ok(queue)) => queue,
+            Some(Err(e)) => {
+                error!(
+                    operation = OPERATION,
+                    "Failed to load transfer queue: {}", e
+                );
+                None
+            }
+            None => None,
+        };
+
+        let (jobs, max_concurrent) = match persisted {
+            Some(queue) => (restore_jobs(queue.jobs), queue.max_concurrent.max(1)),
+            None => (Vec::new(), DEFAULT_MAX_CONCURRENT),
+        };
+
+        Arc::new(Self {
+            app,
+            store_path,
+            state: Mutex::new(ManagerState {
+                jobs,
+                max_concurrent,
+                tokens: HashMap::new(),
+            }),
+        })
+    }
+
+    /// 添加任务到队列，返回任务 ID
+    pub fn enqueue(self: &Arc<Self>, task: TransferTask) -> String {
+        let direction = match task {
+            TransferTask::Upload { .. } => TransferDirection::Upload,
+            TransferTask::Download { .. } => TransferDirection::Download,
+        };
+        let job = TransferJob {
+            id: new_transfer_id(direction),
+            state: JobState::Queued,
+            task,
+            bytes_done: 0,
+            total_bytes: 0,
+            error: None,
+        };
+        let id = job.id.clone();
+
+        {
+            let mut state = self.state.lock().unwrap();
+            self.notify(&job);
+            state.jobs.push(job);
+            self.persist(&state);
+        }
+
+        self.schedule();
+        id
+    }
+
+    /// 列出所有任务
+    pub fn list(&self) -> Vec<TransferJobInfo> {
+        let state = self.state.lock().unwrap();
+        state.jobs.iter().map(|job| job.info()).collect()
+    }
+
+    /// 暂停任务，运行中的任务会保留已传输的部分
+    pub fn pause(&self, id: &str) -> Result<(), AppError> {
+        let mut state = self.state.lock().unwrap();
+        let job = find_job(&mut state.jobs, id)?;
+
+        match job.state {
+            JobState::Queued | JobState::Running => job.state = JobState::Paused,
+            _ => {
+                return Err(AppError::conflict(format!(
+                    "Transfer {} cannot be paused",
+                    id
+                )))
+            }
+        }
+        self.notify(job);
+
+        if let Some(token) = state.tokens.get(id) {
+            token.cancel();
+        }
+        self.persist(&state);
+        Ok(())
+    }
+
+    /// 继续暂停或失败的任务
+    pub fn resume(self: &Arc<Self>, id: &str) -> Result<(), AppError> {
+        {
+            let mut state = self.state.lock().unwrap();
+            let job = find_job(&mut state.jobs, id)?;
+
+            match job.state {
+                JobState::Paused | JobState::Failed => {
+                    job.state = JobState::Queued;
+                    job.error = None;
+                }
+                _ => {
+                    return Err(AppError::conflict(format!(
+                        "Transfer {} cannot be resumed",
+                        id
+                    )))
+                }
+            }
+            self.notify(job);
+            self.persist(&state);
+        }
+
+        self.schedule();
+        Ok(())
+    }
+
+    /// 取消任务并清理未完成的分片上传或临时文件
+    pub fn cancel(self: &Arc<Self>, id: &str) -> Result<(), AppError> {
+        let mut state = self.state.lock().unwrap();
+        let index = state
+            .jobs
+            .iter()
+            .position(|job| job.id == id)
+            .ok_or_else(|| not_found(id))?;
+        let job = state.jobs.remove(index);
+        self.persist(&state);
+
+        match state.tokens.get(id) {
+            // 运行中的任务在退出时清理
+            Some(token) => token.cancel(),
+            None => {
+                if job.state != JobState::Completed {
+                    tauri::async_runtime::spawn(cleanup(self.app.clone(), job.task));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 设置同时运行的任务数
+    pub fn set_max_concurrent(self: &Arc<Self>, max_concurrent: usize) {
+        {
+            let mut state = self.state.lock().unwrap();
+            state.max_concurrent = max_concurrent.max(1);
+            self.persist(&state);
+        }
+
+        self.schedule();
+    }
+
+    /// 移除已完成的任务
+    pub fn clear_completed(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.jobs.retain(|job| job.state != JobState::Completed);
+        self.persist(&state);
+    }
+
+    /// 在并发限制内启动排队中的任务
+    ///
+    /// 凭证存储锁定时任务保持排队，解锁后再调用一次。
+    pub fn schedule(self: &Arc<Self>) {
+        if self.app.state::<Arc<CredentialStore>>().status().locked {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let ManagerState {
+            jobs,
+            max_concurrent,
+            tokens,
+        } = &mut *state;
+
+        while tokens.len() < *max_concurrent {
+            // 暂停后立即继续的任务要等上一次运行退出后再启动
+            let Some(job) = jobs
+                .iter_mut()
+                .find(|job| job.state == JobState::Queued && !tokens.contains_key(&job.id))
+            else {
+                break;
+            };
+
+            job.state = JobState::Running;
+            self.notify(job);
+            let job = job.clone();
+
+            let token = CancellationToken::new();
+            tokens.insert(job.id.clone(), token.clone());
+            tauri::async_runtime::spawn(self.clone().run_job(job, token));
+        }
+
+        self.persist(&state);
+    }
+
+    async fn run_job(self: Arc<Self>, job: TransferJob, token: CancellationToken) {
+        let mut reporter = None;
+        let latest_checkpoint = Arc::new(Mutex::new(match &job.task {
+            TransferTask::Upload { checkpoint, .. } => checkpoint.clone(),
+            TransferTask::Download { .. } => None,
+        }));
+
+        let result = match &job.task {
+            TransferTask::Upload {
+                request,
+                checkpoint,
+            } => {
+                self.run_upload(
+                    &job.id,
+                    request,
+                    checkpoint.clone(),
+                    &latest_checkpoint,
+                    &token,
+                    &mut reporter,
+                )
+                .await
+            }
+            TransferTask::Download { request, dest_path } => {
+                self.run_download(&job.id, request, dest_path, &token, &mut reporter)
+                    .await
+            }
+        };
+
+        let checkpoint = latest_checkpoint.lock().unwrap().clone();
+        self.finish(job, result, reporter, checkpoint).await;
+        self.schedule();
+    }
+
+    async fn run_upload(
+        self: &Arc<Self>,
+        id: &str,
+        request: &UploadFileRequest,
+        checkpoint: Option<MultipartCheckpoint>,
+        latest_checkpoint: &Arc<Mutex<Option<MultipartCheckpoint>>>,
+        token: &CancellationToken,
+        reporter: &mut Option<ProgressReporter>,
+    ) -> Result<(), AppError> {
+        let file_size = std::fs::metadata(&request.file_path)
+            .map_err(|e| AppError::io("Failed to read file", &e))?
+            .len();
+        self.set_total(id, file_size);
+
+        let client = resolve_client(&self.app, request.profile_id.as_deref()).await?;
+
+        let progress = ProgressReporter::new(
+            self.app.clone(),
+            id,
+            TransferDirection::Upload,
+            &request.bucket_name,
+            &request.object_key,
+            file_size,
+        );
+        *reporter = Some(progress.clone());
+
+        let manager = self.clone();
+        let job_id = id.to_string();
+        let latest = latest_checkpoint.clone();
+        let control = UploadControl {
+            cancel: Some(token.clone()),
+            checkpoint,
+            on_checkpoint: Some(Arc::new(move |checkpoint: &MultipartCheckpoint| {
+                *latest.lock().unwrap() = Some(checkpoint.clone());
+                manager.save_checkpoint(&job_id, checkpoint);
+            })),
+        };
+
+        upload_path(client, request, file_size, &progress, control).await
+    }
+
+    async fn run_download(
+        &self,
+        id: &str,
+        request: &DownloadFileRequest,
+        dest_path: &str,
+        token: &CancellationToken,
+        reporter: &mut Option<ProgressReporter>,
+    ) -> Result<(), AppError> {
+        let client = resolve_client(&self.app, request.profile_id.as_deref()).await?;
+
+        let downloader = ObjectDownloader::new(client, &request.bucket_name, &request.object_key)
+            .with_cancellation(token.clone());
+        let remote = downloader.head().await?;
+        self.set_total(id, remote.size);
+
+        let progress = ProgressReporter::new(
+            self.app.clone(),
+            id,
+            TransferDirection::Download,
+            &request.bucket_name,
+            &request.object_key,
+            remote.size,
+        );
+        *reporter = Some(progress.clone());
+
+        downloader
+            .download(&remote, Path::new(dest_path), &progress)
+            .await
+    }
+
+    /// 根据任务结果和暂停、取消请求更新任务状态
+    async fn finish(
+        &self,
+        job: TransferJob,
+        result: Result<(), AppError>,
+        reporter: Option<ProgressReporter>,
+        checkpoint: Option<MultipartCheckpoint>,
+    ) {
+        let bytes_done = reporter
+            .as_ref()
+            .map(|progress| progress.bytes_done())
+            .unwrap_or(job.bytes_done);
+
+        let (cancelled, abandoned) = {
+            let mut state = self.state.lock().unwrap();
+            state.tokens.remove(&job.id);
+
+            let outcome = match state.jobs.iter_mut().find(|j| j.id == job.id) {
+                // 任务已被取消并移出队列
+                None => (true, None),
+                Some(current) => {
+                    match &result {
+                        Ok(()) => {
+                            current.state = JobState::Completed;
+                            current.bytes_done = current.total_bytes;
+                            current.error = None;
+                            if let Some(progress) = &reporter {
+                                progress.complete();
+                            }
+                        }
+                        // 暂停（或暂停后已重新排队）的任务保留断点，继续时从断点开始
+                        Err(_) if matches!(current.state, JobState::Paused | JobState::Queued) => {
+                            current.bytes_done = bytes_done;
+                        }
+                        // 凭证存储被锁定，保留断点重新排队，解锁后继续
+                        Err(e) if e.code == ErrorCode::Locked => {
+                            current.state = JobState::Queued;
+                            current.bytes_done = bytes_done;
+                        }
+                        Err(e) => {
+                            current.state = JobState::Failed;
+                            current.bytes_done = bytes_done;
+                            current.error = Some(e.clone());
+                            if let Some(progress) = &reporter {
+                                progress.fail(e);
+                            }
+                        }
+                    }
+                    // 分片上传已完成或已放弃时，断点不再有效；失败任务的分片要在服务端清理
+                    let failed = current.state == JobState::Failed;
+                    let mut abandoned = None;
+                    if failed || current.state == JobState::Completed {
+                        if let TransferTask::Upload {
+                            request,
+                            checkpoint,
+                        } = &mut current.task
+                        {
+                            if let Some(checkpoint) = checkpoint.take().filter(|_| failed) {
+                                abandoned = Some(TransferTask::Upload {
+                                    request: request.clone(),
+                                    checkpoint: Some(checkpoint),
+                                });
+                            }
+                        }
+                    }
+                    self.notify(current);
+                    (false, abandoned)
+                }
+            };
+
+            self.persist(&state);
+            outcome
+        };
+
+        if let Some(task) = abandoned {
+            cleanup(self.app.clone(), task).await;
+        }
+
+        if cancelled {
+            let mut task = job.task;
+            if let TransferTask::Upload {
+                checkpoint: saved, ..
+            } = &mut task
+            {
+                *saved = checkpoint;
+            }
+            cleanup(self.app.clone(), task).await;
+        }
+    }
+
+    fn save_checkpoint(&self, id: &str, checkpoint: &MultipartCheckpoint) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(job) = state.jobs.iter_mut().find(|job| job.id == id) {
+            if let TransferTask::Upload {
+                checkpoint: saved, ..
+            } = &mut job.task
+            {
+                *saved = Some(checkpoint.clone());
+            }
+        }
+        self.persist(&state);
+    }
+
+    fn set_total(&self, id: &str, total_bytes: u64) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(job) = state.jobs.iter_mut().find(|job| job.id == id) {
+            job.total_bytes = total_bytes;
+        }
+    }
+
+    fn notify(&self, job: &TransferJob) {
+        if let Err(e) = self.app.emit(TRANSFER_UPDATED_EVENT, job.info()) {
+            warn!(
+                operation = OPERATION,
+                "Failed to emit transfer update: {}", e
+            );
+        }
+    }
+
+    /// 持久化队列到应用数据目录
+    fn persist(&self, state: &ManagerState) {
+        let Some(path) = &self.store_path else {
+            return;
+        };
+
+        let queue = PersistedQueue {
+            max_concurrent: state.max_concurrent,
+            jobs: state.jobs.clone(),
+        };
+
+        if let Err(e) = write_json_atomic(path, &queue) {
+            error!(
+                operation = OPERATION,
+                "Failed to persist transfer queue: {}", e
+            );
+        }
+    }
+}
+
+fn find_job<'a>(jobs: &'a mut [TransferJob], id: &str) -> Result<&'a mut TransferJob, AppError> {
+    jobs.iter_mut()
+        .find(|job| job.id == id)
+        .ok_or_else(|| not_found(id))
+}
+
+fn not_found(id: &str) -> AppError {
+    AppError::not_found(format!("Transfer {} not found", id))
+}
+
+/// 清理被取消或失败任务留下的分片或临时文件
+async fn cleanup(app: AppHandle, task: TransferTask) {
+    match task {
+        TransferTask::Upload {
+            request,
+            checkpoint: Some(checkpoint),
+        } => match resolve_client(&app, request.profile_id.as_deref()).await {
+            Ok(client) => {
+                MultipartUploader::new(
+                    client,
+                    &request.bucket_name,
+                    &request.object_key,
+                    Default::default(),
+                )
+                .abort_upload(&checkpoint.upload_id)
+                .await;
+            }
+            Err(e) => warn!(
+                operation = OPERATION,
+                "Failed to create S3 client for cleanup: {}", e
+            ),
+        },
+        TransferTask::Upload { .. } => {}
+        TransferTask::Download { dest_path, .. } => {
+            if let Err(e) = discard_partial(Path::new(&dest_path)).await {
+                warn!(operation = OPERATION, "{}", e);
+            }
+        }
+    }
+}
+
+/// 按任务记录的配置取得 S3 客户端
+async fn resolve_client(
+    app: &AppHandle,
+    profile_id: Option<&str>,
+) -> Result<aws_sdk_s3::Client, AppError> {
+    let config = app.state::<ProfileStore>().resolve(profile_id)?;
+    app.state::<S3ClientPool>().get(&config).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_job(id: &str, state: JobState) -> TransferJob {
+        TransferJob {
+            id: id.to_string(),
+            state,
+            task: TransferTask::Upload {
+                request: UploadFileRequest {
+                    profile_id: Some("profile-1".to_string()),
+                    bucket_name: "test-bucket".to_string(),
+                    object_key: "videos/backup.mp4".to_string(),
+                    file_path: "/tmp/backup.mp4".to_string(),
+                    content_type: None,
+                    multipart_threshold: None,
+                    part_size: None,
+                    max_concurrency: None,
+                    transfer_id: None,
+                },
+                checkpoint: Some(MultipartCheckpoint {
+                    upload_id: "upload-1".to_string(),
+                    part_size: 16 * 1024 * 1024,
+                    file_size: 1024,
+                    modified: Some(1_700_000_000_000),
+                    parts: vec![],
+                }),
+            },
+            bytes_done: 0,
+            total_bytes: 1024,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_restore_jobs_requeues_running() {
+        let jobs = restore_jobs(vec![
+            create_test_job("a", JobState::Running),
+            create_test_job("b", JobState::Paused),
+            create_test_job("c", JobState::Completed),
+        ]);
+
+        assert_eq!(jobs[0].state, JobState::Queued);
+        assert_eq!(jobs[1].state, JobState::Paused);
+        assert_eq!(jobs[2].state, JobState::Completed);
+    }
+
+    #[test]
+    fn test_persisted_queue_roundtrip() {
+        let queue = PersistedQueue {
+            max_concurrent: 2,
+            jobs: vec![create_test_job("a", JobState::Paused)],
+        };
+
+        let json = serde_json::to_string(&queue).unwrap();
+        let restored: PersistedQueue = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.max_concurrent, 2);
+        let job = &restored.jobs[0];
+        assert_eq!(job.state, JobState::Paused);
+        match &job.task {
+            TransferTask::Upload { checkpoint, .. } => {
+                assert_eq!(checkpoint.as_ref().unwrap().upload_id, "upload-1");
+            }
+            TransferTask::Download { .. } => panic!("expected upload task"),
+        }
+
+        let info = job.info();
+        assert_eq!(info.direction, TransferDirection::Upload);
+        assert_eq!(info.object_key, "videos/backup.mp4");
+        assert_eq!(info.local_path, "/tmp/backup.mp4");
+    }
+}
@@ -0,0 +1,96 @@
+use std::path::Path;
+
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::Client;
+use tokio_util::sync::CancellationToken;
+
+use crate::error::{AppError, ErrorCode};
+use crate::models::s3::UploadFileRequest;
+use crate::models::transfer::MultipartCheckpoint;
+use crate::services::multipart::{
+    CheckpointCallback, MultipartOptions, MultipartUploader, DEFAULT_CONCURRENCY,
+    DEFAULT_MULTIPART_THRESHOLD, DEFAULT_PART_SIZE, MAX_SINGLE_PUT_SIZE,
+};
+use crate::services::progress::{track_upload, ProgressReporter};
+
+/// 上传过程的控制参数，供传输队列暂停和续传使用
+#[derive(Default)]
+pub struct UploadControl {
+    pub cancel: Option<CancellationToken>,
+    pub checkpoint: Option<MultipartCheckpoint>,
+    pub on_checkpoint: Option<CheckpointCallback>,
+}
+
+/// 文件是否需要使用分片上传
+pub fn uses_multipart(request: &UploadFileRequest, file_size: u64) -> bool {
+    // 单次 PUT 最大 5 GiB，阈值不能超过这个限制
+    let threshold = request
+        .multipart_threshold
+        .unwrap_or(DEFAULT_MULTIPART_THRESHOLD)
+        .min(MAX_SINGLE_PUT_SIZE);
+
+    file_size > threshold
+}
+
+/// 根据文件大小选择单次上传或分片上传
+pub async fn upload_path(
+    client: Client,
+    request: &UploadFileRequest,
+    file_size: u64,
+    progress: &ProgressReporter,
+    control: UploadControl,
+) -> Result<(), AppError> {
+    let path = Path::new(&request.file_path);
+
+    if uses_multipart(request, file_size) {
+        let options = MultipartOptions {
+            part_size: request.part_size.unwrap_or(DEFAULT_PART_SIZE),
+            concurrency: request.max_concurrency.unwrap_or(DEFAULT_CONCURRENCY),
+        };
+
+        let mut uploader =
+            MultipartUploader::new(client, &request.bucket_name, &request.object_key, options)
+                .with_progress(progress.clone());
+        if let Some(cancel) = control.cancel {
+            uploader = uploader.with_cancellation(cancel);
+        }
+        if let Some(on_checkpoint) = control.on_checkpoint {
+            uploader = uploader.with_checkpoint(control.checkpoint, on_checkpoint);
+        }
+
+        uploader
+            .upload_file(path, request.content_type.as_deref())
+            .await?;
+
+        return Ok(());
+    }
+
+    // 以流的方式读取文件内容
+    let body = ByteStream::from_path(path)
+        .await
+        .map_err(|e| AppError::new(ErrorCode::Io, format!("Failed to read file: {}", e)))?;
+
+    // 构建上传请求
+    let mut put_request = client
+        .put_object()
+        .bucket(&request.bucket_name)
+        .key(&request.object_key)
+        .content_length(file_size as i64)
+        .body(track_upload(body, progress));
+
+    // 如果指定了 content_type，则设置
+    if let Some(content_type) = &request.content_type {
+        put_request = put_request.content_type(content_type);
+    }
+
+    // 执行上传
+    let cancel = control.cancel.unwrap_or_default();
+    tokio::select! {
+        result = put_request.send() => {
+            result.map_err(|e| AppError::from_sdk("Failed to upload object", &e))?;
+        }
+        _ = cancel.cancelled() => return Err(AppError::interrupted()),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,228 @@
+use aws_sdk_s3::operation::list_object_versions::ListObjectVersionsOutput;
+use aws_sdk_s3::types::{BucketVersioningStatus, MfaDeleteStatus, VersioningConfiguration};
+use aws_sdk_s3::Client;
+
+use crate::error::AppError;
+use crate::models::s3::{BucketVersioning, ObjectMetadata, ObjectVersion, VersioningStatus};
+use crate::services::copy::{self, ObjectLocation};
+use crate::services::metadata;
+
+/// 把版本和删除标记合并为一个列表，同一个键按修改时间从新到旧排列
+pub fn collect_versions(output: &ListObjectVersionsOutput) -> Vec<ObjectVersion> {
+    let mut versions: Vec<ObjectVersion> = output
+        .versions()
+        .iter()
+        .map(|version| ObjectVersion {
+            key: version.key().unwrap_or_default().to_string(),
+            version_id: version.version_id().map(str::to_string),
+            is_latest: version.is_latest().unwrap_or(false),
+            is_delete_marker: false,
+            size: version.size().unwrap_or_default().max(0) as u64,
+            last_modified: version.last_modified().map(|date| date.to_string()),
+            etag: version.e_tag().map(str::to_string),
+            storage_class: version
+                .storage_class()
+                .map(|class| class.as_str().to_string()),
+        })
+        .chain(output.delete_markers().iter().map(|marker| ObjectVersion {
+            key: marker.key().unwrap_or_default().to_string(),
+            version_id: marker.version_id().map(str::to_string),
+            is_latest: marker.is_latest().unwrap_or(false),
+            is_delete_marker: true,
+            size: 0,
+            last_modified: marker.last_modified().map(|date| date.to_string()),
+            etag: None,
+            storage_class: None,
+        }))
+        .collect();
+    sort_versions(&mut versions);
+    versions
+}
+
+/// 按键排序，同一个键最新的版本在前
+pub fn sort_versions(versions: &mut [ObjectVersion]) {
+    versions.sort_by(|a, b| {
+        a.key
+            .cmp(&b.key)
+            .then(b.is_latest.cmp(&a.is_latest))
+            .then(b.last_modified.cmp(&a.last_modified))
+    });
+}
+
+/// 把历史版本复制为最新版本，返回新版本的元数据
+pub async fn restore_version(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+    version_id: &str,
+) -> Result<ObjectMetadata, AppError> {
+    let source = ObjectLocation::new(bucket, key).with_version(version_id);
+    let dest = ObjectLocation::new(bucket, key);
+    let size = copy::object_size(client, &source).await?;
+    copy::copy_object(client, &source, &dest, size, None).await?;
+    metadata::head_metadata(client, &dest, None).await
+}
+
+/// 最新版本为删除标记时返回它的版本 ID
+pub async fn latest_delete_marker(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+) -> Result<Option<String>, AppError> {
+    let mut key_marker = None;
+    let mut version_id_marker = None;
+    loop {
+        let output = client
+            .list_object_versions()
+            .bucket(bucket)
+            .prefix(key)
+            .set_key_marker(key_marker)
+            .set_version_id_marker(version_id_marker)
+            .send()
+            .await
+            .map_err(|e| AppError::from_sdk("Failed to list object versions", &e))?;
+
+        if let Some(marker) = output
+            .delete_markers()
+            .iter()
+            .find(|marker| marker.key() == Some(key) && marker.is_latest() == Some(true))
+        {
+            return Ok(marker.version_id().map(str::to_string));
+        }
+        if output
+            .versions()
+            .iter()
+            .any(|version| version.key() == Some(key) && version.is_latest() == Some(true))
+        {
+            return Ok(None);
+        }
+
+        // 结果按键排序，列举已经越过这个键时不必继续
+        if !output.is_truncated().unwrap_or(false) || output.next_key_marker() != Some(key) {
+            return Ok(None);
+        }
+        key_marker = output.next_key_marker().map(str::to_string);
+        version_id_marker = output.next_version_id_marker().map(str::to_string);
+    }
+}
+
+/// 删除最新的删除标记，让上一个版本重新成为当前版本
+pub async fn undelete(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+) -> Result<ObjectMetadata, AppError> {
+    let Some(version_id) = latest_delete_marker(client, bucket, key).await? else {
+        return Err(AppError::not_found(format!(
+            "Object {} has no delete marker to remove",
+            key
+        )));
+    };
+
+    client
+        .delete_object()
+        .bucket(bucket)
+        .key(key)
+        .version_id(version_id)
+        .send()
+        .await
+        .map_err(|e| AppError::from_sdk("Failed to remove delete marker", &e))?;
+    metadata::head_metadata(client, &ObjectLocation::new(bucket, key), None).await
+}
+
+/// 读取存储桶的版本控制状态
+pub async fn get_versioning(client: &Client, bucket: &str) -> Result<BucketVersioning, AppError> {
+    let output = client
+        .get_bucket_versioning()
+        .bucket(bucket)
+        .send()
+        .await
+        .map_err(|e| AppError::from_sdk("Failed to get bucket versioning", &e))?;
+
+    Ok(BucketVersioning {
+        status: versioning_status(output.status()),
+        mfa_delete: output.mfa_delete() == Some(&MfaDeleteStatus::Enabled),
+    })
+}
+
+/// 开启或暂停版本控制
+pub async fn set_versioning(client: &Client, bucket: &str, enabled: bool) -> Result<(), AppError> {
+    let status = if enabled {
+        BucketVersioningStatus::Enabled
+    } else {
+        BucketVersioningStatus::Suspended
+    };
+
+    client
+        .put_bucket_versioning()
+        .bucket(bucket)
+        .versioning_configuration(VersioningConfiguration::builder().status(status).build())
+        .send()
+        .await
+        .map_err(|e| AppError::from_sdk("Failed to update bucket versioning", &e))?;
+    Ok(())
+}
+
+pub fn versioning_status(status: Option<&BucketVersioningStatus>) -> VersioningStatus {
+    match status {
+        Some(BucketVersioningStatus::Enabled) => VersioningStatus::Enabled,
+        Some(BucketVersioningStatus::Suspended) => VersioningStatus::Suspended,
+        _ => VersioningStatus::Disabled,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(key: &str, last_modified: &str, is_latest: bool) -> ObjectVersion {
+        ObjectVersion {
+            key: key.to_string(),
+            version_id: Some(format!("{}@{}", key, last_modified)),
+            is_latest,
+            is_delete_marker: false,
+            size: 0,
+            last_modified: Some(last_modified.to_string()),
+            etag: None,
+            storage_class: None,
+        }
+    }
+
+    #[test]
+    fn test_sort_versions() {
+        let mut versions = vec![
+            version("b.txt", "2024-01-01T00:00:00Z", true),
+            version("a.txt", "2024-01-01T00:00:00Z", false),
+            version("a.txt", "2024-03-01T00:00:00Z", true),
+            version("a.txt", "2024-02-01T00:00:00Z", false),
+        ];
+        sort_versions(&mut versions);
+
+        let order: Vec<_> = versions
+            .iter()
+            .map(|version| version.version_id.as_deref().unwrap())
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                "a.txt@2024-03-01T00:00:00Z",
+                "a.txt@2024-02-01T00:00:00Z",
+                "a.txt@2024-01-01T00:00:00Z",
+                "b.txt@2024-01-01T00:00:00Z",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_versioning_status() {
+        assert_eq!(
+            versioning_status(Some(&BucketVersioningStatus::Enabled)),
+            VersioningStatus::Enabled
+        );
+        assert_eq!(
+            versioning_status(Some(&BucketVersioningStatus::Suspended)),
+            VersioningStatus::Suspended
+        );
+        assert_eq!(versioning_status(None), VersioningStatus::Disabled);
+    }
+}
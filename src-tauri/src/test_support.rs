@@ -0,0 +1,733 @@
+//! 各模块测试共用的辅助函数，以及一个进程内的 S3 模拟服务
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use aws_sdk_s3::Client;
+use base64::Engine;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::models::s3::S3Config;
+use crate::services::s3_client::create_s3_client;
+
+const LAST_MODIFIED: &str = "2024-01-01T00:00:00.000Z";
+const LAST_MODIFIED_HTTP: &str = "Mon, 01 Jan 2024 00:00:00 GMT";
+
+/// 测试用的连接配置，不会真正连接
+pub fn create_test_config() -> S3Config {
+    S3Config {
+        endpoint: "https://test.r2.cloudflarestorage.com".to_string(),
+        region: "auto".to_string(),
+        access_key_id: "test_access_key".to_string(),
+        secret_access_key: "test_secret_key".to_string(),
+        bucket: None,
+        custom_path: None,
+        addressing_style: Default::default(),
+        credential_source: Default::default(),
+        compatibility: Default::default(),
+        network: Default::default(),
+    }
+}
+
+/// 模拟服务收到的请求，`key` 为空表示存储桶级别的请求
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    pub method: String,
+    pub bucket: String,
+    pub key: Option<String>,
+    pub query: Vec<(String, String)>,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl MockRequest {
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// 请求是否为列举对象
+    pub fn is_list(&self) -> bool {
+        self.method == "GET" && self.key.is_none() && self.param("list-type") == Some("2")
+    }
+
+    /// 请求是否要求服务端保存 CRC32 校验值
+    fn wants_crc32(&self) -> bool {
+        self.header("x-amz-checksum-crc32").is_some()
+            || self
+                .header("x-amz-trailer")
+                .is_some_and(|trailer| trailer.eq_ignore_ascii_case("x-amz-checksum-crc32"))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MockObject {
+    body: Vec<u8>,
+    etag: String,
+    crc32: Option<String>,
+}
+
+impl MockObject {
+    fn new(body: Vec<u8>, crc32: bool) -> Self {
+        let checksum = crc32_checksum(&body);
+        Self {
+            etag: format!("\"{:08x}\"", checksum),
+            crc32: crc32.then(|| encode_crc32(checksum)),
+            body,
+        }
+    }
+}
+
+/// 进行中的分片上传
+#[derive(Debug)]
+struct MockUpload {
+    bucket: String,
+    key: String,
+    algorithm: Option<String>,
+    checksum_type: Option<String>,
+    parts: BTreeMap<i32, Vec<u8>>,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    objects: BTreeMap<(String, String), MockObject>,
+    uploads: BTreeMap<String, MockUpload>,
+    requests: Vec<MockRequest>,
+    page_size: usize,
+    next_upload_id: u64,
+    denied_methods: Vec<String>,
+    protected: Vec<(String, String)>,
+}
+
+struct MockResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl MockResponse {
+    fn empty(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    fn xml(body: String) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "application/xml".to_string())],
+            body: format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>{}", body).into_bytes(),
+        }
+    }
+
+    fn error(status: u16, code: &str) -> Self {
+        Self {
+            status,
+            ..Self::xml(format!(
+                "<Error><Code>{code}</Code><Message>{code}</Message></Error>"
+            ))
+        }
+    }
+
+    fn header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+}
+
+/// 在本机随机端口上运行的 S3 模拟服务
+///
+/// 支持路径风格的列举、读写、复制、批量删除和分片上传，对象保存在内存中。
+/// 每页最多返回的键数可以调小，用来测试分页。
+pub struct MockS3 {
+    endpoint: String,
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockS3 {
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(Mutex::new(MockState {
+            page_size: 1000,
+            ..Default::default()
+        }));
+
+        let shared = state.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve(stream, shared.clone()));
+            }
+        });
+        Self { endpoint, state }
+    }
+
+    /// 指向模拟服务的连接配置
+    pub fn config(&self) -> S3Config {
+        S3Config {
+            endpoint: self.endpoint.clone(),
+            region: "us-east-1".to_string(),
+            ..create_test_config()
+        }
+    }
+
+    pub async fn client(&self) -> Client {
+        create_s3_client(&self.config()).await.unwrap()
+    }
+
+    /// 列举时每页最多返回的键数
+    pub fn set_page_size(&self, page_size: usize) {
+        self.state.lock().unwrap().page_size = page_size;
+    }
+
+    /// 之后该方法的请求都返回 403 AccessDenied
+    pub fn deny(&self, method: &str) {
+        self.state
+            .lock()
+            .unwrap()
+            .denied_methods
+            .push(method.to_string());
+    }
+
+    /// 批量删除时该对象返回 AccessDenied 且不被删除
+    pub fn protect(&self, bucket: &str, key: &str) {
+        self.state
+            .lock()
+            .unwrap()
+            .protected
+            .push((bucket.to_string(), key.to_string()));
+    }
+
+    /// 直接写入对象，保存 CRC32 校验值
+    pub fn put(&self, bucket: &str, key: &str, body: &[u8]) {
+        self.state.lock().unwrap().objects.insert(
+            (bucket.to_string(), key.to_string()),
+            MockObject::new(body.to_vec(), true),
+        );
+    }
+
+    /// 存储桶中的所有键，按字典序排列
+    pub fn keys(&self, bucket: &str) -> Vec<String> {
+        self.state
+            .lock()
+            .unwrap()
+            .objects
+            .keys()
+            .filter(|(name, _)| name == bucket)
+            .map(|(_, key)| key.clone())
+            .collect()
+    }
+
+    /// 对象的 ETag 和 CRC32 校验值
+    pub fn object_checksums(&self, bucket: &str, key: &str) -> Option<(String, Option<String>)> {
+        self.state
+            .lock()
+            .unwrap()
+            .objects
+            .get(&(bucket.to_string(), key.to_string()))
+            .map(|object| (object.etag.clone(), object.crc32.clone()))
+    }
+
+    /// 目前收到的所有请求
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl MockState {
+    fn handle(&mut self, request: MockRequest) -> MockResponse {
+        let key = request.key.clone().unwrap_or_default();
+        let response = match (request.method.as_str(), request.key.is_some()) {
+            _ if self.denied_methods.contains(&request.method) => {
+                MockResponse::error(403, "AccessDenied")
+            }
+            ("GET", false) if request.bucket.is_empty() => self.list_buckets(),
+            ("GET", false) if request.is_list() => self.list(&request),
+            ("POST", false) if request.param("delete").is_some() => self.delete_objects(&request),
+            ("HEAD", true) => self.head(&request, &key),
+            ("GET", true) => self.get(&request, &key),
+            ("PUT", true) if request.param("uploadId").is_some() => self.upload_part(&request),
+            ("PUT", true) if request.header("x-amz-copy-source").is_some() => {
+                self.copy(&request, &key)
+            }
+            ("PUT", true) => {
+                let object = MockObject::new(request.body.clone(), request.wants_crc32());
+                let response = MockResponse::empty(200).header("ETag", &object.etag);
+                self.objects.insert((request.bucket.clone(), key), object);
+                response
+            }
+            ("POST", true) if request.param("uploads").is_some() => {
+                self.create_upload(&request, &key)
+            }
+            ("POST", true) if request.param("uploadId").is_some() => self.complete_upload(&request),
+            ("DELETE", true) if request.param("uploadId").is_some() => {
+                self.uploads.remove(request.param("uploadId").unwrap());
+                MockResponse::empty(204)
+            }
+            ("DELETE", true) => {
+                self.objects.remove(&(request.bucket.clone(), key));
+                MockResponse::empty(204)
+            }
+            _ => MockResponse::error(501, "NotImplemented"),
+        };
+        self.requests.push(request);
+        response
+    }
+
+    /// ListBuckets，存储桶为写入过对象的存储桶
+    fn list_buckets(&self) -> MockResponse {
+        let mut names: Vec<&str> = self
+            .objects
+            .keys()
+            .map(|(bucket, _)| bucket.as_str())
+            .collect();
+        names.dedup();
+        let buckets: String = names
+            .iter()
+            .map(|name| {
+                format!(
+                    "<Bucket><Name>{}</Name><CreationDate>{}</CreationDate></Bucket>",
+                    escape(name),
+                    LAST_MODIFIED
+                )
+            })
+            .collect();
+        MockResponse::xml(format!(
+            "<ListAllMyBucketsResult><Owner><ID>mock</ID></Owner>\
+             <Buckets>{}</Buckets></ListAllMyBucketsResult>",
+            buckets
+        ))
+    }
+
+    /// ListObjectsV2，续传令牌为上一页最后一个键或公共前缀
+    fn list(&self, request: &MockRequest) -> MockResponse {
+        let prefix = request.param("prefix").unwrap_or_default();
+        let delimiter = request.param("delimiter").filter(|value| !value.is_empty());
+        let after = request
+            .param("continuation-token")
+            .or(request.param("start-after"))
+            .unwrap_or_default();
+        let max_keys = request
+            .param("max-keys")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(1000)
+            .min(self.page_size);
+
+        let mut entries: Vec<(String, Option<&MockObject>)> = Vec::new();
+        for ((bucket, key), object) in &self.objects {
+            if *bucket != request.bucket || !key.starts_with(prefix) {
+                continue;
+            }
+            let common_prefix = delimiter.and_then(|delimiter| {
+                key[prefix.len()..]
+                    .find(delimiter)
+                    .map(|index| key[..prefix.len() + index + delimiter.len()].to_string())
+            });
+            match common_prefix {
+                Some(common_prefix) => {
+                    if entries.last().map(|(name, _)| name) != Some(&common_prefix) {
+                        entries.push((common_prefix, None));
+                    }
+                }
+                None => entries.push((key.clone(), Some(object))),
+            }
+        }
+        entries.retain(|(name, _)| name.as_str() > after);
+        let is_truncated = entries.len() > max_keys;
+        entries.truncate(max_keys);
+
+        let mut body = format!(
+            "<ListBucketResult xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">\
+             <Name>{}</Name><Prefix>{}</Prefix><KeyCount>{}</KeyCount>\
+             <MaxKeys>{}</MaxKeys><IsTruncated>{}</IsTruncated>",
+            escape(&request.bucket),
+            escape(prefix),
+            entries.len(),
+            max_keys,
+            is_truncated
+        );
+        if let (true, Some((last, _))) = (is_truncated, entries.last()) {
+            body.push_str(&format!(
+                "<NextContinuationToken>{}</NextContinuationToken>",
+                escape(last)
+            ));
+        }
+        for (name, object) in &entries {
+            match object {
+                Some(object) => body.push_str(&format!(
+                    "<Contents><Key>{}</Key><LastModified>{}</LastModified>\
+                     <ETag>{}</ETag><Size>{}</Size><StorageClass>STANDARD</StorageClass></Contents>",
+                    escape(name),
+                    LAST_MODIFIED,
+                    escape(&object.etag),
+                    object.body.len()
+                )),
+                None => body.push_str(&format!(
+                    "<CommonPrefixes><Prefix>{}</Prefix></CommonPrefixes>",
+                    escape(name)
+                )),
+            }
+        }
+        body.push_str("</ListBucketResult>");
+        MockResponse::xml(body)
+    }
+
+    fn delete_objects(&mut self, request: &MockRequest) -> MockResponse {
+        let body = String::from_utf8_lossy(&request.body);
+        let mut errors = String::new();
+        for key in body.split("<Key>").skip(1) {
+            let key = (
+                request.bucket.clone(),
+                unescape(key.split("</Key>").next().unwrap_or_default()),
+            );
+            if self.protected.contains(&key) {
+                errors.push_str(&format!(
+                    "<Error><Key>{}</Key><Code>AccessDenied</Code>\
+                     <Message>Access Denied</Message></Error>",
+                    escape(&key.1)
+                ));
+                continue;
+            }
+            self.objects.remove(&key);
+        }
+        MockResponse::xml(format!(
+            "<DeleteResult xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">{}</DeleteResult>",
+            errors
+        ))
+    }
+
+    fn head(&self, request: &MockRequest, key: &str) -> MockResponse {
+        let Some(object) = self.objects.get(&(request.bucket.clone(), key.to_string())) else {
+            return MockResponse::empty(404);
+        };
+        let mut response = MockResponse::empty(200)
+            .header("Content-Length", object.body.len().to_string())
+            .header("Content-Type", "application/octet-stream")
+            .header("ETag", &object.etag)
+            .header("Last-Modified", LAST_MODIFIED_HTTP);
+        if let (Some("ENABLED"), Some(crc32)) =
+            (request.header("x-amz-checksum-mode"), &object.crc32)
+        {
+            response = response.header("x-amz-checksum-crc32", crc32);
+        }
+        response
+    }
+
+    fn get(&self, request: &MockRequest, key: &str) -> MockResponse {
+        let Some(object) = self.objects.get(&(request.bucket.clone(), key.to_string())) else {
+            return MockResponse::error(404, "NoSuchKey");
+        };
+        let mut response = MockResponse {
+            body: object.body.clone(),
+            ..self.head(request, key)
+        };
+        response
+            .headers
+            .retain(|(name, _)| name != "Content-Length");
+        response
+    }
+
+    fn copy(&mut self, request: &MockRequest, key: &str) -> MockResponse {
+        let source = percent_decode(request.header("x-amz-copy-source").unwrap_or_default());
+        let source = source.split('?').next().unwrap_or_default();
+        let source = source.trim_start_matches('/');
+        let (bucket, source_key) = source.split_once('/').unwrap_or((source, ""));
+        let Some(object) = self
+            .objects
+            .get(&(bucket.to_string(), source_key.to_string()))
+            .cloned()
+        else {
+            return MockResponse::error(404, "NoSuchKey");
+        };
+
+        let etag = escape(&object.etag);
+        self.objects
+            .insert((request.bucket.clone(), key.to_string()), object);
+        MockResponse::xml(format!(
+            "<CopyObjectResult><LastModified>{}</LastModified><ETag>{}</ETag></CopyObjectResult>",
+            LAST_MODIFIED, etag
+        ))
+    }
+
+    fn create_upload(&mut self, request: &MockRequest, key: &str) -> MockResponse {
+        self.next_upload_id += 1;
+        let upload_id = format!("upload-{}", self.next_upload_id);
+        self.uploads.insert(
+            upload_id.clone(),
+            MockUpload {
+                bucket: request.bucket.clone(),
+                key: key.to_string(),
+                algorithm: request
+                    .header("x-amz-checksum-algorithm")
+                    .map(str::to_string),
+                checksum_type: request.header("x-amz-checksum-type").map(str::to_string),
+                parts: BTreeMap::new(),
+            },
+        );
+        MockResponse::xml(format!(
+            "<InitiateMultipartUploadResult><Bucket>{}</Bucket><Key>{}</Key>\
+             <UploadId>{}</UploadId></InitiateMultipartUploadResult>",
+            escape(&request.bucket),
+            escape(key),
+            upload_id
+        ))
+    }
+
+    fn upload_part(&mut self, request: &MockRequest) -> MockResponse {
+        let upload_id = request.param("uploadId").unwrap_or_default();
+        let part_number = request
+            .param("partNumber")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default();
+        let Some(upload) = self.uploads.get_mut(upload_id) else {
+            return MockResponse::error(404, "NoSuchUpload");
+        };
+
+        let checksum = crc32_checksum(&request.body);
+        upload.parts.insert(part_number, request.body.clone());
+        let mut response = MockResponse::empty(200).header("ETag", format!("\"{:08x}\"", checksum));
+        if request.wants_crc32() {
+            response = response.header("x-amz-checksum-crc32", encode_crc32(checksum));
+        }
+        response
+    }
+
+    /// 按 S3 的规则合并分片：整体校验值覆盖全部内容，组合校验值为各分片校验值的校验值加分片数
+    fn complete_upload(&mut self, request: &MockRequest) -> MockResponse {
+        let Some(upload) = self
+            .uploads
+            .remove(request.param("uploadId").unwrap_or_default())
+        else {
+            return MockResponse::error(404, "NoSuchUpload");
+        };
+
+        let body: Vec<u8> = upload.parts.values().flatten().copied().collect();
+        let mut object = MockObject::new(body, false);
+        object.etag = format!(
+            "\"{:08x}-{}\"",
+            crc32_checksum(&object.body),
+            upload.parts.len()
+        );
+        if upload.algorithm.as_deref() == Some("CRC32") {
+            object.crc32 = Some(match upload.checksum_type.as_deref() {
+                Some("FULL_OBJECT") => encode_crc32(crc32_checksum(&object.body)),
+                _ => {
+                    let part_checksums: Vec<u8> = upload
+                        .parts
+                        .values()
+                        .flat_map(|part| crc32_checksum(part).to_be_bytes())
+                        .collect();
+                    format!(
+                        "{}-{}",
+                        encode_crc32(crc32_checksum(&part_checksums)),
+                        upload.parts.len()
+                    )
+                }
+            });
+        }
+
+        let response = MockResponse::xml(format!(
+            "<CompleteMultipartUploadResult><Bucket>{}</Bucket><Key>{}</Key>\
+             <ETag>{}</ETag></CompleteMultipartUploadResult>",
+            escape(&upload.bucket),
+            escape(&upload.key),
+            escape(&object.etag)
+        ));
+        self.objects.insert((upload.bucket, upload.key), object);
+        response
+    }
+}
+
+/// 处理一个连接上的所有请求（HTTP/1.1 长连接）
+async fn serve(mut stream: TcpStream, state: Arc<Mutex<MockState>>) {
+    let mut buffer = Vec::new();
+    loop {
+        let head_end = loop {
+            if let Some(index) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+                break index + 4;
+            }
+            if !read_more(&mut stream, &mut buffer).await {
+                return;
+            }
+        };
+        let head = String::from_utf8_lossy(&buffer[..head_end]).to_string();
+        buffer.drain(..head_end);
+
+        let mut lines = head.lines();
+        let mut request_line = lines.next().unwrap_or_default().split(' ');
+        let method = request_line.next().unwrap_or_default().to_string();
+        let target = request_line.next().unwrap_or_default().to_string();
+        let headers: Vec<(String, String)> = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+            .collect();
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+
+        if header("expect").is_some_and(|value| value.eq_ignore_ascii_case("100-continue"))
+            && stream
+                .write_all(b"HTTP/1.1 100 Continue\r\n\r\n")
+                .await
+                .is_err()
+        {
+            return;
+        }
+        let length: usize = header("content-length")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default();
+        while buffer.len() < length {
+            if !read_more(&mut stream, &mut buffer).await {
+                return;
+            }
+        }
+        let mut body: Vec<u8> = buffer.drain(..length).collect();
+        if header("content-encoding").is_some_and(|value| value.contains("aws-chunked")) {
+            body = decode_aws_chunked(&body);
+        }
+
+        let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+        let path = percent_decode(path.trim_start_matches('/'));
+        let (bucket, key) = match path.split_once('/') {
+            Some((bucket, key)) if !key.is_empty() => (bucket.to_string(), Some(key.to_string())),
+            Some((bucket, _)) => (bucket.to_string(), None),
+            None => (path.clone(), None),
+        };
+        let request = MockRequest {
+            method: method.clone(),
+            bucket,
+            key,
+            query: url::form_urlencoded::parse(query.as_bytes())
+                .into_owned()
+                .collect(),
+            headers,
+            body,
+        };
+
+        let response = state.lock().unwrap().handle(request);
+        let mut head = format!("HTTP/1.1 {} Mock\r\n", response.status);
+        for (name, value) in &response.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        // HEAD 响应带上对象的 Content-Length，但没有响应体
+        if method != "HEAD" {
+            head.push_str(&format!("Content-Length: {}\r\n", response.body.len()));
+        }
+        head.push_str("\r\n");
+        let mut bytes = head.into_bytes();
+        if method != "HEAD" {
+            bytes.extend_from_slice(&response.body);
+        }
+        if stream.write_all(&bytes).await.is_err() {
+            return;
+        }
+    }
+}
+
+async fn read_more(stream: &mut TcpStream, buffer: &mut Vec<u8>) -> bool {
+    let mut chunk = [0u8; 64 * 1024];
+    match stream.read(&mut chunk).await {
+        Ok(0) | Err(_) => false,
+        Ok(read) => {
+            buffer.extend_from_slice(&chunk[..read]);
+            true
+        }
+    }
+}
+
+/// 解码 `aws-chunked` 请求体：每块为 "十六进制长度[;扩展]\r\n数据\r\n"，以长度 0 的块和尾部头结束
+fn decode_aws_chunked(body: &[u8]) -> Vec<u8> {
+    let mut decoded = Vec::new();
+    let mut rest = body;
+    while let Some(line_end) = rest.windows(2).position(|window| window == b"\r\n") {
+        let line = String::from_utf8_lossy(&rest[..line_end]);
+        let size = usize::from_str_radix(line.split(';').next().unwrap_or_default().trim(), 16)
+            .unwrap_or_default();
+        if size == 0 {
+            break;
+        }
+        let start = line_end + 2;
+        decoded.extend_from_slice(&rest[start..start + size]);
+        rest = &rest[(start + size + 2).min(rest.len())..];
+    }
+    decoded
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = (bytes[index] == b'%')
+            .then(|| value.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn crc32_checksum(data: &[u8]) -> u32 {
+    let mut table = [0u32; 256];
+    for (index, entry) in table.iter_mut().enumerate() {
+        let mut value = index as u32;
+        for _ in 0..8 {
+            value = if value & 1 == 1 {
+                (value >> 1) ^ 0xEDB8_8320
+            } else {
+                value >> 1
+            };
+        }
+        *entry = value;
+    }
+
+    !data.iter().fold(!0u32, |crc, &byte| {
+        table[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+/// S3 校验值头的格式：大端字节的 Base64
+fn encode_crc32(checksum: u32) -> String {
+    base64::engine::general_purpose::STANDARD.encode(checksum.to_be_bytes())
+}
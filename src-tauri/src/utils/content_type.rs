@@ -1,6 +1,6 @@
 /// 根据文件扩展名推断 Content-Type
 pub fn guess_content_type(filename: &str) -> String {
-    let ext = filename.split('.').last().unwrap_or("").to_lowercase();
+    let ext = filename.split('.').next_back().unwrap_or("").to_lowercase();
 
     match ext.as_str() {
         // 图片
@@ -0,0 +1,92 @@
+/// INI 文件中的一个小节
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IniSection {
+    pub name: String,
+    pub entries: Vec<(String, String)>,
+}
+
+impl IniSection {
+    /// 查找键对应的值，键名不区分大小写
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// 解析 AWS、rclone、s3cmd 使用的简单 INI 格式
+///
+/// 支持 `#` 和 `;` 注释。AWS 配置中 `s3 =` 下缩进的子项会展开为 `s3.addressing_style` 这样的键。
+pub fn parse_ini(content: &str) -> Vec<IniSection> {
+    let mut sections: Vec<IniSection> = Vec::new();
+    let mut parent: Option<String> = None;
+
+    for raw_line in content.lines() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if line.starts_with('[') && line.ends_with(']') {
+            sections.push(IniSection {
+                name: line[1..line.len() - 1].trim().to_string(),
+                entries: Vec::new(),
+            });
+            parent = None;
+            continue;
+        }
+
+        let Some(section) = sections.last_mut() else {
+            continue;
+        };
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().to_string();
+        let value = value.trim().to_string();
+
+        let indented = raw_line.starts_with(' ') || raw_line.starts_with('\t');
+        match &parent {
+            Some(parent) if indented => {
+                section.entries.push((format!("{}.{}", parent, key), value));
+            }
+            _ => {
+                parent = value.is_empty().then(|| key.clone());
+                section.entries.push((key, value));
+            }
+        }
+    }
+
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ini() {
+        let content = "\
+# comment
+[default]
+aws_access_key_id = AKIAEXAMPLE
+region=us-west-2
+
+[profile minio]
+endpoint_url = http://localhost:9000
+s3 =
+  addressing_style = path
+; trailing comment
+output = json
+";
+        let sections = parse_ini(content);
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].name, "default");
+        assert_eq!(sections[0].get("AWS_ACCESS_KEY_ID"), Some("AKIAEXAMPLE"));
+        assert_eq!(sections[0].get("region"), Some("us-west-2"));
+        assert_eq!(sections[1].name, "profile minio");
+        assert_eq!(sections[1].get("s3.addressing_style"), Some("path"));
+        assert_eq!(sections[1].get("output"), Some("json"));
+    }
+}
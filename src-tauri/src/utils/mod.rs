@@ -1 +1,4 @@
 pub mod content_type;
+pub mod id;
+pub mod ini;
+pub mod storage;
@@ -0,0 +1,50 @@
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// 读取 JSON 文件，文件不存在时返回 `Ok(None)`
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, String> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+/// 写入临时文件后重命名，避免写入中途退出导致文件损坏
+pub fn write_json_atomic<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+
+    let mut tmp_path = path.as_os_str().to_os_string();
+    tmp_path.push(".tmp");
+    std::fs::write(&tmp_path, content).map_err(|e| e.to_string())?;
+    std::fs::rename(&tmp_path, path).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("snowy-storage-test-{}", std::process::id()));
+        let path = dir.join("data.json");
+
+        assert_eq!(read_json::<Vec<String>>(&path).unwrap(), None);
+
+        let value = vec!["a".to_string(), "b".to_string()];
+        write_json_atomic(&path, &value).unwrap();
+        assert_eq!(read_json::<Vec<String>>(&path).unwrap(), Some(value));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,4 +1,4 @@
-import React from "react";
+import React, { useEffect } from "react";
 import { Cloud, Settings, Wifi, WifiOff } from "lucide-react";
 import { useS3Store } from "./stores/useS3Store";
 import ConfigModal from "./components/ConfigModal";
@@ -11,25 +11,32 @@ import { Card, CardContent } from "@/components/ui/card";
 
 function App() {
   const {
-    config,
+    profile,
     isConnected,
     connectionError,
     selectedBucket,
     objects,
     setShowConfigModal,
-    clearConfig,
+    clearProfile,
+    loadProfile,
   } = useS3Store();
 
+  useEffect(() => {
+    loadProfile().catch((error) => {
+      console.error("❌ 读取当前配置失败:", error);
+    });
+  }, [loadProfile]);
+
   const handleOpenConfig = () => {
     setShowConfigModal(true);
   };
 
   const handleDisconnect = () => {
-    clearConfig();
+    clearProfile();
   };
 
   const getConnectionStatus = () => {
-    if (!config) {
+    if (!profile) {
       return (
         <Badge variant="outline" className="text-gray-500 border-gray-300">
           未配置
@@ -75,11 +82,11 @@ function App() {
           </div>
 
           <div className="flex items-center space-x-4">
-            {config && (
+            {profile && (
               <>
                 <div className="flex items-center space-x-2 text-sm text-gray-600">
                   <span className="font-medium">
-                    {new URL(config.endpoint).hostname}
+                    {new URL(profile.endpoint).hostname}
                   </span>
                   {getConnectionStatus()}
                 </div>
@@ -88,15 +95,15 @@ function App() {
             )}
 
             <Button
-              variant={config ? "outline" : "default"}
+              variant={profile ? "outline" : "default"}
               onClick={handleOpenConfig}
               className="flex items-center space-x-2 shadow-sm"
             >
               <Settings className="w-4 h-4" />
-              <span>{config ? "修改配置" : "OSS 配置"}</span>
+              <span>{profile ? "修改配置" : "OSS 配置"}</span>
             </Button>
 
-            {config && (
+            {profile && (
               <Button
                 variant="destructive"
                 onClick={handleDisconnect}
@@ -120,7 +127,7 @@ function App() {
         {/* Main Content */}
         <main className="flex-1 flex flex-col bg-gray-50">
           <div className="p-6 flex-1">
-            {!config ? (
+            {!profile ? (
               <div className="flex items-center justify-center h-full">
                 <Card className="w-full max-w-md shadow-lg border-0">
                   <CardContent className="pt-8 pb-8">
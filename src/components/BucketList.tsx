@@ -12,7 +12,7 @@ import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
 
 const BucketList: React.FC = () => {
   const {
-    config,
+    profile,
     buckets,
     isLoadingBuckets: loading,
     error,
@@ -22,16 +22,16 @@ const BucketList: React.FC = () => {
   } = useS3Store();
 
   useEffect(() => {
-    if (config && !buckets.length) {
+    if (profile && !buckets.length) {
       fetchBuckets();
     }
-  }, [config, buckets.length]); // 移除 fetchBuckets 依赖，避免重复调用
+  }, [profile, buckets.length]); // 移除 fetchBuckets 依赖，避免重复调用
 
   const handleRefresh = () => {
     fetchBuckets();
   };
 
-  if (!config) {
+  if (!profile) {
     return (
       <Card className="shadow-sm border-gray-200">
         <CardHeader className="pb-3">
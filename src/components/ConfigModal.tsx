@@ -2,7 +2,8 @@ import React, { useState, useEffect } from "react";
 import { Loader2, TestTube, Save, X, Info } from "lucide-react";
 import { useS3Store } from "../stores/useS3Store";
 import { S3Service } from "../services/s3Service";
-import { S3Config } from "../types/s3";
+import { localizeError } from "../lib/errors";
+import { ProfileSummary, S3Config } from "../types/s3";
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Card, CardHeader, CardTitle, CardContent } from "@/components/ui/card";
@@ -14,6 +15,19 @@ import {
   DialogFooter,
 } from "@/components/ui/dialog";
 
+// 密钥只保存在后端，编辑已有配置时表单中的 Access Key ID 和密钥为空，留空表示保留原值
+const profileForm = (profile: ProfileSummary): S3Config => ({
+  endpoint: profile.endpoint,
+  region: profile.region,
+  access_key_id: "",
+  secret_access_key: "",
+  bucket: profile.bucket || "",
+  custom_path: profile.customPath || "",
+  addressing_style: profile.addressingStyle,
+  compatibility: profile.compatibility,
+  network: profile.network,
+});
+
 const ConfigModal: React.FC = () => {
   const [formData, setFormData] = useState<S3Config>({
     endpoint: "",
@@ -33,17 +47,17 @@ const ConfigModal: React.FC = () => {
   const {
     showConfigModal,
     setShowConfigModal,
-    config,
-    setConfig,
+    profile,
+    setProfile,
     setConnectionStatus,
     testConnection,
   } = useS3Store();
 
   useEffect(() => {
-    if (showConfigModal && config) {
-      setFormData(config);
+    if (showConfigModal && profile) {
+      setFormData(profileForm(profile));
     }
-  }, [showConfigModal, config]);
+  }, [showConfigModal, profile]);
 
   const presetConfigs = {
     r2: {
@@ -96,12 +110,12 @@ const ConfigModal: React.FC = () => {
     }));
   };
 
+  // 新建配置需要完整的密钥；编辑时密钥留空则保留已保存的密钥
+  const hasKeys = !!formData.access_key_id && !!formData.secret_access_key;
+  const isComplete = () => !!formData.endpoint && (!!profile || hasKeys);
+
   const handleTestConnection = async () => {
-    if (
-      !formData.endpoint ||
-      !formData.access_key_id ||
-      !formData.secret_access_key
-    ) {
+    if (!isComplete()) {
       setTestResult({
         success: false,
         message: "请填写完整的配置信息",
@@ -113,6 +127,7 @@ const ConfigModal: React.FC = () => {
       setTesting(true);
       setTestResult(null);
 
+      // 编辑时留空的密钥由后端使用已保存的值
       await testConnection(formData);
       setTestResult({
         success: true,
@@ -122,7 +137,7 @@ const ConfigModal: React.FC = () => {
       console.error("Connection test failed:", error);
       setTestResult({
         success: false,
-        message: `连接测试失败: ${error}`,
+        message: `连接测试失败: ${localizeError(error, String(error))}`,
       });
     } finally {
       setTesting(false);
@@ -130,11 +145,7 @@ const ConfigModal: React.FC = () => {
   };
 
   const handleSave = async () => {
-    if (
-      !formData.endpoint ||
-      !formData.access_key_id ||
-      !formData.secret_access_key
-    ) {
+    if (!isComplete()) {
       setTestResult({
         success: false,
         message: "请填写完整的配置信息",
@@ -144,7 +155,14 @@ const ConfigModal: React.FC = () => {
 
     try {
       setSaving(true);
-      setConfig(formData);
+      const input = {
+        name: profile?.name || new URL(formData.endpoint).hostname,
+        config: formData,
+      };
+      const saved = profile
+        ? await S3Service.updateProfile(profile.id, input)
+        : await S3Service.createProfile(input);
+      setProfile(await S3Service.selectProfile(saved.id));
       setShowConfigModal(false);
       setTestResult({
         success: true,
@@ -154,7 +172,7 @@ const ConfigModal: React.FC = () => {
       console.error("Save failed:", error);
       setTestResult({
         success: false,
-        message: "保存配置失败",
+        message: `保存配置失败: ${localizeError(error, String(error))}`,
       });
     } finally {
       setSaving(false);
@@ -164,8 +182,8 @@ const ConfigModal: React.FC = () => {
   const handleCancel = () => {
     setShowConfigModal(false);
     setTestResult(null);
-    if (config) {
-      setFormData(config);
+    if (profile) {
+      setFormData(profileForm(profile));
     }
   };
 
@@ -250,7 +268,9 @@ const ConfigModal: React.FC = () => {
                 Access Key ID <span className="text-destructive">*</span>
               </label>
               <Input
-                placeholder="您的 Access Key ID"
+                placeholder={
+                  profile ? "留空保留原有 Access Key ID" : "您的 Access Key ID"
+                }
                 value={formData.access_key_id}
                 onChange={(e) =>
                   handleInputChange("access_key_id", e.target.value)
@@ -264,7 +284,7 @@ const ConfigModal: React.FC = () => {
               </label>
               <Input
                 type="password"
-                placeholder="您的 Secret Access Key"
+                placeholder={profile ? "留空保留原有密钥" : "您的 Secret Access Key"}
                 value={formData.secret_access_key}
                 onChange={(e) =>
                   handleInputChange("secret_access_key", e.target.value)
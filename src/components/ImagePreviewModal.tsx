@@ -13,6 +13,7 @@ import {
 } from "lucide-react";
 import { useS3Store } from "../stores/useS3Store";
 import { S3Service } from "../services/s3Service";
+import { isAppError, isCancelled, localizeError } from "../lib/errors";
 import { Button } from "@/components/ui/button";
 import {
   Dialog,
@@ -42,7 +43,7 @@ const ImagePreviewModal: React.FC = () => {
     showImagePreview,
     previewImageUrl,
     setShowImagePreview,
-    config,
+    profile,
     selectedBucket,
     currentPreviewObject,
   } = useS3Store();
@@ -216,7 +217,7 @@ const ImagePreviewModal: React.FC = () => {
   };
 
   const handleDownload = async () => {
-    if (!config || !selectedBucket || !currentPreviewObject) {
+    if (!profile || !selectedBucket || !currentPreviewObject) {
       showNotification("error", "无法确定要下载的文件");
       return;
     }
@@ -227,11 +228,13 @@ const ImagePreviewModal: React.FC = () => {
         await downloadWithTauri(currentPreviewObject);
       } catch (tauriError: any) {
         console.warn("Tauri 下载不可用，使用浏览器下载:", tauriError.message);
+        // 后端返回的 AppError 说明命令可用，只是执行失败
         if (
-          tauriError.message?.includes("not found") ||
+          !isAppError(tauriError) &&
+          (tauriError.message?.includes("not found") ||
           tauriError.message?.includes("command") ||
           tauriError.message?.includes("Command") ||
-          tauriError.message?.includes("invoke")
+            tauriError.message?.includes("invoke"))
         ) {
           await downloadWithBrowser(currentPreviewObject);
         } else {
@@ -239,8 +242,9 @@ const ImagePreviewModal: React.FC = () => {
         }
       }
     } catch (error) {
+      if (isCancelled(error)) return;
       console.error("下载失败:", error);
-      showNotification("error", "下载失败");
+      showNotification("error", `下载失败: ${localizeError(error, "未知错误")}`);
     }
   };
 
@@ -254,7 +258,7 @@ const ImagePreviewModal: React.FC = () => {
     // 调用后端的下载文件函数
     const result = await invoke("download_file", {
       request: {
-        config: config!,
+        profileId: profile!.id,
         bucket_name: selectedBucket!,
         object_key: object.key,
         default_file_name: fileName,
@@ -268,7 +272,7 @@ const ImagePreviewModal: React.FC = () => {
     try {
       showNotification("info", "正在生成下载链接...");
       const url = await S3Service.getPresignedUrl(
-        config!,
+        profile!.id,
         selectedBucket!,
         object.key
       );
@@ -17,6 +17,7 @@ import {
 } from "lucide-react";
 import { useS3Store } from "../stores/useS3Store";
 import { S3Service } from "../services/s3Service";
+import { isAppError, isCancelled, localizeError } from "../lib/errors";
 import { S3Object } from "../types/s3";
 import ImagePreviewModal from "./ImagePreviewModal";
 import UploadModal from "./UploadModal";
@@ -43,7 +44,7 @@ const ObjectList: React.FC = () => {
   } | null>(null);
 
   const {
-    config,
+    profile,
     selectedBucket,
     objects,
     selectedObjects,
@@ -116,8 +117,7 @@ const ObjectList: React.FC = () => {
   useEffect(() => {
     if (selectedBucket) {
       fetchObjects().catch((error) => {
-        const errorMessage =
-          error instanceof Error ? error.message : String(error);
+        const errorMessage = localizeError(error, String(error));
         showNotification("error", `加载文件列表失败: ${errorMessage}`);
       });
     }
@@ -125,21 +125,20 @@ const ObjectList: React.FC = () => {
 
   const handleRefresh = () => {
     fetchObjects().catch((error) => {
-      const errorMessage =
-        error instanceof Error ? error.message : String(error);
+      const errorMessage = localizeError(error, String(error));
       showNotification("error", `刷新文件列表失败: ${errorMessage}`);
     });
   };
 
   const handlePreview = async (object: S3Object) => {
-    if (!config || !selectedBucket) return;
+    if (!profile || !selectedBucket) return;
 
     try {
       const fileType = S3Service.getFileType(object.key);
 
       if (fileType === "image") {
         const url = await S3Service.getPresignedUrl(
-          config,
+          profile.id,
           selectedBucket,
           object.key
         );
@@ -154,7 +153,7 @@ const ObjectList: React.FC = () => {
   };
 
   const handleDownload = async (object: S3Object) => {
-    if (!config || !selectedBucket) return;
+    if (!profile || !selectedBucket) return;
 
     try {
       // 先尝试 Tauri 原生下载，如果失败则回退到浏览器下载
@@ -162,11 +161,13 @@ const ObjectList: React.FC = () => {
         await downloadWithTauri(object);
       } catch (tauriError: any) {
         console.warn("Tauri 下载不可用，使用浏览器下载:", tauriError.message);
+        // 后端返回的 AppError 说明命令可用，只是执行失败
         if (
-          tauriError.message?.includes("not found") ||
+          !isAppError(tauriError) &&
+          (tauriError.message?.includes("not found") ||
           tauriError.message?.includes("command") ||
           tauriError.message?.includes("Command") ||
-          tauriError.message?.includes("invoke")
+            tauriError.message?.includes("invoke"))
         ) {
           await downloadWithBrowser(object);
         } else {
@@ -174,8 +175,9 @@ const ObjectList: React.FC = () => {
         }
       }
     } catch (error) {
+      if (isCancelled(error)) return;
       console.error("下载失败:", error);
-      showNotification("error", "下载失败");
+      showNotification("error", `下载失败: ${localizeError(error, "未知错误")}`);
     }
   };
 
@@ -189,7 +191,7 @@ const ObjectList: React.FC = () => {
     // 调用后端的下载文件函数
     const result = await invoke("download_file", {
       request: {
-        config: config!,
+        profileId: profile!.id,
         bucket_name: selectedBucket!,
         object_key: object.key,
         default_file_name: fileName,
@@ -203,7 +205,7 @@ const ObjectList: React.FC = () => {
     try {
       showNotification("info", "正在生成下载链接...");
       const url = await S3Service.getPresignedUrl(
-        config!,
+        profile!.id,
         selectedBucket!,
         object.key
       );
@@ -233,17 +235,26 @@ const ObjectList: React.FC = () => {
   };
 
   const confirmDelete = async () => {
-    if (!config || !selectedBucket) return;
+    if (!profile || !selectedBucket) return;
 
     try {
       setDeleting(true);
-      await deleteObjects(selectedObjects);
-      showNotification("success", `成功删除 ${selectedObjects.length} 个文件`);
+      const result = await deleteObjects(selectedObjects);
+      if (result.errors.length === 0) {
+        showNotification("success", `成功删除 ${result.deleted.length} 个文件`);
+      } else {
+        const failed = result.errors
+          .map((error) => `${error.key}: ${error.message ?? error.code ?? "未知错误"}`)
+          .join("; ");
+        showNotification(
+          "error",
+          `已删除 ${result.deleted.length} 个文件，${result.errors.length} 个删除失败: ${failed}`
+        );
+      }
       setShowDeleteModal(false);
     } catch (error) {
       console.error("Failed to delete objects:", error);
-      const errorMessage =
-        error instanceof Error ? error.message : String(error);
+      const errorMessage = localizeError(error, String(error));
       showNotification("error", `删除文件失败: ${errorMessage}`);
     } finally {
       setDeleting(false);
@@ -9,6 +9,7 @@ import {
 } from "lucide-react";
 import { useS3Store } from "../stores/useS3Store";
 import { S3Service } from "../services/s3Service";
+import { localizeError } from "../lib/errors";
 import { Button } from "@/components/ui/button";
 import { Card, CardHeader, CardTitle, CardContent } from "@/components/ui/card";
 import {
@@ -32,7 +33,7 @@ const UploadModal: React.FC<UploadModalProps> = ({ open, onClose }) => {
   const [isDragOver, setIsDragOver] = useState(false);
   const dropRef = useRef<HTMLDivElement>(null);
 
-  const { config, uploadFilesWithDialog, selectedBucket, currentPrefix } =
+  const { profile, uploadFilesWithDialog, selectedBucket, currentPrefix } =
     useS3Store();
 
   // 简化的拖拽处理
@@ -67,7 +68,7 @@ const UploadModal: React.FC<UploadModalProps> = ({ open, onClose }) => {
       console.log("💎 Drop detected!");
       setIsDragOver(false);
 
-      if (!config || !selectedBucket) {
+      if (!profile || !selectedBucket) {
         setUploadResults(["❌ 请先配置 OSS 连接并选择 Bucket"]);
         return;
       }
@@ -98,7 +99,7 @@ const UploadModal: React.FC<UploadModalProps> = ({ open, onClose }) => {
 
               const { invoke } = await import("@tauri-apps/api/core");
               const uploadRequest = {
-                config,
+                profileId: profile.id,
                 bucketName: selectedBucket,
                 objectKey: objectKey,
                 fileBytes: Array.from(uint8Array),
@@ -117,7 +118,7 @@ const UploadModal: React.FC<UploadModalProps> = ({ open, onClose }) => {
               console.error(`❌ Upload failed: ${file.name}`, error);
               results.push(
                 `❌ 上传 '${file.name}' 失败: ${
-                  error instanceof Error ? error.message : "未知错误"
+                  localizeError(error, "未知错误")
                 }`
               );
               errorCount++;
@@ -137,7 +138,7 @@ const UploadModal: React.FC<UploadModalProps> = ({ open, onClose }) => {
           console.error("❌ Upload error:", error);
           setUploadResults([
             `❌ 拖拽上传失败: ${
-              error instanceof Error ? error.message : "未知错误"
+              localizeError(error, "未知错误")
             }`,
           ]);
         } finally {
@@ -145,7 +146,7 @@ const UploadModal: React.FC<UploadModalProps> = ({ open, onClose }) => {
         }
       }
     },
-    [config, selectedBucket, currentPrefix]
+    [profile, selectedBucket, currentPrefix]
   );
 
   const handleQuickUpload = useCallback(async () => {
@@ -158,7 +159,7 @@ const UploadModal: React.FC<UploadModalProps> = ({ open, onClose }) => {
     } catch (error) {
       console.error("Upload failed:", error);
       setUploadResults([
-        `上传失败: ${error instanceof Error ? error.message : "未知错误"}`,
+        `上传失败: ${localizeError(error, "未知错误")}`,
       ]);
     } finally {
       setUploading(false);
@@ -0,0 +1,44 @@
+import type { AppError, ErrorCode } from "../types/s3";
+
+const ERROR_MESSAGES: Record<ErrorCode, string> = {
+  cancelled: "操作已取消",
+  accessDenied: "没有权限执行此操作",
+  invalidCredentials: "访问密钥无效或已过期",
+  notFound: "请求的资源不存在",
+  conflict: "操作与当前状态冲突",
+  throttled: "请求过于频繁，请稍后重试",
+  network: "网络连接失败，请检查网络或服务地址",
+  timeout: "请求超时，请稍后重试",
+  invalidInput: "参数或配置不正确",
+  locked: "凭证已锁定，请输入主密码解锁",
+  wrongPassword: "主密码错误",
+  io: "读写本地文件失败",
+  service: "服务端返回错误",
+  internal: "应用内部错误",
+};
+
+export function isAppError(error: unknown): error is AppError {
+  return (
+    typeof error === "object" &&
+    error !== null &&
+    "code" in error &&
+    typeof (error as AppError).code === "string" &&
+    (error as AppError).code in ERROR_MESSAGES
+  );
+}
+
+export function isCancelled(error: unknown): boolean {
+  return isAppError(error) && error.code === "cancelled";
+}
+
+// 根据错误码生成界面上显示的提示，未知错误使用 fallback
+export function localizeError(error: unknown, fallback: string): string {
+  if (isAppError(error)) {
+    const message = ERROR_MESSAGES[error.code];
+    return error.s3Code ? `${message} (${error.s3Code})` : message;
+  }
+  if (error instanceof Error) {
+    return error.message;
+  }
+  return typeof error === "string" ? error : fallback;
+}
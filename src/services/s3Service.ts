@@ -1,50 +1,414 @@
 import { invoke } from "@tauri-apps/api/core";
-import { S3Config, S3Object, BucketInfo } from "../types/s3";
+import {
+  S3Config,
+  S3Object,
+  BucketInfo,
+  ListObjectsPage,
+  ProfileInput,
+  ProfileSummary,
+  CredentialStatus,
+  ImportSource,
+  ImportCandidate,
+  ImportProfilesResult,
+  CompatibilityDefaults,
+  ProviderPreset,
+  BuildProviderConfigRequest,
+  ProviderMatch,
+  DiagnosticReport,
+  BucketPermissions,
+  ConflictStrategy,
+  ExportFormat,
+  ExportProfilesResult,
+  LogEntry,
+  LogQuery,
+  HttpInspectorState,
+  CopyObjectOptions,
+  CopyPrefixResult,
+  MigrateObjectsRequest,
+  MigrateObjectsResult,
+  ObjectMetadata,
+  MetadataChanges,
+  UpdatePrefixMetadataResult,
+  ObjectTag,
+  ListTagOptions,
+  TagObjectsOptions,
+  TagObjectsResult,
+  ObjectVersionsPage,
+  ObjectVersionId,
+  DeleteObjectVersionsResult,
+  DeleteObjectsResult,
+  BucketVersioning,
+} from "../types/s3";
 
 export class S3Service {
-  static async testConnection(config: S3Config): Promise<string> {
-    return invoke("test_s3_connection", { config });
+  static async listProfiles(): Promise<ProfileSummary[]> {
+    return invoke("list_profiles");
   }
 
-  static async listBuckets(config: S3Config): Promise<BucketInfo[]> {
-    return invoke("list_buckets", { config });
+  static async getSelectedProfile(): Promise<ProfileSummary | null> {
+    return invoke("get_selected_profile");
+  }
+
+  static async createProfile(input: ProfileInput): Promise<ProfileSummary> {
+    return invoke("create_profile", { input });
+  }
+
+  // 密钥留空时后端保留原有密钥
+  static async updateProfile(
+    profileId: string,
+    input: ProfileInput
+  ): Promise<ProfileSummary> {
+    return invoke("update_profile", { profileId, input });
+  }
+
+  static async deleteProfile(profileId: string): Promise<void> {
+    return invoke("delete_profile", { profileId });
+  }
+
+  static async selectProfile(profileId: string): Promise<ProfileSummary> {
+    return invoke("select_profile", { profileId });
+  }
+
+  // MFA 验证码只用于本次会话，不会被保存
+  static async startMfaSession(profileId: string, mfaCode: string): Promise<void> {
+    return invoke("start_mfa_session", { profileId, mfaCode });
+  }
+
+  static async listProviderPresets(): Promise<ProviderPreset[]> {
+    return invoke("list_provider_presets");
+  }
+
+  static async buildConfigFromPreset(request: BuildProviderConfigRequest): Promise<S3Config> {
+    return invoke("build_config_from_preset", { request });
+  }
+
+  static async detectProvider(endpoint: string): Promise<ProviderMatch | null> {
+    return invoke("detect_provider", { endpoint });
+  }
+
+  static async getCompatibilityDefaults(endpoint: string): Promise<CompatibilityDefaults> {
+    return invoke("get_compatibility_defaults", { endpoint });
+  }
+
+  static async previewProfileImport(
+    source: ImportSource,
+    path?: string
+  ): Promise<ImportCandidate[]> {
+    return invoke("preview_profile_import", { source, path: path || null });
+  }
+
+  static async importProfiles(
+    source: ImportSource,
+    conflict: ConflictStrategy,
+    names?: string[],
+    path?: string
+  ): Promise<ImportProfilesResult> {
+    const request = { source, path, conflict, names };
+    return invoke("import_profiles", { request });
+  }
+
+  // 返回导出文件的保存路径和无法导出的配置
+  static async exportProfiles(
+    format: ExportFormat,
+    profileIds: string[]
+  ): Promise<ExportProfilesResult> {
+    const request = { format, profileIds };
+    return invoke("export_profiles", { request });
+  }
+
+  static async getCredentialStatus(): Promise<CredentialStatus> {
+    return invoke("get_credential_status");
+  }
+
+  // 第一次解锁时设置主密码
+  static async unlockCredentials(password: string): Promise<CredentialStatus> {
+    return invoke("unlock_credentials", { password });
+  }
+
+  static async lockCredentials(): Promise<CredentialStatus> {
+    return invoke("lock_credentials");
+  }
+
+  static async setAutoLockTimeout(seconds: number): Promise<CredentialStatus> {
+    return invoke("set_auto_lock_timeout", { seconds });
+  }
+
+  // 传入 draft 时测试尚未保存的配置，否则测试已保存的配置
+  static async testConnection(profileId?: string, draft?: S3Config): Promise<string> {
+    return invoke("test_s3_connection", { profileId, draft });
+  }
+
+  static async diagnoseConnection(
+    profileId?: string,
+    draft?: S3Config
+  ): Promise<DiagnosticReport> {
+    return invoke("diagnose_connection", { profileId, draft });
+  }
+
+  static async listBuckets(profileId: string): Promise<BucketInfo[]> {
+    return invoke("list_buckets", { profileId });
+  }
+
+  static async probePermissions(
+    profileId: string,
+    bucketName: string,
+    prefix?: string
+  ): Promise<BucketPermissions> {
+    const request = { profileId, bucketName, prefix };
+    return invoke("probe_permissions", { request });
+  }
+
+  static async getLogs(query: LogQuery = {}): Promise<LogEntry[]> {
+    return invoke("get_logs", { query });
+  }
+
+  static async getHttpInspector(): Promise<HttpInspectorState> {
+    return invoke("get_http_inspector");
+  }
+
+  static async setHttpInspectorEnabled(enabled: boolean): Promise<void> {
+    return invoke("set_http_inspector_enabled", { enabled });
+  }
+
+  static async clearHttpExchanges(): Promise<void> {
+    return invoke("clear_http_exchanges");
+  }
+
+  // 返回 HAR 文件的保存路径
+  static async exportHttpHar(): Promise<string> {
+    return invoke("export_http_har");
   }
 
   static async listObjects(
-    config: S3Config,
+    profileId: string,
     bucketName: string,
     prefix?: string
   ): Promise<S3Object[]> {
-    console.log("Calling list_objects with:", { config, bucketName, prefix });
+    console.log("Calling list_objects with:", { profileId, bucketName, prefix });
     const request = {
-      config,
+      profileId,
       bucketName: bucketName,
       prefix,
     };
     return invoke("list_objects", { request });
   }
 
+  static async listObjectsPage(
+    profileId: string,
+    bucketName: string,
+    prefix?: string,
+    continuationToken?: string,
+    delimiter: string = "/",
+    tagOptions: ListTagOptions = {}
+  ): Promise<ListObjectsPage> {
+    const request = {
+      profileId,
+      bucketName: bucketName,
+      prefix,
+      delimiter,
+      continuationToken,
+      ...tagOptions,
+    };
+    return invoke("list_objects_page", { request });
+  }
+
   static async deleteObjects(
-    config: S3Config,
+    profileId: string,
     bucketName: string,
     objectKeys: string[]
-  ): Promise<string> {
+  ): Promise<DeleteObjectsResult> {
     const request = {
-      config,
+      profileId,
       bucketName: bucketName,
       objectKeys: objectKeys,
     };
     return invoke("delete_objects", { request });
   }
 
+  static async copyObject(
+    profileId: string,
+    sourceBucket: string,
+    sourceKey: string,
+    destKey: string,
+    options: CopyObjectOptions = {}
+  ): Promise<string> {
+    const request = { profileId, sourceBucket, sourceKey, destKey, ...options };
+    return invoke("copy_object", { request });
+  }
+
+  static async moveObject(
+    profileId: string,
+    sourceBucket: string,
+    sourceKey: string,
+    destKey: string,
+    options: CopyObjectOptions = {}
+  ): Promise<string> {
+    const request = { profileId, sourceBucket, sourceKey, destKey, ...options };
+    return invoke("move_object", { request });
+  }
+
+  static async copyPrefix(
+    profileId: string,
+    sourceBucket: string,
+    sourcePrefix: string,
+    destPrefix: string,
+    destBucket?: string
+  ): Promise<CopyPrefixResult> {
+    const request = { profileId, sourceBucket, sourcePrefix, destPrefix, destBucket };
+    return invoke("copy_prefix", { request });
+  }
+
+  static async movePrefix(
+    profileId: string,
+    sourceBucket: string,
+    sourcePrefix: string,
+    destPrefix: string,
+    destBucket?: string
+  ): Promise<CopyPrefixResult> {
+    const request = { profileId, sourceBucket, sourcePrefix, destPrefix, destBucket };
+    return invoke("move_prefix", { request });
+  }
+
+  static async migrateObjects(request: MigrateObjectsRequest): Promise<MigrateObjectsResult> {
+    return invoke("migrate_objects", { request });
+  }
+
+  static async getObjectMetadata(
+    profileId: string,
+    bucketName: string,
+    objectKey: string,
+    versionId?: string
+  ): Promise<ObjectMetadata> {
+    const request = { profileId, bucketName, objectKey, versionId };
+    return invoke("get_object_metadata", { request });
+  }
+
+  static async updateObjectMetadata(
+    profileId: string,
+    bucketName: string,
+    objectKey: string,
+    changes: MetadataChanges
+  ): Promise<ObjectMetadata> {
+    const request = { profileId, bucketName, objectKey, changes };
+    return invoke("update_object_metadata", { request });
+  }
+
+  static async updatePrefixMetadata(
+    profileId: string,
+    bucketName: string,
+    prefix: string,
+    changes: MetadataChanges,
+    transferId?: string
+  ): Promise<UpdatePrefixMetadataResult> {
+    const request = { profileId, bucketName, prefix, changes, transferId };
+    return invoke("update_prefix_metadata", { request });
+  }
+
+  static async getObjectTags(
+    profileId: string,
+    bucketName: string,
+    objectKey: string,
+    versionId?: string
+  ): Promise<ObjectTag[]> {
+    const request = { profileId, bucketName, objectKey, versionId };
+    return invoke("get_object_tags", { request });
+  }
+
+  static async putObjectTags(
+    profileId: string,
+    bucketName: string,
+    objectKey: string,
+    tags: ObjectTag[],
+    versionId?: string
+  ): Promise<void> {
+    const request = { profileId, bucketName, objectKey, tags, versionId };
+    return invoke("put_object_tags", { request });
+  }
+
+  static async deleteObjectTags(
+    profileId: string,
+    bucketName: string,
+    objectKey: string,
+    versionId?: string
+  ): Promise<void> {
+    const request = { profileId, bucketName, objectKey, versionId };
+    return invoke("delete_object_tags", { request });
+  }
+
+  static async tagObjects(
+    profileId: string,
+    bucketName: string,
+    options: TagObjectsOptions
+  ): Promise<TagObjectsResult> {
+    const request = { profileId, bucketName, ...options };
+    return invoke("tag_objects", { request });
+  }
+
+  static async listObjectVersions(
+    profileId: string,
+    bucketName: string,
+    prefix?: string,
+    keyMarker?: string,
+    versionIdMarker?: string,
+    delimiter: string = "/"
+  ): Promise<ObjectVersionsPage> {
+    const request = { profileId, bucketName, prefix, delimiter, keyMarker, versionIdMarker };
+    return invoke("list_object_versions", { request });
+  }
+
+  static async restoreObjectVersion(
+    profileId: string,
+    bucketName: string,
+    objectKey: string,
+    versionId: string
+  ): Promise<ObjectMetadata> {
+    const request = { profileId, bucketName, objectKey, versionId };
+    return invoke("restore_object_version", { request });
+  }
+
+  static async undeleteObject(
+    profileId: string,
+    bucketName: string,
+    objectKey: string
+  ): Promise<ObjectMetadata> {
+    const request = { profileId, bucketName, objectKey };
+    return invoke("undelete_object", { request });
+  }
+
+  static async deleteObjectVersions(
+    profileId: string,
+    bucketName: string,
+    versions: ObjectVersionId[]
+  ): Promise<DeleteObjectVersionsResult> {
+    const request = { profileId, bucketName, versions };
+    return invoke("delete_object_versions", { request });
+  }
+
+  static async getBucketVersioning(
+    profileId: string,
+    bucketName: string
+  ): Promise<BucketVersioning> {
+    const request = { profileId, bucketName };
+    return invoke("get_bucket_versioning", { request });
+  }
+
+  static async setBucketVersioning(
+    profileId: string,
+    bucketName: string,
+    enabled: boolean
+  ): Promise<BucketVersioning> {
+    const request = { profileId, bucketName, enabled };
+    return invoke("set_bucket_versioning", { request });
+  }
+
   static async getPresignedUrl(
-    config: S3Config,
+    profileId: string,
     bucketName: string,
     objectKey: string,
     expiresInSeconds?: number
   ): Promise<string> {
     const request = {
-      config,
+      profileId,
       bucketName: bucketName,
       objectKey: objectKey,
       expiresInSeconds: expiresInSeconds,
@@ -76,14 +440,14 @@ export class S3Service {
   }
 
   static async uploadFile(
-    config: S3Config,
+    profileId: string,
     bucketName: string,
     objectKey: string,
     filePath: string,
     contentType?: string
   ): Promise<string> {
     const request = {
-      config,
+      profileId,
       bucketName: bucketName,
       objectKey: objectKey,
       filePath: filePath,
@@ -93,12 +457,12 @@ export class S3Service {
   }
 
   static async uploadFilesWithDialog(
-    config: S3Config,
+    profileId: string,
     bucketName: string,
     prefix?: string
   ): Promise<string[]> {
     return invoke("upload_files_with_dialog", {
-      config,
+      profileId,
       bucketName: bucketName,
       prefix: prefix || null,
     });
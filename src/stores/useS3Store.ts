@@ -1,13 +1,24 @@
 import { create } from "zustand";
-import { persist, createJSONStorage } from "zustand/middleware";
-import { S3Config, S3Object, BucketInfo, UploadProgress } from "../types/s3";
+import {
+  S3Config,
+  S3Object,
+  BucketInfo,
+  UploadProgress,
+  ProfileSummary,
+  DeleteObjectsResult,
+} from "../types/s3";
 import { S3Service } from "../services/s3Service";
+import { localizeError } from "../lib/errors";
+
+// 旧版本把完整的连接配置（包括密钥）保存在 localStorage 中，现在由后端保存配置
+localStorage.removeItem("snowy-oss-storage");
 
 interface S3Store {
-  // Configuration
-  config: S3Config | null;
-  setConfig: (config: S3Config) => void;
-  clearConfig: () => void;
+  // Profile
+  profile: ProfileSummary | null;
+  setProfile: (profile: ProfileSummary) => void;
+  loadProfile: () => Promise<void>;
+  clearProfile: () => void;
 
   // Connection status
   isConnected: boolean;
@@ -47,10 +58,10 @@ interface S3Store {
   clearUploadProgresses: () => void;
 
   // Actions
-  testConnection: (config: S3Config) => Promise<string>;
+  testConnection: (draft?: S3Config) => Promise<string>;
   fetchBuckets: () => Promise<void>;
   fetchObjects: () => Promise<void>;
-  deleteObjects: (objectKeys: string[]) => Promise<void>;
+  deleteObjects: (objectKeys: string[]) => Promise<DeleteObjectsResult>;
   uploadFilesWithDialog: () => Promise<string[]>;
 
   // UI state
@@ -63,293 +74,291 @@ interface S3Store {
 }
 
 export const useS3Store = create<S3Store>()(
-  persist(
-    (set, get) => ({
-      // Configuration
-      config: null,
-      setConfig: (config) => set({ config }),
-      clearConfig: () =>
+  (set, get) => ({
+    // Profile
+    profile: null,
+    setProfile: (profile) => set({ profile }),
+    loadProfile: async () => {
+      const profile = await S3Service.getSelectedProfile();
+      set({ profile });
+    },
+    clearProfile: () =>
+      set({
+        profile: null,
+        isConnected: false,
+        buckets: [],
+        objects: [],
+        selectedBucket: null,
+        selectedObjects: [],
+        currentPrefix: "",
+      }),
+
+    // Connection status
+    isConnected: false,
+    connectionError: null,
+    setConnectionStatus: (connected, error) =>
+      set({
+        isConnected: connected,
+        connectionError: error || null,
+      }),
+
+    // Buckets
+    buckets: [],
+    selectedBucket: null,
+    setBuckets: (buckets) => set({ buckets }),
+    selectBucket: (bucketName) =>
+      set({
+        selectedBucket: bucketName,
+        objects: [],
+        selectedObjects: [],
+        currentPrefix: "",
+      }),
+
+    // Objects
+    objects: [],
+    selectedObjects: [],
+    currentPrefix: "",
+    setObjects: (objects) => set({ objects }),
+    setSelectedObjects: (objectKeys) => set({ selectedObjects: objectKeys }),
+    toggleObjectSelection: (objectKey) => {
+      const { selectedObjects } = get();
+      const newSelection = selectedObjects.includes(objectKey)
+        ? selectedObjects.filter((key) => key !== objectKey)
+        : [...selectedObjects, objectKey];
+      set({ selectedObjects: newSelection });
+    },
+    setCurrentPrefix: (prefix) => set({ currentPrefix: prefix }),
+
+    // Loading states
+    loading: false,
+    error: null,
+    isLoadingBuckets: false,
+    isLoadingObjects: false,
+    setLoadingBuckets: (loading) => set({ isLoadingBuckets: loading }),
+    setLoadingObjects: (loading) => set({ isLoadingObjects: loading }),
+
+    // Upload state
+    uploadProgresses: [],
+    isUploading: false,
+    setUploadProgresses: (progresses) => set({ uploadProgresses: progresses }),
+    addUploadProgress: (progress) => {
+      const { uploadProgresses } = get();
+      set({
+        uploadProgresses: [...uploadProgresses, progress],
+        isUploading: true
+      });
+    },
+    updateUploadProgress: (filename, update) => {
+      const { uploadProgresses } = get();
+      const newProgresses = uploadProgresses.map(p =>
+        p.filename === filename ? { ...p, ...update } : p
+      );
+      set({
+        uploadProgresses: newProgresses,
+        isUploading: newProgresses.some(p => p.status === "uploading")
+      });
+    },
+    removeUploadProgress: (filename) => {
+      const { uploadProgresses } = get();
+      const newProgresses = uploadProgresses.filter(p => p.filename !== filename);
+      set({
+        uploadProgresses: newProgresses,
+        isUploading: newProgresses.some(p => p.status === "uploading")
+      });
+    },
+    clearUploadProgresses: () => set({ uploadProgresses: [], isUploading: false }),
+
+    // Actions
+    testConnection: async (draft) => {
+      try {
+        console.log("🔌 开始测试 OSS 连接");
+        const startTime = performance.now();
+        set({ loading: true, error: null });
+
+        const result = await S3Service.testConnection(
+          get().profile?.id,
+          draft
+        );
+
+        const endTime = performance.now();
+        console.log(
+          `✅ OSS 连接测试成功，耗时: ${(endTime - startTime).toFixed(0)}ms`
+        );
+
+        set({ isConnected: true, connectionError: null });
+        return result;
+      } catch (error) {
+        console.error("❌ OSS 连接测试失败:", error);
+        const errorMessage = localizeError(error, "连接失败");
         set({
-          config: null,
           isConnected: false,
-          buckets: [],
-          objects: [],
-          selectedBucket: null,
-          selectedObjects: [],
-          currentPrefix: "",
-        }),
-
-      // Connection status
-      isConnected: false,
-      connectionError: null,
-      setConnectionStatus: (connected, error) =>
-        set({
-          isConnected: connected,
-          connectionError: error || null,
-        }),
-
-      // Buckets
-      buckets: [],
-      selectedBucket: null,
-      setBuckets: (buckets) => set({ buckets }),
-      selectBucket: (bucketName) =>
-        set({
-          selectedBucket: bucketName,
-          objects: [],
-          selectedObjects: [],
-          currentPrefix: "",
-        }),
-
-      // Objects
-      objects: [],
-      selectedObjects: [],
-      currentPrefix: "",
-      setObjects: (objects) => set({ objects }),
-      setSelectedObjects: (objectKeys) => set({ selectedObjects: objectKeys }),
-      toggleObjectSelection: (objectKey) => {
-        const { selectedObjects } = get();
-        const newSelection = selectedObjects.includes(objectKey)
-          ? selectedObjects.filter((key) => key !== objectKey)
-          : [...selectedObjects, objectKey];
-        set({ selectedObjects: newSelection });
-      },
-      setCurrentPrefix: (prefix) => set({ currentPrefix: prefix }),
-
-      // Loading states
-      loading: false,
-      error: null,
-      isLoadingBuckets: false,
-      isLoadingObjects: false,
-      setLoadingBuckets: (loading) => set({ isLoadingBuckets: loading }),
-      setLoadingObjects: (loading) => set({ isLoadingObjects: loading }),
-
-      // Upload state
-      uploadProgresses: [],
-      isUploading: false,
-      setUploadProgresses: (progresses) => set({ uploadProgresses: progresses }),
-      addUploadProgress: (progress) => {
-        const { uploadProgresses } = get();
-        set({
-          uploadProgresses: [...uploadProgresses, progress],
-          isUploading: true
+          connectionError: errorMessage,
+          error: errorMessage,
         });
-      },
-      updateUploadProgress: (filename, update) => {
-        const { uploadProgresses } = get();
-        const newProgresses = uploadProgresses.map(p =>
-          p.filename === filename ? { ...p, ...update } : p
+        throw error;
+      } finally {
+        set({ loading: false });
+      }
+    },
+
+    fetchBuckets: async () => {
+      const { profile, isLoadingBuckets } = get();
+      if (!profile) {
+        throw new Error("请先配置 OSS 连接");
+      }
+
+      // 避免重复请求
+      if (isLoadingBuckets) {
+        console.log("⏳ 跳过重复的 fetchBuckets 请求");
+        return;
+      }
+
+      try {
+        console.log("🚀 开始获取 Bucket 列表");
+        const startTime = performance.now();
+        set({ isLoadingBuckets: true, error: null });
+
+        const buckets = await S3Service.listBuckets(profile.id);
+
+        const endTime = performance.now();
+        console.log(
+          `✅ Bucket 列表获取成功，耗时: ${(endTime - startTime).toFixed(
+            0
+          )}ms`
         );
-        set({
-          uploadProgresses: newProgresses,
-          isUploading: newProgresses.some(p => p.status === "uploading")
-        });
-      },
-      removeUploadProgress: (filename) => {
-        const { uploadProgresses } = get();
-        const newProgresses = uploadProgresses.filter(p => p.filename !== filename);
-        set({
-          uploadProgresses: newProgresses,
-          isUploading: newProgresses.some(p => p.status === "uploading")
-        });
-      },
-      clearUploadProgresses: () => set({ uploadProgresses: [], isUploading: false }),
-
-      // Actions
-      testConnection: async (config) => {
-        try {
-          console.log("🔌 开始测试 OSS 连接");
-          const startTime = performance.now();
-          set({ loading: true, error: null });
-
-          const result = await S3Service.testConnection(config);
-
-          const endTime = performance.now();
-          console.log(
-            `✅ OSS 连接测试成功，耗时: ${(endTime - startTime).toFixed(0)}ms`
-          );
-
-          set({ isConnected: true, connectionError: null });
-          return result;
-        } catch (error) {
-          console.error("❌ OSS 连接测试失败:", error);
-          const errorMessage =
-            error instanceof Error ? error.message : "连接失败";
-          set({
-            isConnected: false,
-            connectionError: errorMessage,
-            error: errorMessage,
-          });
-          throw error;
-        } finally {
-          set({ loading: false });
-        }
-      },
-
-      fetchBuckets: async () => {
-        const { config, isLoadingBuckets } = get();
-        if (!config) {
-          throw new Error("请先配置 OSS 连接");
-        }
-
-        // 避免重复请求
-        if (isLoadingBuckets) {
-          console.log("⏳ 跳过重复的 fetchBuckets 请求");
-          return;
-        }
-
-        try {
-          console.log("🚀 开始获取 Bucket 列表");
-          const startTime = performance.now();
-          set({ isLoadingBuckets: true, error: null });
-
-          const buckets = await S3Service.listBuckets(config);
-
-          const endTime = performance.now();
-          console.log(
-            `✅ Bucket 列表获取成功，耗时: ${(endTime - startTime).toFixed(
-              0
-            )}ms`
-          );
-
-          set({ buckets });
-        } catch (error) {
-          console.error("❌ 获取 Bucket 列表失败:", error);
-          const errorMessage =
-            error instanceof Error ? error.message : "获取 Bucket 列表失败";
-          set({ error: errorMessage });
-          throw error;
-        } finally {
-          set({ isLoadingBuckets: false });
-        }
-      },
-
-      fetchObjects: async () => {
-        const { config, selectedBucket, currentPrefix, isLoadingObjects } =
-          get();
-        if (!config || !selectedBucket) {
-          throw new Error("请先选择 Bucket");
-        }
-
-        // 避免重复请求
-        if (isLoadingObjects) {
-          console.log("⏳ 跳过重复的 fetchObjects 请求");
-          return;
-        }
-
-        try {
-          console.log(
-            `🚀 开始获取文件列表 - Bucket: ${selectedBucket}, Prefix: ${
-              currentPrefix || "根目录"
-            }`
-          );
-          const startTime = performance.now();
-          set({ isLoadingObjects: true, error: null });
-
-          const objects = await S3Service.listObjects(
-            config,
-            selectedBucket,
-            currentPrefix || undefined
-          );
-
-          const endTime = performance.now();
-          console.log(
-            `✅ 文件列表获取成功，共 ${objects.length} 个文件，耗时: ${(
-              endTime - startTime
-            ).toFixed(0)}ms`
-          );
-
-          set({ objects });
-        } catch (error) {
-          console.error("❌ 获取文件列表失败:", error);
-          const errorMessage =
-            error instanceof Error ? error.message : "获取文件列表失败";
-          set({ error: errorMessage });
-          throw error;
-        } finally {
-          set({ isLoadingObjects: false });
-        }
-      },
-
-      deleteObjects: async (objectKeys) => {
-        const { config, selectedBucket } = get();
-        if (!config || !selectedBucket) {
-          throw new Error("请先选择 Bucket");
-        }
-
-        try {
-          set({ loading: true, error: null });
-          await S3Service.deleteObjects(config, selectedBucket, objectKeys);
-
-          // 重新获取对象列表
-          const { fetchObjects } = get();
-          await fetchObjects();
-
-          // 清空选择
-          set({ selectedObjects: [] });
-        } catch (error) {
-          const errorMessage =
-            error instanceof Error ? error.message : "删除文件失败";
-          set({ error: errorMessage });
-          throw error;
-        } finally {
-          set({ loading: false });
-        }
-      },
-
-      uploadFilesWithDialog: async () => {
-        const { config, selectedBucket, currentPrefix } = get();
-        if (!config || !selectedBucket) {
-          throw new Error("请先选择 Bucket");
-        }
-
-        try {
-          console.log("🚀 开始文件上传对话框");
-          const startTime = performance.now();
-
-          const results = await S3Service.uploadFilesWithDialog(
-            config,
-            selectedBucket,
-            currentPrefix || undefined
-          );
-
-          const endTime = performance.now();
-          console.log(
-            `✅ 文件上传完成，耗时: ${(endTime - startTime).toFixed(0)}ms`
-          );
-
-          // 刷新文件列表
-          const { fetchObjects } = get();
-          await fetchObjects();
-
-          return results;
-        } catch (error) {
-          console.error("❌ 文件上传失败:", error);
-          const errorMessage =
-            error instanceof Error ? error.message : "文件上传失败";
-          set({ error: errorMessage });
-          throw error;
-        }
-      },
-
-      // UI state
-      showConfigModal: false,
-      showImagePreview: false,
-      previewImageUrl: null,
-      currentPreviewObject: null,
-      setShowConfigModal: (show) => set({ showConfigModal: show }),
-      setShowImagePreview: (show, url, object) =>
-        set({
-          showImagePreview: show,
-          previewImageUrl: url || null,
-          currentPreviewObject: object || null,
-        }),
-    }),
-    {
-      name: "snowy-oss-storage",
-      storage: createJSONStorage(() => localStorage),
-      partialize: (state) => ({
-        config: state.config,
+
+        set({ buckets });
+      } catch (error) {
+        console.error("❌ 获取 Bucket 列表失败:", error);
+        const errorMessage = localizeError(error, "获取 Bucket 列表失败");
+        set({ error: errorMessage });
+        throw error;
+      } finally {
+        set({ isLoadingBuckets: false });
+      }
+    },
+
+    fetchObjects: async () => {
+      const { profile, selectedBucket, currentPrefix, isLoadingObjects } =
+        get();
+      if (!profile || !selectedBucket) {
+        throw new Error("请先选择 Bucket");
+      }
+
+      // 避免重复请求
+      if (isLoadingObjects) {
+        console.log("⏳ 跳过重复的 fetchObjects 请求");
+        return;
+      }
+
+      try {
+        console.log(
+          `🚀 开始获取文件列表 - Bucket: ${selectedBucket}, Prefix: ${
+            currentPrefix || "根目录"
+          }`
+        );
+        const startTime = performance.now();
+        set({ isLoadingObjects: true, error: null });
+
+        const objects = await S3Service.listObjects(
+          profile.id,
+          selectedBucket,
+          currentPrefix || undefined
+        );
+
+        const endTime = performance.now();
+        console.log(
+          `✅ 文件列表获取成功，共 ${objects.length} 个文件，耗时: ${(
+            endTime - startTime
+          ).toFixed(0)}ms`
+        );
+
+        set({ objects });
+      } catch (error) {
+        console.error("❌ 获取文件列表失败:", error);
+        const errorMessage = localizeError(error, "获取文件列表失败");
+        set({ error: errorMessage });
+        throw error;
+      } finally {
+        set({ isLoadingObjects: false });
+      }
+    },
+
+    deleteObjects: async (objectKeys) => {
+      const { profile, selectedBucket } = get();
+      if (!profile || !selectedBucket) {
+        throw new Error("请先选择 Bucket");
+      }
+
+      try {
+        set({ loading: true, error: null });
+        const result = await S3Service.deleteObjects(
+          profile.id,
+          selectedBucket,
+          objectKeys
+        );
+
+        // 重新获取对象列表
+        const { fetchObjects } = get();
+        await fetchObjects();
+
+        // 删除失败的文件保持选中，方便重试
+        set({ selectedObjects: result.errors.map((error) => error.key) });
+        return result;
+      } catch (error) {
+        const errorMessage = localizeError(error, "删除文件失败");
+        set({ error: errorMessage });
+        throw error;
+      } finally {
+        set({ loading: false });
+      }
+    },
+
+    uploadFilesWithDialog: async () => {
+      const { profile, selectedBucket, currentPrefix } = get();
+      if (!profile || !selectedBucket) {
+        throw new Error("请先选择 Bucket");
+      }
+
+      try {
+        console.log("🚀 开始文件上传对话框");
+        const startTime = performance.now();
+
+        const results = await S3Service.uploadFilesWithDialog(
+          profile.id,
+          selectedBucket,
+          currentPrefix || undefined
+        );
+
+        const endTime = performance.now();
+        console.log(
+          `✅ 文件上传完成，耗时: ${(endTime - startTime).toFixed(0)}ms`
+        );
+
+        // 刷新文件列表
+        const { fetchObjects } = get();
+        await fetchObjects();
+
+        return results;
+      } catch (error) {
+        console.error("❌ 文件上传失败:", error);
+        const errorMessage = localizeError(error, "文件上传失败");
+        set({ error: errorMessage });
+        throw error;
+      }
+    },
+
+    // UI state
+    showConfigModal: false,
+    showImagePreview: false,
+    previewImageUrl: null,
+    currentPreviewObject: null,
+    setShowConfigModal: (show) => set({ showConfigModal: show }),
+    setShowImagePreview: (show, url, object) =>
+      set({
+        showImagePreview: show,
+        previewImageUrl: url || null,
+        currentPreviewObject: object || null,
       }),
-    }
-  )
+  })
 );
@@ -5,6 +5,103 @@ export interface S3Config {
   secret_access_key: string;
   bucket?: string;
   custom_path?: string;
+  addressing_style?: AddressingStyle;
+  credential_source?: CredentialSource;
+  compatibility?: Compatibility;
+  network?: NetworkSettings;
+}
+
+export type CredentialSource =
+  | { type: "static" }
+  | { type: "sessionToken"; sessionToken: string }
+  | {
+      type: "assumeRole";
+      roleArn: string;
+      sessionName?: string;
+      externalId?: string;
+      mfaSerial?: string;
+      mfaCode?: string;
+      durationSeconds?: number;
+      stsEndpoint?: string;
+    }
+  | { type: "environment" }
+  | { type: "profile"; name: string }
+  | { type: "anonymous" };
+
+export type AddressingStyle = "path" | "virtual";
+
+export type ChecksumMode = "when_supported" | "when_required";
+
+export interface Compatibility {
+  request_checksum: ChecksumMode;
+  unsigned_payload: boolean;
+  dual_stack: boolean;
+  accelerate: boolean;
+}
+
+export type RetryMode = "standard" | "adaptive";
+
+export interface NetworkSettings {
+  connect_timeout_secs?: number | null;
+  read_timeout_secs?: number | null;
+  operation_timeout_secs?: number | null;
+  retry_mode?: RetryMode;
+  max_attempts?: number | null;
+  max_requests_per_second?: number | null;
+  proxy?: ProxySettings | null;
+  ca_certificates?: string | null;
+  pinned_certificate?: string | null;
+  accept_invalid_certs?: boolean;
+}
+
+export interface ProxySettings {
+  url: string;
+  username?: string | null;
+  password?: string | null;
+  no_proxy?: string[];
+}
+
+export interface CompatibilityDefaults {
+  addressing_style: AddressingStyle;
+  compatibility: Compatibility;
+}
+
+export type ProviderQuirk =
+  | "virtualHostOnly"
+  | "bucketNameAppId"
+  | "regionAuto"
+  | "legacyChecksum"
+  | "dualStackAccelerate";
+
+export interface ProviderRegion {
+  id: string;
+  name: string;
+}
+
+export interface ProviderPreset {
+  id: string;
+  name: string;
+  endpointTemplate: string;
+  parameters: string[];
+  regions: ProviderRegion[];
+  defaultRegion: string;
+  customRegion: boolean;
+  addressingStyle: AddressingStyle;
+  compatibility: Compatibility;
+  quirks: ProviderQuirk[];
+}
+
+export interface BuildProviderConfigRequest {
+  providerId: string;
+  region?: string;
+  parameters?: Record<string, string>;
+  bucket?: string;
+}
+
+export interface ProviderMatch {
+  providerId: string;
+  region?: string;
+  parameters: Record<string, string>;
 }
 
 export interface S3Object {
@@ -13,6 +110,50 @@ export interface S3Object {
   last_modified?: string;
   etag?: string;
   storage_class?: string;
+  tags?: ObjectTag[];
+}
+
+export interface ObjectTag {
+  key: string;
+  value: string;
+}
+
+export interface TagFilter {
+  key: string;
+  value?: string;
+}
+
+export interface ListTagOptions {
+  includeTags?: boolean;
+  tagFilter?: TagFilter;
+}
+
+export interface TagObjectsOptions {
+  objectKeys?: string[];
+  prefix?: string;
+  tags?: ObjectTag[];
+  removeKeys?: string[];
+  replace?: boolean;
+  transferId?: string;
+}
+
+export interface TagObjectsResult {
+  transferId: string;
+  objectCount: number;
+  updatedCount: number;
+  failed: ObjectError[];
+}
+
+export interface S3Folder {
+  prefix: string;
+  name: string;
+}
+
+export interface ListObjectsPage {
+  objects: S3Object[];
+  folders: S3Folder[];
+  isTruncated: boolean;
+  nextContinuationToken?: string;
 }
 
 export interface BucketInfo {
@@ -20,6 +161,139 @@ export interface BucketInfo {
   creation_date?: string;
 }
 
+export interface CopyObjectOptions {
+  destBucket?: string;
+  overwrite?: boolean;
+  transferId?: string;
+}
+
+export interface DeleteObjectError {
+  key: string;
+  versionId?: string;
+  code?: string;
+  message?: string;
+}
+
+export interface DeleteObjectsResult {
+  deleted: string[];
+  errors: DeleteObjectError[];
+}
+
+export interface CopyObjectError {
+  sourceKey: string;
+  destKey: string;
+  error: AppError;
+}
+
+export interface CopyPrefixResult {
+  transferId: string;
+  objectCount: number;
+  totalSize: number;
+  copiedCount: number;
+  failed: CopyObjectError[];
+  rolledBack: boolean;
+  rollbackErrors: DeleteObjectError[];
+  deletedCount: number;
+  deleteErrors: DeleteObjectError[];
+}
+
+export interface TransferLocation {
+  profileId?: string;
+  bucketName: string;
+  prefix?: string;
+}
+
+export interface MigrateObjectsRequest {
+  source: TransferLocation;
+  destination: TransferLocation;
+  partSize?: number;
+  maxConcurrency?: number;
+  transferId?: string;
+}
+
+export interface MigrateObjectsResult {
+  transferId: string;
+  objectCount: number;
+  totalSize: number;
+  transferredCount: number;
+  skippedCount: number;
+  failed: CopyObjectError[];
+}
+
+export interface ObjectMetadata {
+  key: string;
+  size: number;
+  lastModified?: string;
+  etag?: string;
+  versionId?: string;
+  storageClass?: string;
+  contentType?: string;
+  cacheControl?: string;
+  contentDisposition?: string;
+  contentEncoding?: string;
+  contentLanguage?: string;
+  expires?: string;
+  userMetadata: Record<string, string>;
+  serverSideEncryption?: string;
+  sseKmsKeyId?: string;
+  bucketKeyEnabled?: boolean;
+}
+
+export interface MetadataChanges {
+  contentType?: string;
+  cacheControl?: string;
+  contentDisposition?: string;
+  contentEncoding?: string;
+  contentLanguage?: string;
+  userMetadata?: Record<string, string>;
+}
+
+export interface ObjectError {
+  key: string;
+  error: AppError;
+}
+
+export interface UpdatePrefixMetadataResult {
+  transferId: string;
+  objectCount: number;
+  updatedCount: number;
+  failed: ObjectError[];
+}
+
+export interface ObjectVersion {
+  key: string;
+  versionId?: string;
+  isLatest: boolean;
+  isDeleteMarker: boolean;
+  size: number;
+  lastModified?: string;
+  etag?: string;
+  storageClass?: string;
+}
+
+export interface ObjectVersionsPage {
+  versions: ObjectVersion[];
+  folders: S3Folder[];
+  isTruncated: boolean;
+  nextKeyMarker?: string;
+  nextVersionIdMarker?: string;
+}
+
+export interface ObjectVersionId {
+  key: string;
+  versionId: string;
+}
+
+export interface DeleteObjectVersionsResult {
+  deletedCount: number;
+  errors: DeleteObjectError[];
+}
+
+export interface BucketVersioning {
+  status: "enabled" | "suspended" | "disabled";
+  mfaDelete: boolean;
+}
+
 export interface FilePreview {
   url: string;
   type: "image" | "video" | "text" | "other";
@@ -27,7 +301,7 @@ export interface FilePreview {
 }
 
 export interface UploadFileRequest {
-  config: S3Config;
+  profileId?: string;
   bucket_name: string;
   object_key: string;
   file_path: string;
@@ -49,3 +323,170 @@ export interface UploadResult {
   filename: string;
   objectKey: string;
 }
+
+export interface TransferProgress {
+  transferId: string;
+  direction: "upload" | "download" | "copy" | "batch";
+  status: "running" | "completed" | "failed";
+  bucketName: string;
+  objectKey: string;
+  bytesDone: number;
+  totalBytes: number;
+  bytesPerSecond: number;
+  etaSeconds?: number;
+  error?: AppError;
+}
+
+export interface ProfileInput {
+  name: string;
+  config: S3Config;
+}
+
+export interface ProfileSummary {
+  id: string;
+  name: string;
+  endpoint: string;
+  region: string;
+  accessKeyHint: string;
+  bucket?: string;
+  customPath?: string;
+  addressingStyle: AddressingStyle;
+  compatibility: Compatibility;
+  network: NetworkSettings;
+  selected: boolean;
+}
+
+export interface CredentialStatus {
+  backend: "keyring" | "vault";
+  locked: boolean;
+  initialized: boolean;
+  autoLockSeconds: number;
+}
+
+export type ImportSource = "aws" | "rclone" | "s3cmd";
+export type ExportFormat = "aws" | "rclone";
+export type ConflictStrategy = "skip" | "rename" | "overwrite";
+
+export interface ImportCandidate {
+  name: string;
+  endpoint: string;
+  region: string;
+  accessKeyHint: string;
+  addressingStyle: AddressingStyle;
+  conflict: boolean;
+  problem?: string;
+}
+
+export interface ImportProfilesResult {
+  imported: ProfileSummary[];
+  skipped: { name: string; reason: string }[];
+}
+
+export interface ExportProfilesResult {
+  path: string;
+  skipped: { name: string; reason: string }[];
+}
+
+export type DiagnosticCheck =
+  | "url"
+  | "dns"
+  | "connect"
+  | "tls"
+  | "clock"
+  | "signature"
+  | "region"
+  | "addressing";
+
+export type DiagnosticStatus = "passed" | "warning" | "failed" | "skipped";
+
+export interface DiagnosticStep {
+  check: DiagnosticCheck;
+  status: DiagnosticStatus;
+  detail: string;
+  hint?: string;
+  durationMs: number;
+}
+
+export interface DiagnosticReport {
+  passed: boolean;
+  steps: DiagnosticStep[];
+}
+
+export type Capability = "allowed" | "denied" | "unknown";
+
+export interface BucketPermissions {
+  list: Capability;
+  read: Capability;
+  write: Capability;
+  delete: Capability;
+  readAcl: Capability;
+  readPolicy: Capability;
+  leftoverKey?: string;
+}
+
+export type ErrorCode =
+  | "cancelled"
+  | "accessDenied"
+  | "invalidCredentials"
+  | "notFound"
+  | "conflict"
+  | "throttled"
+  | "network"
+  | "timeout"
+  | "invalidInput"
+  | "locked"
+  | "wrongPassword"
+  | "io"
+  | "service"
+  | "internal";
+
+export interface AppError {
+  code: ErrorCode;
+  message: string;
+  s3Code?: string;
+  requestId?: string;
+  status?: number;
+  retryable: boolean;
+}
+
+export type LogLevel = "trace" | "debug" | "info" | "warn" | "error";
+
+export interface LogEntry {
+  timestamp: string;
+  level: LogLevel;
+  target: string;
+  operation?: string;
+  message: string;
+  fields: Record<string, unknown>;
+}
+
+export interface LogQuery {
+  level?: LogLevel;
+  operation?: string;
+  limit?: number;
+}
+
+export interface HttpHeader {
+  name: string;
+  value: string;
+}
+
+export interface HttpExchange {
+  id: number;
+  startedAt: string;
+  durationMs: number;
+  method: string;
+  url: string;
+  requestHeaders: HttpHeader[];
+  status: number | null;
+  responseHeaders: HttpHeader[];
+  responseBody: string | null;
+  bodyTruncated: boolean;
+  error: string | null;
+}
+
+export interface HttpInspectorState {
+  enabled: boolean;
+  capacity: number;
+  exchanges: HttpExchange[];
+}